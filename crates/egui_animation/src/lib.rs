@@ -99,6 +99,73 @@ pub fn animate_position(
     position - scroll_offset
 }
 
+#[derive(Debug, Clone, Copy)]
+struct SpringState {
+    position: Pos2,
+    velocity: Vec2,
+    time: f64,
+}
+
+/// Animate a position with a critically damped spring that settles in about `time` seconds.
+/// Unlike [animate_position], changing the target while the animation is running keeps the current
+/// velocity, so interrupted animations smoothly change direction instead of starting over.
+/// Like [animate_position], it will try to correct for scrolling.
+pub fn animate_position_spring(
+    ui: &mut Ui,
+    id: impl Hash + Sized,
+    value: Pos2,
+    time: f32,
+    scroll_correction: bool,
+) -> Pos2 {
+    let id = Id::new(id).with("animate_position_spring");
+
+    let scroll_offset = if scroll_correction {
+        current_scroll_delta(ui)
+    } else {
+        Vec2::ZERO
+    };
+
+    let value = value + scroll_offset;
+    let now = ui.input(|i| i.time);
+
+    let position = ui.ctx().memory_mut(|mem| {
+        let state = mem.data.get_temp_mut_or_insert_with(id, || SpringState {
+            position: value,
+            velocity: Vec2::ZERO,
+            time: now,
+        });
+        // Don't jump if the animation wasn't shown for a while
+        let dt = ((now - state.time) as f32).clamp(0.0, 0.1);
+        state.time = now;
+
+        if time <= 0.0 {
+            state.position = value;
+            state.velocity = Vec2::ZERO;
+        } else {
+            // A critically damped spring is within 1% of the target after 6.6 / omega seconds
+            let omega = 6.6 / time;
+            let offset = state.position - value;
+            let decay = (-omega * dt).exp();
+            let offset_rate = state.velocity + offset * omega;
+            let offset = (offset + offset_rate * dt) * decay;
+            state.velocity = (state.velocity - offset_rate * omega * dt) * decay;
+            state.position = value + offset;
+
+            if offset.length() < 0.1 && state.velocity.length() < 1.0 {
+                state.position = value;
+                state.velocity = Vec2::ZERO;
+            }
+        }
+        state.position
+    });
+
+    if position != value {
+        ui.ctx().request_repaint();
+    }
+
+    position - scroll_offset
+}
+
 /// A wrapper around [animate_position] that animates the position of a child ui.
 pub fn animate_ui_translation(
     ui: &mut Ui,
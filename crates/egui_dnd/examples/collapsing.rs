@@ -0,0 +1,32 @@
+use eframe::egui;
+use egui::SidePanel;
+use egui_dnd::dnd;
+
+pub fn main() -> eframe::Result<()> {
+    let mut sections = vec![
+        ("Transform", 3),
+        ("Material", 5),
+        ("Physics", 2),
+        ("Audio", 4),
+    ];
+
+    eframe::run_simple_native(
+        "DnD Collapsing Example",
+        Default::default(),
+        move |ctx, _frame| {
+            SidePanel::left("inspector").show(ctx, |ui| {
+                dnd(ui, "sections").show_collapsing_vec(
+                    &mut sections,
+                    |ui, (name, _), _state| {
+                        ui.strong(*name);
+                    },
+                    |ui, (name, properties)| {
+                        for property in 0..*properties {
+                            ui.label(format!("{name} property {property}"));
+                        }
+                    },
+                );
+            });
+        },
+    )
+}
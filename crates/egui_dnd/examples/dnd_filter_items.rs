@@ -31,6 +31,7 @@ fn filter_by_skipping_items(ui: &mut Ui, filter: &str, items: &mut Vec<ItemType>
 /// use a virtual list to improve performance with a lot of items
 #[allow(clippy::ptr_arg)]
 fn filter_by_filtering_source_list(ui: &mut Ui, filter: &str, items: &mut Vec<ItemType>) {
+    let item_count = items.len();
     let mut filtered = items
         .iter_mut()
         // We enumerate so we can later get the original index
@@ -38,18 +39,24 @@ fn filter_by_filtering_source_list(ui: &mut Ui, filter: &str, items: &mut Vec<It
         .filter(|(_, item)| item.number.to_string().contains(filter))
         .collect::<Vec<_>>();
 
-    let response = dnd(ui, "dnd").show(filtered.iter_mut(), |ui, (_, item), handle, _dragging| {
-        ui.horizontal(|ui| {
-            handle.ui(ui, |ui| {
-                ui.label(&item.number.to_string());
+    // The filter may change while dragging, the drag continues against the filtered items
+    let response = dnd(ui, "dnd").filtered(true).show(
+        filtered.iter_mut(),
+        |ui, (_, item), handle, _dragging| {
+            ui.horizontal(|ui| {
+                handle.ui(ui, |ui| {
+                    ui.label(&item.number.to_string());
+                });
             });
-        });
-    });
+        },
+    );
 
     if let Some(update) = response.final_update() {
         // Get the index the item had in the original vec
         let (original_index_from, _) = filtered[update.from];
-        let (original_index_to, _) = filtered[update.to];
+        let original_index_to = filtered
+            .get(update.to)
+            .map_or(item_count, |(index, _)| *index);
         // Get the original indices of the items for the update
         shift_vec(original_index_from, original_index_to, items);
     }
@@ -0,0 +1,68 @@
+use eframe::egui;
+use egui::{CentralPanel, DragAndDrop, SidePanel};
+use egui_dnd::{dnd, DndPayload};
+
+// The dragged item is published as an egui DragAndDrop payload, so any drop target can accept it.
+// This is how you would open items from a list as tabs in egui_tiles or egui_dock as well:
+// check for a DndPayload where the docking crate shows its drop targets.
+pub fn main() -> eframe::Result<()> {
+    let mut items = vec!["alfred", "bernhard", "christian", "dietrich"];
+    let mut tabs: Vec<&str> = Vec::new();
+    let mut selected_tab = 0;
+
+    eframe::run_simple_native(
+        "DnD Drag To Tabs Example",
+        Default::default(),
+        move |ctx, _frame| {
+            let response = SidePanel::left("items")
+                .show(ctx, |ui| {
+                    ui.label("Reorder the items or drag them to the right to open them as tabs");
+                    dnd(ui, "dnd_example").show(items.iter(), |ui, item, handle, _state| {
+                        handle.ui(ui, |ui| {
+                            ui.label(*item);
+                        });
+                    })
+                })
+                .inner;
+
+            let mut opened_tab = false;
+            CentralPanel::default().show(ctx, |ui| {
+                let hovering = DragAndDrop::payload::<DndPayload>(ctx).is_some()
+                    && ui.rect_contains_pointer(ui.max_rect());
+                if hovering && ui.input(|i| i.pointer.any_released()) {
+                    if let Some(payload) = DragAndDrop::take_payload::<DndPayload>(ctx) {
+                        let item = items[payload.index];
+                        if let Some(existing) = tabs.iter().position(|tab| *tab == item) {
+                            selected_tab = existing;
+                        } else {
+                            tabs.push(item);
+                            selected_tab = tabs.len() - 1;
+                        }
+                        opened_tab = true;
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    for (idx, tab) in tabs.iter().enumerate() {
+                        if ui.selectable_label(idx == selected_tab, *tab).clicked() {
+                            selected_tab = idx;
+                        }
+                    }
+                });
+                ui.separator();
+                if hovering {
+                    ui.label("Drop to open as a tab");
+                } else if let Some(tab) = tabs.get(selected_tab) {
+                    ui.heading(*tab);
+                } else {
+                    ui.label("No tabs open");
+                }
+            });
+
+            // Only reorder the list if the item wasn't dropped on the tabs
+            if response.is_drag_finished() && !opened_tab {
+                response.update_vec(&mut items);
+            }
+        },
+    )
+}
@@ -1,5 +1,5 @@
 use eframe::egui;
-use egui::{CentralPanel, Id, ScrollArea};
+use egui::{CentralPanel, ScrollArea};
 use egui_dnd::dnd;
 use egui_infinite_scroll::InfiniteScroll;
 
@@ -16,26 +16,22 @@ pub fn main() -> eframe::Result<()> {
         move |ctx, _frame| {
             CentralPanel::default().show(ctx, |ui| {
                 ScrollArea::vertical().show(ui, |ui| {
-                    let response = dnd(ui, "dnd").show_custom(|ui, iter| {
-                        infinite_scroll.ui(ui, 10, |ui, index, item| {
-                            iter.next(ui, Id::new(*item), index, true, |ui, item_handle| {
-                                item_handle.ui(ui, |ui, handle, state| {
-                                    ui.horizontal(|ui| {
-                                        handle.ui(ui, |ui| {
-                                            if state.dragged {
-                                                ui.label("dragging");
-                                            } else {
-                                                ui.label("drag");
-                                            }
-                                        });
-                                        ui.label(format!("Item {}", item));
-                                    });
-                                })
+                    dnd(ui, "dnd").show_infinite_scroll(
+                        &mut infinite_scroll,
+                        10,
+                        |ui, item, handle, state| {
+                            ui.horizontal(|ui| {
+                                handle.ui(ui, |ui| {
+                                    if state.dragged {
+                                        ui.label("dragging");
+                                    } else {
+                                        ui.label("drag");
+                                    }
+                                });
+                                ui.label(format!("Item {}", item));
                             });
-                        });
-                    });
-
-                    response.update_vec(&mut infinite_scroll.items);
+                        },
+                    );
                 });
             });
         },
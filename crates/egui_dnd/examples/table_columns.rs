@@ -0,0 +1,95 @@
+use eframe::egui;
+use egui::{CentralPanel, DragAndDrop, Id, SidePanel};
+use egui_dnd::{dnd, DndPayload};
+use egui_extras::{Column, TableBuilder};
+
+/// The payload of a row dragged out of the table, the index in the available columns
+struct AvailableColumn(usize);
+
+// Rows of the table are dragged with egui's DragAndDrop, which the Dnd list accepts with
+// accept_payload. Items of the list publish a DndPayload, which the table accepts when it's
+// released over it. This way columns can be moved between "available" and "visible".
+pub fn main() -> eframe::Result<()> {
+    let mut available = vec![
+        ("size", "Size of the file"),
+        ("owner", "User owning the file"),
+        ("created", "When the file was created"),
+        ("permissions", "Who may read and write the file"),
+    ];
+    let mut visible = vec![
+        ("name", "Name of the file"),
+        ("modified", "When the file was last modified"),
+    ];
+
+    eframe::run_simple_native(
+        "DnD Table Columns Example",
+        Default::default(),
+        move |ctx, _frame| {
+            let response = SidePanel::right("visible_columns")
+                .show(ctx, |ui| {
+                    ui.heading("Visible columns");
+                    let response = dnd(ui, "visible_columns")
+                        .accept_payload::<AvailableColumn>()
+                        .show(visible.iter(), |ui, (name, _), handle, _state| {
+                            handle.ui(ui, |ui| {
+                                ui.label(*name);
+                            });
+                        });
+                    ui.allocate_space(ui.available_size());
+                    response
+                })
+                .inner;
+
+            let mut removed_column = false;
+            CentralPanel::default().show(ctx, |ui| {
+                ui.heading("Available columns");
+                let table_rect = ui.available_rect_before_wrap();
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .column(Column::auto().at_least(100.0))
+                    .column(Column::remainder())
+                    .header(20.0, |mut header| {
+                        header.col(|ui| {
+                            ui.strong("Column");
+                        });
+                        header.col(|ui| {
+                            ui.strong("Description");
+                        });
+                    })
+                    .body(|mut body| {
+                        for (idx, (name, description)) in available.iter().enumerate() {
+                            body.row(20.0, |mut row| {
+                                row.col(|ui| {
+                                    ui.dnd_drag_source(
+                                        Id::new(("available_column", name)),
+                                        AvailableColumn(idx),
+                                        |ui| ui.label(*name),
+                                    );
+                                });
+                                row.col(|ui| {
+                                    ui.label(*description);
+                                });
+                            });
+                        }
+                    });
+
+                // Move items of the list back to the table when they are released over it
+                let hovering = DragAndDrop::payload::<DndPayload>(ctx)
+                    .is_some_and(|payload| payload.is_from("visible_columns"))
+                    && ui.rect_contains_pointer(table_rect);
+                if hovering && ui.input(|i| i.pointer.any_released()) {
+                    if let Some(payload) = DragAndDrop::take_payload::<DndPayload>(ctx) {
+                        available.push(visible.remove(payload.index));
+                        removed_column = true;
+                    }
+                }
+            });
+
+            if let Some((index, payload)) = response.dropped_payload::<AvailableColumn>() {
+                visible.insert(index, available.remove(payload.0));
+            } else if response.is_drag_finished() && !removed_column {
+                response.update_vec(&mut visible);
+            }
+        },
+    )
+}
@@ -0,0 +1,159 @@
+use egui::accesskit::{Action, ActionData, CustomAction, Live, Role};
+use egui::{Id, Ui};
+
+use crate::DragDropResponse;
+
+const MOVE_UP_ACTION: i32 = 0;
+const MOVE_DOWN_ACTION: i32 = 1;
+const MOVE_TO_TOP_ACTION: i32 = 2;
+const MOVE_TO_BOTTOM_ACTION: i32 = 3;
+
+/// Creates the list node the item nodes are added to.
+/// Should be called before showing the items.
+pub(crate) fn list_node(ui: &Ui, list_id: Id) {
+    ui.ctx().accesskit_node_builder(list_id, |builder| {
+        builder.set_role(Role::List);
+    });
+}
+
+/// Exposes the handle with the given focus id as a list item with actions to move it.
+/// The position is exposed as the numeric value, so setting the value moves the item there.
+pub(crate) fn item_node(ui: &Ui, focus_id: Id, idx: usize, item_count: usize, grabbed: bool) {
+    ui.ctx().accesskit_node_builder(focus_id, |builder| {
+        builder.set_role(Role::ListItem);
+        builder.set_position_in_set(idx + 1);
+        if item_count > idx {
+            builder.set_size_of_set(item_count);
+            builder.set_numeric_value((idx + 1) as f64);
+            builder.set_min_numeric_value(1.0);
+            builder.set_max_numeric_value(item_count as f64);
+        }
+        // AccessKit has no grabbed state, so we describe it instead
+        builder.set_description(if grabbed {
+            "Grabbed, use the arrow keys to move, press space to drop or escape to cancel"
+        } else {
+            "Press space to pick up, then use the arrow keys to move"
+        });
+        builder.add_action(Action::CustomAction);
+        builder.add_action(Action::SetValue);
+        builder.set_custom_actions(vec![
+            CustomAction {
+                id: MOVE_UP_ACTION,
+                description: "Move item up".into(),
+            },
+            CustomAction {
+                id: MOVE_DOWN_ACTION,
+                description: "Move item down".into(),
+            },
+            CustomAction {
+                id: MOVE_TO_TOP_ACTION,
+                description: "Move item to the top".into(),
+            },
+            CustomAction {
+                id: MOVE_TO_BOTTOM_ACTION,
+                description: "Move item to the bottom".into(),
+            },
+        ]);
+    });
+}
+
+/// Returns the slot the item should be moved to, if a move action was requested.
+/// Like the target of a keyboard drag, the slot doesn't count the item itself.
+pub(crate) fn requested_move(
+    ui: &Ui,
+    focus_id: Id,
+    idx: usize,
+    item_count: usize,
+) -> Option<usize> {
+    let last = item_count.saturating_sub(1);
+    ui.input(|i| {
+        let custom_action = i
+            .accesskit_action_requests(focus_id, Action::CustomAction)
+            .find_map(|request| match request.data {
+                Some(ActionData::CustomAction(MOVE_UP_ACTION)) => Some(idx.saturating_sub(1)),
+                Some(ActionData::CustomAction(MOVE_DOWN_ACTION)) => Some(idx + 1),
+                Some(ActionData::CustomAction(MOVE_TO_TOP_ACTION)) => Some(0),
+                Some(ActionData::CustomAction(MOVE_TO_BOTTOM_ACTION)) => Some(last),
+                _ => None,
+            });
+        custom_action.or_else(|| {
+            i.accesskit_action_requests(focus_id, Action::SetValue)
+                .find_map(|request| match request.data {
+                    // The value is the position, starting at 1
+                    Some(ActionData::NumericValue(position)) => {
+                        Some((position.round().max(1.0) as usize - 1).min(last))
+                    }
+                    _ => None,
+                })
+        })
+    })
+}
+
+/// Announces pickups, position changes and drops to screen readers via a live region
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Announcer {
+    /// The position of the dragged item when it was last announced
+    position: Option<usize>,
+    message: Option<String>,
+}
+
+impl Announcer {
+    pub fn update(&mut self, response: &DragDropResponse, item_count: usize) {
+        // The index the item would end up at, see [crate::utils::shift_vec]
+        let position = response.update.as_ref().map(|update| {
+            if update.to > update.from {
+                update.to - 1
+            } else {
+                update.to
+            }
+        });
+
+        if response.is_drag_finished() {
+            if let Some(position) = position {
+                self.message = Some(format!(
+                    "Dropped item at position {} of {item_count}",
+                    position + 1
+                ));
+            }
+            self.position = None;
+        } else if !response.is_dragging() && self.position.is_some() {
+            // The drag was cancelled
+            self.message = Some(match position {
+                Some(position) => format!(
+                    "Move cancelled, item returned to position {} of {item_count}",
+                    position + 1
+                ),
+                None => "Move cancelled".to_owned(),
+            });
+            self.position = None;
+        } else if let (true, Some(position), Some(update)) =
+            (response.is_dragging(), position, &response.update)
+        {
+            match self.position {
+                None => {
+                    self.message =
+                        Some(format!("Grabbed item {} of {item_count}", update.from + 1));
+                }
+                Some(last_position) if last_position != position => {
+                    self.message = Some(format!(
+                        "Moved to position {} of {item_count}",
+                        position + 1
+                    ));
+                }
+                _ => {}
+            }
+            self.position = Some(position);
+        }
+    }
+
+    /// Shows the live region containing the last announcement
+    pub fn ui(&self, ui: &Ui, id: Id) {
+        ui.ctx().accesskit_node_builder(id, |builder| {
+            builder.set_role(Role::Status);
+            builder.set_live(Live::Polite);
+            if let Some(message) = &self.message {
+                builder.set_name(message.clone());
+            }
+        });
+    }
+}
@@ -0,0 +1,187 @@
+use std::hash::Hash;
+
+use egui::{Align, DragAndDrop, Frame, Id, Label, Layout, Ui, WidgetText};
+
+use crate::utils::shift_vec;
+use crate::{dnd, DndPayload, DragDropResponse};
+
+/// Day columns with event cards that can be moved between the days. Created with [dnd_calendar].
+pub struct Calendar<'a> {
+    ui: &'a mut Ui,
+    id_source: Id,
+}
+
+/// An event card that was moved by [Calendar::show]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarMove {
+    /// The day the card was dragged from
+    pub from_day: usize,
+    /// The day the card was dropped on, the same as `from_day` if it was reordered within its day
+    pub to_day: usize,
+    /// The index the card ends up at in `to_day`
+    pub index: usize,
+}
+
+/// The response of [Calendar::show]
+pub struct CalendarResponse {
+    /// The responses of the lists of the days, one for each day
+    pub days: Vec<DragDropResponse>,
+    /// The responses of the day columns, including their headers
+    pub columns: Vec<egui::Response>,
+    /// The card that was moved this frame
+    pub moved: Option<CalendarMove>,
+}
+
+/// Shows a column for each day, with a header and the event cards of the day below it, like the
+/// week view of a calendar. Cards are reordered within their day and moved to other days by
+/// dragging them, the other days accept them like with [crate::Dnd::accept_payload]. A day
+/// without events shows a placeholder that cards can be dropped onto. The days are only changed
+/// once a card is dropped, like with [crate::CommitStrategy::OnDrop].
+/// Example usage:
+/// ```rust no_run
+/// use eframe::egui;
+/// use egui::CentralPanel;
+/// use egui_dnd::dnd_calendar;
+///
+/// pub fn main() -> eframe::Result<()> {
+///     let mut days = vec![vec!["Standup", "Review"], vec!["Planning"], vec![]];
+///     let names = ["Mon", "Tue", "Wed"];
+///
+///     eframe::run_simple_native("DnD Calendar Example", Default::default(), move |ctx, _frame| {
+///         CentralPanel::default().show(ctx, |ui| {
+///             let response = dnd_calendar(ui, "week").show(
+///                 &mut days,
+///                 |day| names[day].into(),
+///                 |ui, event| {
+///                     ui.label(*event);
+///                 },
+///             );
+///             if let Some(moved) = response.moved {
+///                 println!("moved from {} to {}", names[moved.from_day], names[moved.to_day]);
+///             }
+///         });
+///     })
+/// }
+/// ```
+pub fn dnd_calendar(ui: &mut Ui, id_source: impl Hash) -> Calendar {
+    Calendar {
+        ui,
+        id_source: Id::new(id_source),
+    }
+}
+
+impl Calendar<'_> {
+    /// Shows the days with the headers returned by `header`, which is called with the index of
+    /// the day, and the cards with `card`. Cards are identified by their hash, like the items of
+    /// [crate::Dnd::show_vec], so a card needs to be unique within the whole calendar.
+    pub fn show<T: Hash>(
+        self,
+        days: &mut [Vec<T>],
+        mut header: impl FnMut(usize) -> WidgetText,
+        mut card: impl FnMut(&mut Ui, &T),
+    ) -> CalendarResponse {
+        let Calendar { ui, id_source } = self;
+        let day_source = |day: usize| id_source.with("dnd_calendar_day").with(day);
+        let dragging = DragAndDrop::payload::<DndPayload>(ui.ctx())
+            .filter(|payload| (0..days.len()).any(|day| payload.is_from(day_source(day))));
+
+        let mut responses = Vec::with_capacity(days.len());
+        let mut columns = Vec::with_capacity(days.len());
+        let mut placeholder_drop = None;
+        ui.columns(days.len().max(1), |uis| {
+            for ((day, events), ui) in days.iter().enumerate().zip(uis) {
+                let column = ui.vertical(|ui| {
+                    ui.with_layout(Layout::top_down(Align::Center), |ui| {
+                        ui.add(Label::new(header(day)).selectable(false));
+                    });
+                    ui.separator();
+                    // A day without events has no cards to drop between
+                    if events.is_empty() {
+                        let placeholder = Frame::group(ui.style())
+                            .stroke(if dragging.is_some() {
+                                ui.visuals().selection.stroke
+                            } else {
+                                ui.visuals().widgets.noninteractive.bg_stroke
+                            })
+                            .show(ui, |ui| {
+                                ui.set_width(ui.available_width());
+                                ui.set_min_height(ui.spacing().interact_size.y * 2.0);
+                                ui.weak("No events");
+                            })
+                            .response;
+                        let released = ui.input(|i| {
+                            i.pointer.any_released()
+                                && i.pointer
+                                    .hover_pos()
+                                    .is_some_and(|pos| placeholder.rect.contains(pos))
+                        });
+                        if released && dragging.is_some() {
+                            placeholder_drop = DragAndDrop::take_payload::<DndPayload>(ui.ctx())
+                                .map(|payload| (day, 0, payload));
+                        }
+                    }
+                    dnd(ui, day_source(day))
+                        .accept_payload::<DndPayload>()
+                        .show(events.iter(), |ui, event, handle, _state| {
+                            handle.ui(ui, |ui| {
+                                Frame::group(ui.style())
+                                    .fill(ui.visuals().faint_bg_color)
+                                    .show(ui, |ui| {
+                                        ui.set_width(ui.available_width());
+                                        card(ui, event);
+                                    });
+                            });
+                        })
+                });
+                columns.push(column.response);
+                responses.push(column.inner);
+            }
+        });
+
+        // A card dropped on another day is taken by that day's list, the list it came from still
+        // reports a drop but it's ignored
+        let dropped_on_other_day = responses
+            .iter()
+            .enumerate()
+            .find_map(|(day, response)| {
+                response
+                    .dropped_payload::<DndPayload>()
+                    .map(|(index, payload)| (day, index, payload))
+            })
+            .or(placeholder_drop)
+            .and_then(|(to_day, index, payload)| {
+                let from_day = (0..days.len()).find(|day| payload.is_from(day_source(*day)))?;
+                Some((from_day, payload.index, to_day, index))
+            });
+        let moved = match dropped_on_other_day {
+            Some((from_day, from, to_day, index)) if from < days[from_day].len() => {
+                let event = days[from_day].remove(from);
+                let index = index.min(days[to_day].len());
+                days[to_day].insert(index, event);
+                Some(CalendarMove {
+                    from_day,
+                    to_day,
+                    index,
+                })
+            }
+            Some(_) => None,
+            None => responses.iter().enumerate().find_map(|(day, response)| {
+                let update = response
+                    .final_update()
+                    .filter(|update| update.target_index() != update.from)?;
+                shift_vec(update.from, update.to, &mut days[day]);
+                Some(CalendarMove {
+                    from_day: day,
+                    to_day: day,
+                    index: update.target_index(),
+                })
+            }),
+        };
+
+        CalendarResponse {
+            days: responses,
+            columns,
+            moved,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::hash::Hash;
+
+use egui::{Id, Label, Ui, WidgetText};
+
+use crate::{dnd, Dnd, DragDropConfig, DragDropResponse, Handle};
+
+/// A list of labeled actions, like menu entries or the steps of a macro, that can be reordered.
+/// Created with [dnd_command_list].
+pub struct CommandList<'a> {
+    ui: &'a mut Ui,
+    id_source: Id,
+}
+
+/// Shows a list of labeled actions with a drag handle in front of each, as a wrapper over
+/// [crate::Dnd::show_vec]. Actions are reordered by dragging their handle, or by focusing it with
+/// tab, picking the action up with space and moving it with the arrow keys, see
+/// [crate::DragDropConfig::keyboard_reordering], which the command list turns on.
+/// Example usage:
+/// ```rust no_run
+/// use eframe::egui;
+/// use egui::CentralPanel;
+/// use egui_dnd::dnd_command_list;
+///
+/// struct Step {
+///     name: &'static str,
+///     enabled: bool,
+/// }
+///
+/// pub fn main() -> eframe::Result<()> {
+///     let mut steps = vec![
+///         Step { name: "Select all", enabled: true },
+///         Step { name: "Copy", enabled: true },
+///         Step { name: "Open new tab", enabled: false },
+///     ];
+///
+///     eframe::run_simple_native("DnD Commands Example", Default::default(), move |ctx, _frame| {
+///         CentralPanel::default().show(ctx, |ui| {
+///             dnd_command_list(ui, "macro").show_toggleable(
+///                 &mut steps,
+///                 |step| step.name,
+///                 |step| step.name.into(),
+///                 |step| &mut step.enabled,
+///             );
+///         });
+///     })
+/// }
+/// ```
+pub fn dnd_command_list(ui: &mut Ui, id_source: impl Hash) -> CommandList {
+    CommandList {
+        ui,
+        id_source: Id::new(id_source),
+    }
+}
+
+impl CommandList<'_> {
+    /// Shows the actions with the labels returned by `label` and reorders them when one is dropped
+    pub fn show<T: Hash>(
+        self,
+        items: &mut [T],
+        mut label: impl FnMut(&T) -> WidgetText,
+    ) -> DragDropResponse {
+        list(self.ui, self.id_source).show_vec(items, |ui, item, handle, _state| {
+            command_row(ui, handle, label(item), None);
+        })
+    }
+
+    /// Same as [CommandList::show], but with a checkbox in each row to enable or disable the
+    /// action, which changes the flag returned by `enabled`. Disabled actions are shown greyed
+    /// out. The actions are identified by the key returned by `key`, like with
+    /// [crate::Dnd::show_vec_keyed], so toggling them doesn't change their id.
+    pub fn show_toggleable<T, K: Hash>(
+        self,
+        items: &mut [T],
+        key: impl Fn(&T) -> K,
+        mut label: impl FnMut(&T) -> WidgetText,
+        mut enabled: impl FnMut(&mut T) -> &mut bool,
+    ) -> DragDropResponse {
+        list(self.ui, self.id_source).show_vec_keyed(items, key, |ui, item, handle, _state| {
+            let label = label(item);
+            command_row(ui, handle, label, Some(enabled(item)));
+        })
+    }
+}
+
+fn list(ui: &mut Ui, id_source: Id) -> Dnd {
+    dnd(ui, id_source).with_mouse_config(DragDropConfig {
+        keyboard_reordering: true,
+        ..DragDropConfig::mouse()
+    })
+}
+
+fn command_row(ui: &mut Ui, handle: Handle, label: WidgetText, enabled: Option<&mut bool>) {
+    ui.horizontal(|ui| {
+        handle.ui(ui, |ui| {
+            ui.label("☰");
+        });
+        let enabled = match enabled {
+            Some(enabled) => {
+                ui.checkbox(enabled, "");
+                *enabled
+            }
+            None => true,
+        };
+        ui.add_enabled(enabled, Label::new(label));
+    });
+}
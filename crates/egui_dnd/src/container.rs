@@ -0,0 +1,199 @@
+use std::collections::VecDeque;
+
+#[cfg(any(feature = "indexmap", feature = "im"))]
+use crate::state::target_index;
+use crate::utils::shift_vec;
+
+/// A collection that a [crate::DragUpdate] can be applied to, see [crate::DragDropResponse::update].
+///
+/// Implemented for slices, [Vec] and [VecDeque], and behind the respective features for
+/// `smallvec::SmallVec`, `indexmap::IndexMap` and `im::Vector`.
+/// Tuples of containers of the same length are moved together, see [crate::DragDropResponse::update_parallel].
+/// Implement it for your own collection to use it with [crate::DragDropResponse::update].
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::VecDeque;
+/// use egui_dnd::DndContainer;
+///
+/// let mut items = VecDeque::from([1, 2, 3, 4]);
+/// items.shift_item(0, 2);
+/// assert_eq!(items, [2, 1, 3, 4]);
+///
+/// let mut names = vec!["a", "b", "c"];
+/// let mut sizes = vec![1, 2, 3];
+/// (&mut names, &mut sizes).shift_item(2, 0);
+/// assert_eq!(names, ["c", "a", "b"]);
+/// assert_eq!(sizes, [3, 1, 2]);
+/// ```
+///
+/// Models that aren't collections, like a database handle, an undo command stack or a rope, can
+/// implement it as well, or implement [DndSortable] if they don't know their length.
+///
+/// [crate::DragDropResponse::update] applies every change while dragging, each time the dragged
+/// item passes another item, so the model is always in the order that is shown. If you only want
+/// to apply the drop, e.g. to write a single change to a database, use
+/// [crate::DragDropResponse::final_update] instead:
+///
+/// ```rust
+/// use egui_dnd::{DndContainer, DragDropResponse};
+///
+/// /// Sends each move to a server that only understands "move item to position" requests
+/// struct RemoteList {
+///     len: usize,
+///     requests: Vec<String>,
+/// }
+///
+/// impl DndContainer for RemoteList {
+///     fn shift_item(&mut self, from: usize, to: usize) {
+///         let position = if to > from { to - 1 } else { to };
+///         self.requests.push(format!("MOVE {from} TO {position}"));
+///     }
+///
+///     fn item_count(&self) -> usize {
+///         self.len
+///     }
+/// }
+///
+/// fn send_drop(response: &DragDropResponse, list: &mut RemoteList) {
+///     // Only sends a request when the item is dropped, not for every change while dragging
+///     if let Some(update) = response.final_update() {
+///         list.shift_item(update.from, update.to);
+///     }
+/// }
+///
+/// let mut list = RemoteList { len: 3, requests: Vec::new() };
+/// list.shift_item(0, 3);
+/// assert_eq!(list.requests, ["MOVE 0 TO 2"]);
+/// ```
+pub trait DndContainer {
+    /// Moves the item at `from` so it ends up in front of the item that was at `to`, like [shift_vec].
+    /// If `to` is the length of the container, the item is moved to the end.
+    fn shift_item(&mut self, from: usize, to: usize);
+
+    /// The number of items in the container
+    fn item_count(&self) -> usize;
+}
+
+/// A model the items of a list can be moved in, see [crate::DragDropResponse::apply].
+///
+/// This is [DndContainer] without [DndContainer::item_count], for models that don't know their
+/// length, like a database handle or an undo command stack. Every [DndContainer] is a
+/// [DndSortable], and the same caveat applies: [crate::DragDropResponse::apply] moves the item
+/// for every change while dragging, use [crate::DragDropResponse::final_update] to only apply
+/// the drop.
+pub trait DndSortable {
+    /// Moves the item at `from` so it ends up in front of the item that was at `to`, like
+    /// [DndContainer::shift_item].
+    fn move_item(&mut self, from: usize, to: usize);
+}
+
+impl<C: DndContainer + ?Sized> DndSortable for C {
+    fn move_item(&mut self, from: usize, to: usize) {
+        self.shift_item(from, to);
+    }
+}
+
+impl<T> DndContainer for [T] {
+    fn shift_item(&mut self, from: usize, to: usize) {
+        shift_vec(from, to, self);
+    }
+
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> DndContainer for Vec<T> {
+    fn shift_item(&mut self, from: usize, to: usize) {
+        shift_vec(from, to, self);
+    }
+
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> DndContainer for VecDeque<T> {
+    fn shift_item(&mut self, from: usize, to: usize) {
+        shift_vec(from, to, self.make_contiguous());
+    }
+
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> DndContainer for smallvec::SmallVec<A> {
+    fn shift_item(&mut self, from: usize, to: usize) {
+        shift_vec(from, to, self);
+    }
+
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> DndContainer for indexmap::IndexMap<K, V, S> {
+    fn shift_item(&mut self, from: usize, to: usize) {
+        self.move_index(from, target_index(from, to));
+    }
+
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "im")]
+impl<T: Clone> DndContainer for im::Vector<T> {
+    fn shift_item(&mut self, from: usize, to: usize) {
+        let item = self.remove(from);
+        self.insert(target_index(from, to), item);
+    }
+
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<C: DndContainer + ?Sized> DndContainer for &mut C {
+    fn shift_item(&mut self, from: usize, to: usize) {
+        (**self).shift_item(from, to);
+    }
+
+    fn item_count(&self) -> usize {
+        (**self).item_count()
+    }
+}
+
+macro_rules! impl_dnd_container_for_tuple {
+    ($first:ident $(, $rest:ident)*) => {
+        #[allow(non_snake_case)]
+        impl<$first: DndContainer, $($rest: DndContainer),*> DndContainer for ($first, $($rest),*) {
+            /// Moves the item in all containers, panics before moving anything if their lengths differ
+            fn shift_item(&mut self, from: usize, to: usize) {
+                let ($first, $($rest),*) = self;
+                let count = $first.item_count();
+                assert!(
+                    $($rest.item_count() == count)&&*,
+                    "Parallel containers must have the same number of items"
+                );
+                $first.shift_item(from, to);
+                $($rest.shift_item(from, to);)*
+            }
+
+            fn item_count(&self) -> usize {
+                self.0.item_count()
+            }
+        }
+    };
+}
+
+impl_dnd_container_for_tuple!(A, B);
+impl_dnd_container_for_tuple!(A, B, C);
+impl_dnd_container_for_tuple!(A, B, C, D);
+impl_dnd_container_for_tuple!(A, B, C, D, E);
+impl_dnd_container_for_tuple!(A, B, C, D, E, F);
@@ -0,0 +1,118 @@
+use std::any::Any;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use egui::{Context, Direction, DragAndDrop, DroppedFile, Id, Layout, Pos2, Rect, Vec2};
+
+use crate::{DndEvent, DragDropResponse};
+
+/// The item that is dragged in a [crate::Dnd] list. It's published as an egui [DragAndDrop]
+/// payload while it's dragged with the pointer, so any egui widget can accept it, e.g. with
+/// [egui::Ui::dnd_drop_zone], as well as other lists using [crate::Dnd::accept_payload].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DndPayload {
+    /// Identifies the list the item is dragged from, scoped by the ui it's shown in
+    pub list: Id,
+    /// The id source passed to [crate::dnd], see [DndPayload::is_from]
+    pub source: Id,
+    /// The id of the dragged item, see [crate::DragDropItem::id]
+    pub item: Id,
+    /// The current index of the dragged item in its list
+    pub index: usize,
+}
+
+impl DndPayload {
+    /// Returns true if the item is dragged from a list with the given id source,
+    /// i.e. the one passed to [crate::dnd]. Use [DndPayload::list] to tell apart lists with the
+    /// same id source in different windows.
+    pub fn is_from(&self, id_source: impl Hash) -> bool {
+        self.source == Id::new(id_source).with("dnd")
+    }
+}
+
+/// Files dropped from the OS onto a list that accepts them, see [crate::Dnd::accept_files]
+#[derive(Debug, Clone)]
+pub struct ExternalDrop {
+    /// The index the files should be inserted at
+    pub index: usize,
+    /// The dropped files
+    pub files: Vec<DroppedFile>,
+}
+
+/// Looks up the payload accepted by [crate::Dnd::accept_payload], taking it if the bool is true
+pub(crate) type AcceptPayload = fn(&Context, bool) -> Option<Arc<dyn Any + Send + Sync>>;
+
+/// Sets the [DndPayload] while an item of the list is dragged with the pointer,
+/// and clears it if the drag is cancelled
+pub(crate) fn publish_payload(ctx: &Context, list: Id, source: Id, response: &DragDropResponse) {
+    match (response.dragged_item_id(), &response.update) {
+        // Keyboard drags have no pointer that could carry the payload anywhere
+        (Some(item), Some(update)) if update.pointer_pos.is_some() => {
+            DragAndDrop::set_payload(
+                ctx,
+                DndPayload {
+                    list,
+                    source,
+                    item,
+                    index: update.from,
+                },
+            );
+        }
+        _ => {
+            let cancelled = response
+                .events()
+                .iter()
+                .any(|event| matches!(event, DndEvent::Cancelled { .. }));
+            if cancelled
+                && DragAndDrop::payload::<DndPayload>(ctx)
+                    .is_some_and(|payload| payload.list == list)
+            {
+                DragAndDrop::clear_payload(ctx);
+            }
+        }
+    }
+}
+
+/// Something dragged in from outside of the list, hovering it
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ExternalHover {
+    /// The index it would be inserted at
+    pub(crate) slot: usize,
+    /// The size of the gap that's opened for it
+    pub(crate) size: Vec2,
+}
+
+/// Where something dragged in from outside of the list would be inserted, given the rects of the
+/// items and the area covered by them and the gap in the last frame.
+/// Returns `None` if the pointer isn't over that area.
+pub(crate) fn external_hover(
+    layout: &Layout,
+    rects: &[Rect],
+    area: Rect,
+    pointer: Pos2,
+) -> Option<ExternalHover> {
+    if !area.contains(pointer) {
+        return None;
+    }
+
+    // The gap from the last frame is already in between the rects, so the slot only changes once
+    // the pointer crosses the center of an item
+    let is_before_pointer = |rect: &Rect| {
+        if layout.main_wrap && layout.is_horizontal() {
+            rect.max.y < pointer.y || (rect.min.y <= pointer.y && rect.center().x < pointer.x)
+        } else {
+            match layout.main_dir() {
+                Direction::LeftToRight => rect.center().x < pointer.x,
+                Direction::RightToLeft => rect.center().x > pointer.x,
+                Direction::TopDown => rect.center().y < pointer.y,
+                Direction::BottomUp => rect.center().y > pointer.y,
+            }
+        }
+    };
+    let slot = rects.iter().filter(|rect| is_before_pointer(rect)).count();
+    let size = rects
+        .get(slot)
+        .or(rects.last())
+        .map_or(Vec2::ZERO, Rect::size);
+    Some(ExternalHover { slot, size })
+}
@@ -0,0 +1,258 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::time::Duration;
+
+use egui::{DroppedFile, Id, Label, Ui};
+
+use crate::tree::{self, TreeNode, TreeRow};
+use crate::{dnd, DragDropResponse};
+
+/// A file or a folder shown by [FileList]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    /// The name of the entry, which has to be unique within its folder
+    pub name: String,
+    /// The entries in the folder, or `None` if the entry is a file
+    pub children: Option<Vec<FileEntry>>,
+}
+
+impl FileEntry {
+    /// Creates a file
+    pub fn file(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            children: None,
+        }
+    }
+
+    /// Creates a folder with the given entries
+    pub fn folder(name: impl Into<String>, children: Vec<FileEntry>) -> Self {
+        Self {
+            name: name.into(),
+            children: Some(children),
+        }
+    }
+
+    /// Whether the entry is a folder
+    pub fn is_folder(&self) -> bool {
+        self.children.is_some()
+    }
+
+    fn from_dropped_file(file: &DroppedFile) -> Self {
+        let name = file
+            .path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| file.name.clone());
+        Self::file(name)
+    }
+}
+
+impl TreeNode for FileEntry {
+    fn id(&self, parent: Id) -> Id {
+        parent.with(&self.name)
+    }
+
+    fn children(&self) -> Option<&Vec<Self>> {
+        self.children.as_ref()
+    }
+
+    fn children_mut(&mut self) -> Option<&mut Vec<Self>> {
+        self.children.as_mut()
+    }
+}
+
+/// A tree of files and folders, like the list view of a file manager. Created with [dnd_file_list].
+pub struct FileList<'a> {
+    ui: &'a mut Ui,
+    id_source: Id,
+    spring_delay: Duration,
+}
+
+/// The response of [FileList::show]
+pub struct FileListResponse {
+    /// The response of the list the visible entries are shown in
+    pub response: DragDropResponse,
+    /// Whether the entries were changed this frame, because an entry was moved or copied or files
+    /// were dropped on the list
+    pub changed: bool,
+}
+
+/// Shows a tree of files and folders that can be rearranged like in a file manager:
+/// - entries are moved by dragging them between other entries, also into and out of open folders
+/// - dropping an entry onto a folder moves it into the folder, see [crate::Dnd::drop_onto]
+/// - holding ctrl (cmd on mac) while dropping copies the entry instead of moving it
+/// - holding an entry over a closed folder opens it after a moment, so the entry can be dropped
+///   anywhere inside of it
+/// - files dropped on the window from the OS are added where they are dropped, see
+///   [crate::Dnd::accept_files]
+///
+/// Folders are opened and closed with the arrow in front of them.
+/// Example usage:
+/// ```rust no_run
+/// use eframe::egui;
+/// use egui::CentralPanel;
+/// use egui_dnd::{dnd_file_list, FileEntry};
+///
+/// pub fn main() -> eframe::Result<()> {
+///     let mut files = vec![
+///         FileEntry::folder("src", vec![FileEntry::file("main.rs")]),
+///         FileEntry::file("Cargo.toml"),
+///     ];
+///
+///     eframe::run_simple_native("DnD Files Example", Default::default(), move |ctx, _frame| {
+///         CentralPanel::default().show(ctx, |ui| {
+///             dnd_file_list(ui, "files").show(&mut files);
+///         });
+///     })
+/// }
+/// ```
+pub fn dnd_file_list(ui: &mut Ui, id_source: impl Hash) -> FileList {
+    FileList {
+        ui,
+        id_source: Id::new(id_source),
+        spring_delay: Duration::from_millis(700),
+    }
+}
+
+impl FileList<'_> {
+    /// How long an entry has to be held over a closed folder until the folder opens.
+    /// The default is 0.7 seconds.
+    pub fn spring_delay(mut self, delay: Duration) -> Self {
+        self.spring_delay = delay;
+        self
+    }
+
+    /// Shows the entries and applies the moves, copies and dropped files to them
+    pub fn show(self, entries: &mut Vec<FileEntry>) -> FileListResponse {
+        let FileList {
+            ui,
+            id_source,
+            spring_delay,
+        } = self;
+        let open_id = ui.id().with(id_source).with("dnd_file_list_open");
+        let mut open = ui.data(|data| data.get_temp::<HashSet<Id>>(open_id).unwrap_or_default());
+
+        let rows = tree::flatten(entries, id_source.with("files"), &open);
+
+        let mut toggled = None;
+        let mut sprung = None;
+        let response = dnd(ui, id_source)
+            .accept_files()
+            .drop_onto(|idx| rows.get(idx).is_some_and(|row| row.open.is_some()))
+            .spring_open(
+                spring_delay,
+                |idx| rows.get(idx).is_some_and(|row| row.open == Some(false)),
+                |id, _| sprung = Some(id),
+            )
+            .show(rows.iter().cloned(), |ui, row, handle, _state| {
+                let Some(entry) = tree::node_at(entries, &row.path) else {
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    ui.add_space(ui.spacing().indent * row.depth() as f32);
+                    match row.open {
+                        Some(is_open) => {
+                            if ui.small_button(if is_open { "⏷" } else { "⏵" }).clicked() {
+                                toggled = Some(row.id);
+                            }
+                        }
+                        None => ui.add_space(ui.spacing().icon_width),
+                    }
+                    handle.ui(ui, |ui| {
+                        let icon = if row.open.is_some() { "🗀" } else { "🗋" };
+                        ui.add(Label::new(format!("{icon} {}", entry.name)).selectable(false));
+                    });
+                });
+            });
+
+        if let Some(id) = toggled {
+            if !open.remove(&id) {
+                open.insert(id);
+            }
+        }
+        // A closed folder the entry was held over opens
+        open.extend(sprung);
+
+        // Highlight the folder the entry would be dropped into
+        let onto = response
+            .update
+            .as_ref()
+            .filter(|_| response.is_dragging())
+            .and_then(|update| update.onto)
+            .and_then(|idx| rows.get(idx));
+        if let Some(folder) = onto {
+            if let Some(item) = response.item_response(folder.id) {
+                ui.painter().rect_stroke(
+                    item.rect,
+                    ui.visuals().widgets.active.rounding,
+                    ui.visuals().selection.stroke,
+                );
+            }
+        }
+
+        let mut changed = false;
+        if let Some(update) = response.final_update() {
+            let copy = update.modifiers.command;
+            let target = tree::drop_target(&rows, entries.len(), &update);
+            if let (Some(source), Some(target)) = (rows.get(update.from), target) {
+                changed = move_entry(entries, &source.path, target, copy);
+            }
+        }
+
+        if let Some(drop) = response.external_drop() {
+            let target = rows
+                .get(drop.index)
+                .map_or(vec![entries.len()], |row| row.path.clone());
+            for (offset, file) in drop.files.iter().enumerate() {
+                let mut path = target.clone();
+                *path.last_mut().unwrap() += offset;
+                insert_entry(entries, &path, FileEntry::from_dropped_file(file));
+            }
+            changed |= !drop.files.is_empty();
+        }
+
+        ui.data_mut(|data| data.insert_temp(open_id, open));
+
+        FileListResponse { response, changed }
+    }
+}
+
+/// Moves or copies the entry at `source` so it ends up at `target`, the path of the position it's
+/// inserted at. Returns false if the target is inside of the entry itself.
+fn move_entry(
+    entries: &mut Vec<FileEntry>,
+    source: &[usize],
+    mut target: Vec<usize>,
+    copy: bool,
+) -> bool {
+    // A folder can't be moved into itself
+    if tree::is_inside(&target, source) {
+        return false;
+    }
+    let entry = if copy {
+        tree::node_at(entries, source).cloned()
+    } else {
+        tree::remove(entries, source, &mut target)
+    };
+    match entry {
+        Some(entry) => insert_entry(entries, &target, entry),
+        None => false,
+    }
+}
+
+/// Inserts the entry at `path`, clamped to the end of the folder. If the folder already contains
+/// an entry with the same name, " (copy)" is added to the name.
+fn insert_entry(entries: &mut Vec<FileEntry>, path: &[usize], mut entry: FileEntry) -> bool {
+    let Some(folder) = path
+        .split_last()
+        .and_then(|(_, parent)| tree::children_mut(entries, parent))
+    else {
+        return false;
+    };
+    while folder.iter().any(|other| other.name == entry.name) {
+        entry.name.push_str(" (copy)");
+    }
+    tree::insert(entries, path, entry)
+}
@@ -0,0 +1,185 @@
+use std::hash::Hash;
+
+use egui::{DragAndDrop, Frame, Id, Label, Ui, WidgetText};
+
+use crate::{dnd, DragDropResponse};
+
+/// A palette of field types next to a form that is built by dragging them onto it.
+/// Created with [dnd_form_builder].
+pub struct FormBuilder<'a> {
+    ui: &'a mut Ui,
+    id_source: Id,
+}
+
+/// The response of [FormBuilder::show]
+pub struct FormBuilderResponse {
+    /// The response of the list the fields of the form are shown in
+    pub response: DragDropResponse,
+    /// The responses of the palette entries, in the order of the palette
+    pub palette: Vec<egui::Response>,
+    /// The response of the form, the pane the fields are shown in
+    pub canvas: egui::Response,
+    /// The index of the field that was created from a palette entry this frame
+    pub spawned: Option<usize>,
+}
+
+/// The payload of a palette entry while it's dragged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PaletteItem {
+    builder: Id,
+    index: usize,
+}
+
+/// Shows a palette of field types on the left and the fields of a form on the right.
+/// The palette entries can only be dragged, they stay in the palette. Dropping one onto the
+/// form creates a new field where it's dropped, by calling the factory passed to
+/// [FormBuilder::show]. The fields of the form are reordered like with [crate::Dnd::show_vec].
+/// Example usage:
+/// ```rust no_run
+/// use eframe::egui;
+/// use egui::CentralPanel;
+/// use egui_dnd::dnd_form_builder;
+///
+/// #[derive(Hash)]
+/// struct Field {
+///     id: usize,
+///     kind: &'static str,
+/// }
+///
+/// pub fn main() -> eframe::Result<()> {
+///     let mut fields = Vec::new();
+///     let mut next_id = 0;
+///
+///     eframe::run_simple_native("DnD Form Example", Default::default(), move |ctx, _frame| {
+///         CentralPanel::default().show(ctx, |ui| {
+///             dnd_form_builder(ui, "form").show(
+///                 &["Text", "Number", "Checkbox"],
+///                 |kind| (*kind).into(),
+///                 &mut fields,
+///                 |kind| {
+///                     next_id += 1;
+///                     Field { id: next_id, kind }
+///                 },
+///                 |ui, field| {
+///                     ui.label(format!("{} #{}", field.kind, field.id));
+///                 },
+///             );
+///         });
+///     })
+/// }
+/// ```
+pub fn dnd_form_builder(ui: &mut Ui, id_source: impl Hash) -> FormBuilder {
+    FormBuilder {
+        ui,
+        id_source: Id::new(id_source),
+    }
+}
+
+impl FormBuilder<'_> {
+    /// Shows the palette entries with the labels returned by `palette_label` and the fields with
+    /// `field_ui`. When a palette entry is dropped onto the form, `factory` is called with it and
+    /// the returned field is inserted. Fields are identified by their hash, so the factory has to
+    /// return a field with a new hash each time, e.g. by giving it a counter.
+    pub fn show<P, F: Hash>(
+        self,
+        palette: &[P],
+        mut palette_label: impl FnMut(&P) -> WidgetText,
+        fields: &mut Vec<F>,
+        mut factory: impl FnMut(&P) -> F,
+        mut field_ui: impl FnMut(&mut Ui, &mut F),
+    ) -> FormBuilderResponse {
+        let FormBuilder { ui, id_source } = self;
+        let builder = ui.id().with(id_source).with("dnd_form_builder");
+        let dragged = DragAndDrop::payload::<PaletteItem>(ui.ctx())
+            .filter(|payload| payload.builder == builder);
+
+        let (palette_responses, (response, canvas, placeholder)) = ui
+            .horizontal_top(|ui| {
+                let palette_responses = ui
+                    .vertical(|ui| {
+                        palette
+                            .iter()
+                            .enumerate()
+                            .map(|(index, entry)| {
+                                let payload = PaletteItem { builder, index };
+                                ui.dnd_drag_source(builder.with(index), payload, |ui| {
+                                    Frame::group(ui.style()).show(ui, |ui| {
+                                        ui.add(Label::new(palette_label(entry)).selectable(false));
+                                    });
+                                })
+                                .response
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .inner;
+                ui.separator();
+                let canvas = ui.vertical(|ui| {
+                    // The list only accepts drops between its fields, so an empty form gets a
+                    // placeholder to drop the first field onto
+                    let placeholder = fields.is_empty().then(|| {
+                        let stroke = if dragged.is_some() {
+                            ui.visuals().selection.stroke
+                        } else {
+                            ui.visuals().widgets.noninteractive.bg_stroke
+                        };
+                        Frame::group(ui.style())
+                            .stroke(stroke)
+                            .show(ui, |ui| {
+                                ui.set_width(ui.available_width());
+                                ui.weak("Drop fields here");
+                            })
+                            .response
+                            .rect
+                    });
+                    let response = dnd(ui, id_source).accept_payload::<PaletteItem>().show_vec(
+                        fields,
+                        |ui, field, handle, _state| {
+                            ui.horizontal(|ui| {
+                                handle.ui(ui, |ui| {
+                                    ui.label("☰");
+                                });
+                                field_ui(ui, field);
+                            });
+                        },
+                    );
+                    (response, placeholder)
+                });
+                let (response, placeholder) = canvas.inner;
+                (palette_responses, (response, canvas.response, placeholder))
+            })
+            .inner;
+
+        // Palette entries dropped between the fields, or onto the placeholder of an empty form
+        let dropped = match response.dropped_payload::<PaletteItem>() {
+            Some((index, payload)) => Some((index, *payload)),
+            None => dragged
+                .filter(|_| {
+                    placeholder.is_some_and(|rect| {
+                        ui.input(|i| {
+                            i.pointer.any_released()
+                                && i.pointer.hover_pos().is_some_and(|pos| rect.contains(pos))
+                        })
+                    })
+                })
+                .map(|payload| {
+                    DragAndDrop::clear_payload(ui.ctx());
+                    (0, *payload)
+                }),
+        };
+        let spawned = dropped
+            .filter(|(_, payload)| payload.builder == builder)
+            .and_then(|(index, payload)| {
+                let entry = palette.get(payload.index)?;
+                let index = index.min(fields.len());
+                fields.insert(index, factory(entry));
+                Some(index)
+            });
+
+        FormBuilderResponse {
+            response,
+            palette: palette_responses,
+            canvas,
+            spawned,
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use egui::{Align2, Direction, Id, Layout, Rect, Sense, TextStyle, Ui, Vec2};
+
+use crate::utils::{shift_many, shift_vec};
+use crate::{dnd, DragDropResponse};
+
+/// A grid of fixed-size thumbnails that can be selected and reordered by dragging.
+/// Created with [dnd_gallery].
+pub struct Gallery<'a> {
+    ui: &'a mut Ui,
+    id_source: Id,
+    thumbnail_size: Vec2,
+}
+
+/// The response of [Gallery::show]
+pub struct GalleryResponse {
+    /// The response of the list the thumbnails are shown in
+    pub response: DragDropResponse,
+    /// The indices of the selected items, after the items were reordered
+    pub selected: Vec<usize>,
+    /// Whether the items were reordered this frame
+    pub moved: bool,
+}
+
+/// Shows items as a grid of thumbnails, like a photo gallery. The thumbnails wrap into rows and
+/// the dragged thumbnail is dropped at the row and column it's hovering, see
+/// [crate::Dnd::show_sized].
+///
+/// Thumbnails are selected by clicking them, ctrl (cmd on mac) + click adds or removes a
+/// thumbnail from the selection. Dragging a selected thumbnail moves all selected items: they are shown
+/// as a pile under the pointer and are inserted next to each other where they are dropped, in
+/// the order they had before. The items are only reordered once they are dropped, like with
+/// [crate::CommitStrategy::OnDrop].
+/// Example usage:
+/// ```rust no_run
+/// use eframe::egui;
+/// use egui::{CentralPanel, Color32};
+/// use egui_dnd::dnd_gallery;
+///
+/// pub fn main() -> eframe::Result<()> {
+///     let mut photos = vec![
+///         ("beach", Color32::LIGHT_BLUE),
+///         ("forest", Color32::DARK_GREEN),
+///         ("desert", Color32::GOLD),
+///     ];
+///
+///     eframe::run_simple_native("DnD Gallery Example", Default::default(), move |ctx, _frame| {
+///         CentralPanel::default().show(ctx, |ui| {
+///             dnd_gallery(ui, "photos").show(&mut photos, |ui, (name, color)| {
+///                 ui.painter().rect_filled(ui.max_rect(), 4.0, *color);
+///                 ui.label(*name);
+///             });
+///         });
+///     })
+/// }
+/// ```
+pub fn dnd_gallery(ui: &mut Ui, id_source: impl Hash) -> Gallery {
+    Gallery {
+        ui,
+        id_source: Id::new(id_source),
+        thumbnail_size: Vec2::splat(96.0),
+    }
+}
+
+impl Gallery<'_> {
+    /// The size of each thumbnail. The default is 96x96.
+    pub fn thumbnail_size(mut self, size: Vec2) -> Self {
+        self.thumbnail_size = size;
+        self
+    }
+
+    /// Shows the items with `thumbnail`, which is called with a ui of the thumbnail size, and
+    /// reorders them when a thumbnail is dropped. Items are identified by their hash, like the
+    /// items of [crate::Dnd::show_vec].
+    pub fn show<T: Hash>(
+        self,
+        items: &mut Vec<T>,
+        mut thumbnail: impl FnMut(&mut Ui, &T),
+    ) -> GalleryResponse {
+        let Gallery {
+            ui,
+            id_source,
+            thumbnail_size,
+        } = self;
+        let selection_id = ui.id().with(id_source).with("dnd_gallery_selection");
+        let mut selection = ui.data(|data| {
+            data.get_temp::<HashSet<Id>>(selection_id)
+                .unwrap_or_default()
+        });
+        // Items that were removed can't be selected anymore
+        selection.retain(|id| items.iter().any(|item| Id::new(item) == *id));
+
+        let mut clicked = None;
+        let response = ui
+            .horizontal_wrapped(|ui| {
+                let dragging = ui.memory(|mem| mem.is_anything_being_dragged());
+                dnd(ui, id_source).show_sized(
+                    items.iter(),
+                    thumbnail_size,
+                    |ui, item, handle, state| {
+                        let id = Id::new(item);
+                        let selected = selection.contains(&id);
+                        // The dragged thumbnail carries the whole selection with it
+                        let pile = if state.dragged && selected {
+                            selection.len()
+                        } else {
+                            1
+                        };
+                        let response = handle.sense(Sense::click()).ui(ui, |ui| {
+                            let rect = Rect::from_min_size(ui.cursor().min, thumbnail_size);
+                            paint_pile(ui, rect, pile);
+                            ui.painter().rect_filled(
+                                rect,
+                                ui.visuals().widgets.noninteractive.rounding,
+                                ui.visuals().extreme_bg_color,
+                            );
+                            ui.allocate_ui_with_layout(
+                                thumbnail_size,
+                                Layout::centered_and_justified(Direction::TopDown),
+                                |ui| {
+                                    ui.set_min_size(thumbnail_size);
+                                    thumbnail(ui, item);
+                                },
+                            );
+                        });
+                        if selected {
+                            let faded = dragging && !state.dragged;
+                            let mut stroke = ui.visuals().selection.stroke;
+                            if faded {
+                                stroke.color = stroke.color.gamma_multiply(0.4);
+                            }
+                            ui.painter().rect_stroke(
+                                response.rect,
+                                ui.visuals().widgets.noninteractive.rounding,
+                                stroke,
+                            );
+                        }
+                        if pile > 1 {
+                            paint_count(ui, response.rect, pile);
+                        }
+                        if response.clicked() {
+                            clicked = Some((id, ui.input(|i| i.modifiers.command)));
+                        }
+                    },
+                )
+            })
+            .inner;
+
+        match clicked {
+            Some((id, true)) => {
+                if !selection.remove(&id) {
+                    selection.insert(id);
+                }
+            }
+            Some((id, false)) => {
+                selection.clear();
+                selection.insert(id);
+            }
+            None => {}
+        }
+
+        let mut moved = false;
+        if let Some(update) = response.final_update() {
+            if selection.contains(&update.id) {
+                move_selected(items, &selection, update.to);
+            } else {
+                shift_vec(update.from, update.to, items);
+            }
+            moved = true;
+        }
+
+        let selected = items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| selection.contains(&Id::new(item)))
+            .map(|(idx, _)| idx)
+            .collect();
+        ui.data_mut(|data| data.insert_temp(selection_id, selection));
+
+        GalleryResponse {
+            response,
+            selected,
+            moved,
+        }
+    }
+}
+
+/// Paints the thumbnails below the top one of a pile of `count` thumbnails
+fn paint_pile(ui: &Ui, rect: Rect, count: usize) {
+    let visuals = ui.visuals();
+    for layer in (1..count.min(3)).rev() {
+        let offset = Vec2::splat(4.0 * layer as f32);
+        ui.painter().rect(
+            rect.translate(offset),
+            visuals.widgets.noninteractive.rounding,
+            visuals.faint_bg_color,
+            visuals.widgets.noninteractive.bg_stroke,
+        );
+    }
+}
+
+/// Paints a badge with the number of thumbnails in the pile at the top right corner
+fn paint_count(ui: &Ui, rect: Rect, count: usize) {
+    let center = rect.right_top();
+    let radius = ui.spacing().interact_size.y * 0.5;
+    ui.painter()
+        .circle_filled(center, radius, ui.visuals().selection.bg_fill);
+    ui.painter().text(
+        center,
+        Align2::CENTER_CENTER,
+        count.to_string(),
+        TextStyle::Small.resolve(ui.style()),
+        ui.visuals().selection.stroke.color,
+    );
+}
+
+/// Moves the selected items next to each other, so they are inserted before the item that was at
+/// index `to`, keeping their order
+fn move_selected<T: Hash>(items: &mut [T], selection: &HashSet<Id>, to: usize) {
+    let moves: Vec<(usize, usize)> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| selection.contains(&Id::new(item)))
+        .map(|(idx, _)| (idx, to.min(items.len())))
+        .collect();
+    shift_many(&moves, items);
+}
@@ -0,0 +1,70 @@
+use std::collections::VecDeque;
+
+use crate::state::insertion_index;
+
+/// The moves made in a list, so they can be undone, see [crate::Dnd::with_history]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct History {
+    capacity: usize,
+    /// The index each item was moved from and the index it ended up at, oldest first
+    undo: VecDeque<(usize, usize)>,
+    redo: Vec<(usize, usize)>,
+}
+
+impl History {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    /// Drops the oldest moves that don't fit anymore
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.undo.len() > capacity {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Remembers that the item at `from` was moved to `target`, forgetting the undone moves
+    pub(crate) fn push(&mut self, from: usize, target: usize) {
+        if from == target {
+            return;
+        }
+        self.redo.clear();
+        self.push_undo((from, target));
+    }
+
+    fn push_undo(&mut self, entry: (usize, usize)) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.undo.len() == self.capacity {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(entry);
+    }
+
+    pub(crate) fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub(crate) fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Returns the `from` and `to` of the move that undoes the last move
+    pub(crate) fn undo(&mut self) -> Option<(usize, usize)> {
+        let (from, target) = self.undo.pop_back()?;
+        self.redo.push((from, target));
+        Some((target, insertion_index(target, from)))
+    }
+
+    /// Returns the `from` and `to` of the move that was undone last
+    pub(crate) fn redo(&mut self) -> Option<(usize, usize)> {
+        let (from, target) = self.redo.pop()?;
+        self.push_undo((from, target));
+        Some((from, insertion_index(from, target)))
+    }
+}
@@ -0,0 +1,41 @@
+use egui::{Context, Ui};
+
+use crate::state::DragDropUi;
+use crate::ListScopes;
+
+/// Lists the state of every drag and drop list stored in the memory of `ctx`, with its drag
+/// detection state, the dragged item, the animation times and the configs. Like egui's
+/// [Context::inspection_ui], but for the lists of this crate, e.g. to show in a debug window.
+/// `ctx` may belong to another viewport than `ui`.
+pub fn inspector(ui: &mut Ui, ctx: &Context) {
+    let lists = ctx.data_mut(|data| {
+        let ids = data
+            .get_temp::<ListScopes>(ListScopes::all_lists())
+            .unwrap_or_default()
+            .0;
+        // Forget the lists whose state was removed, e.g. with Dnd::forget
+        let lists: Vec<_> = ids
+            .into_iter()
+            .filter_map(|id| Some((id, data.get_temp::<DragDropUi>(id)?)))
+            .collect();
+        data.insert_temp(
+            ListScopes::all_lists(),
+            ListScopes(lists.iter().map(|(id, _)| *id).collect()),
+        );
+        lists
+    });
+
+    if lists.is_empty() {
+        ui.label("No drag and drop lists were shown yet");
+    }
+    for (id, list) in lists {
+        let dragging = list.detection_state.is_dragging();
+        egui::CollapsingHeader::new(format!(
+            "List {}{}",
+            id.short_debug_format(),
+            if dragging { " (dragging)" } else { "" }
+        ))
+        .id_source(id.with("inspector"))
+        .show(ui, |ui| list.inspection_ui(ui));
+    }
+}
@@ -0,0 +1,74 @@
+//! Spans and events of the `tracing` feature, to correlate performance problems with dnd activity
+
+use egui::Id;
+
+use crate::state::DragDropUi;
+use crate::{DndEvent, DragDropResponse};
+
+/// The span the list is shown in
+pub(crate) fn list_span(list: Id) -> tracing::Span {
+    tracing::debug_span!("egui_dnd", list = %list.short_debug_format())
+}
+
+/// Emits an event for each [DndEvent] of the response and the size of the state that is stored
+pub(crate) fn trace_response(list: Id, response: &DragDropResponse, state: &DragDropUi) {
+    let list = list.short_debug_format();
+    for event in response.events() {
+        match event {
+            DndEvent::DragStarted { id, index } => {
+                tracing::debug!(
+                    list = %list,
+                    item = %id.short_debug_format(),
+                    index,
+                    "drag started"
+                );
+            }
+            DndEvent::TargetChanged { id, from, to } => {
+                tracing::trace!(
+                    list = %list,
+                    item = %id.short_debug_format(),
+                    from,
+                    to,
+                    "slot changed"
+                );
+            }
+            DndEvent::Dropped { id, update } => {
+                tracing::debug!(
+                    list = %list,
+                    item = %id.short_debug_format(),
+                    from = update.from,
+                    to = update.to,
+                    "dropped"
+                );
+            }
+            DndEvent::DropVetoed { id, .. } => {
+                tracing::debug!(list = %list, item = %id.short_debug_format(), "drop vetoed");
+            }
+            DndEvent::DraggedOutOfWindow { id, .. } => {
+                tracing::debug!(
+                    list = %list,
+                    item = %id.short_debug_format(),
+                    "dragged out of window"
+                );
+            }
+            DndEvent::Merge { id, source, target } => {
+                tracing::debug!(list = %list, item = %id.short_debug_format(), source, target, "merged");
+            }
+            DndEvent::SpringOpened { id, index } => {
+                tracing::debug!(list = %list, item = %id.short_debug_format(), index, "spring opened");
+            }
+            DndEvent::SpringClosed { id, index } => {
+                tracing::debug!(list = %list, item = %id.short_debug_format(), index, "spring closed");
+            }
+            DndEvent::Cancelled { id, reason } => {
+                tracing::debug!(
+                    list = %list,
+                    item = %id.short_debug_format(),
+                    %reason,
+                    "drag cancelled"
+                );
+            }
+        }
+    }
+    tracing::trace!(list = %list, bytes = state.approximate_size(), "stored state");
+}
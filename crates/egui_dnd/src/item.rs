@@ -1,15 +1,25 @@
-use egui::{CursorIcon, Id, InnerResponse, LayerId, Order, Pos2, Rect, Sense, Ui, Vec2};
-use egui_animation::animate_position;
+use std::ops::Range;
 
-use crate::state::DragDetectionState;
+use egui::epaint::shape_transform::adjust_colors;
+use egui::epaint::ClippedShape;
+use egui::layers::ShapeIdx;
+use egui::{Direction, Id, InnerResponse, Layout, Pos2, Rect, Sense, Shape, Ui, Vec2};
+
+use crate::state::{DragDetectionState, HandleMode};
 use crate::{DragDropUi, Handle, ItemState};
 
+/// The opacity of an item that was cut, see [ItemState::cut]
+const CUT_ITEM_OPACITY: f32 = 0.4;
+
 pub struct Item<'a> {
     id: Id,
     pub state: ItemState,
     dnd_state: &'a mut DragDropUi,
     hovering_over_any_handle: &'a mut bool,
     easing: fn(f32) -> f32,
+    /// Whether the handle can start a drag, e.g. not if the item is locked
+    handle_mode: HandleMode,
+    z_index: i32,
 }
 
 impl<'a> Item<'a> {
@@ -18,6 +28,7 @@ impl<'a> Item<'a> {
         state: ItemState,
         dnd_state: &'a mut DragDropUi,
         hovering_over_any_handle: &'a mut bool,
+        handle_mode: HandleMode,
     ) -> Self {
         Self {
             id,
@@ -25,9 +36,24 @@ impl<'a> Item<'a> {
             dnd_state,
             hovering_over_any_handle,
             easing: simple_easing::linear,
+            handle_mode,
+            z_index: 0,
         }
     }
 
+    /// Controls the paint order of items that overlap, e.g. with negative item spacing or in a
+    /// fan of cards. Items with a higher z index are painted above items with a lower one, items
+    /// with the same z index are painted in list order. The default is 0.
+    /// The dragged item and the item returning to its place after a drop are always painted
+    /// above the list, on [crate::DragDropConfig::floating_item_layer].
+    ///
+    /// Use [ItemState::is_displaced] to decide whether items moving out of the way of the
+    /// dragged item slide under or over their neighbors.
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
     pub fn ui(
         self,
         ui: &mut Ui,
@@ -54,7 +80,17 @@ impl<'a> Item<'a> {
         let hovering_over_any_handle = self.hovering_over_any_handle;
         let id = self.id;
         let index = self.state.index;
+        let handle_mode = self.handle_mode;
         let last_pointer_pos = self.dnd_state.detection_state.last_pointer_pos();
+        let is_keyboard_drag = self.dnd_state.detection_state.is_keyboard_dragging();
+        let shift_animation_time = self.dnd_state.shift_animation_time(ui);
+        let return_animation_time = self.dnd_state.return_animation_time(ui);
+        let snap = self
+            .dnd_state
+            .config(ui)
+            .slot_snapping
+            .zip(self.dnd_state.snap_position)
+            .filter(|_| !is_keyboard_drag);
         if let DragDetectionState::Dragging {
             id: dragging_id,
             offset,
@@ -63,26 +99,38 @@ impl<'a> Item<'a> {
         {
             // Draw the item item in it's original position in the first frame to avoid flickering
             if id == *dragging_id {
-                ui.output_mut(|o| o.cursor_icon = CursorIcon::Grabbing);
-
-                let _layer_id = LayerId::new(Order::Tooltip, id);
-
-                let pointer_pos = ui
-                    .ctx()
-                    .pointer_hover_pos()
-                    .or(last_pointer_pos)
-                    .unwrap_or_else(|| ui.next_widget_position());
+                let pointer_pos = if is_keyboard_drag {
+                    last_pointer_pos
+                } else {
+                    ui.ctx().pointer_hover_pos().or(last_pointer_pos)
+                }
+                .unwrap_or_else(|| ui.next_widget_position());
                 let position = pointer_pos + *offset;
+                // With slot snapping, the item is pulled toward the slot it would be dropped in
+                let position = if let Some((strength, slot)) = snap {
+                    position + (slot - position) * strength.clamp(0.0, 1.0)
+                } else {
+                    position
+                };
 
-                // We animate so the animated position is updated, even though we don't use it here.
-                animate_position(
+                // We animate so the animated position is updated, even though we only use it
+                // when dragging with the keyboard or snapping to slots, where the item should move
+                // smoothly between slots.
+                // The gap moves with the content when the list is scrolled, so the item follows it
+                // instead of sliding over from where the gap was before.
+                let animated_position = animate_item_position(
                     ui,
                     id,
                     position,
-                    self.dnd_state.swap_animation_time,
+                    shift_animation_time,
                     self.easing,
-                    false,
+                    is_keyboard_drag,
                 );
+                let position = if is_keyboard_drag || snap.is_some() {
+                    animated_position
+                } else {
+                    position
+                };
 
                 let InnerResponse { inner: rect, .. } = Self::draw_floating_at_position(
                     self.state,
@@ -102,7 +150,7 @@ impl<'a> Item<'a> {
             }
         } else if let DragDetectionState::TransitioningBackAfterDragFinished {
             id: transitioning_id,
-            dragged_item_size: _,
+            dragged_item_size,
         } = &mut self.dnd_state.detection_state
         {
             if id == *transitioning_id {
@@ -110,14 +158,16 @@ impl<'a> Item<'a> {
                     let (_, rect) = ui.allocate_space(size);
                     (rect.min, Some(rect))
                 } else {
-                    (ui.next_widget_position(), None)
+                    let size = dragged_item_size.unwrap_or_default();
+                    let end_rect = content_rect(ui.layout(), ui.next_widget_position(), size);
+                    (end_rect.min, None)
                 };
 
-                let position = animate_position(
+                let position = animate_item_position(
                     ui,
                     id,
                     end_pos,
-                    self.dnd_state.return_animation_time,
+                    return_animation_time,
                     self.easing,
                     false,
                 );
@@ -148,22 +198,27 @@ impl<'a> Item<'a> {
             }
         }
 
+        let cut = self.state.cut;
+        let shapes_start = ui.painter().add(Shape::Noop);
         let was_dragging = self.dnd_state.detection_state.is_dragging();
+        let lock_cross_axis_size = self.dnd_state.config(ui).lock_cross_axis_size;
+        let locked_size = if was_dragging && lock_cross_axis_size {
+            self.dnd_state.item_sizes.get(&id).copied()
+        } else {
+            None
+        };
 
+        // In reversed layouts, the handle is created before we know how far above or left of its
+        // position the contents end up, so the offset of a drag started in it is corrected after
+        let mut content_offset = Vec2::ZERO;
         let rect = if let Some(size) = size {
             // We need to do it like this because in some layouts
             // ui.next_widget_position() will return the vertical center instead
             // of the top left corner
             let (_, rect) = ui.allocate_space(size);
 
-            let animated_position = animate_position(
-                ui,
-                id,
-                rect.min,
-                self.dnd_state.swap_animation_time,
-                self.easing,
-                true,
-            );
+            let animated_position =
+                animate_item_position(ui, id, rect.min, shift_animation_time, self.easing, true);
 
             let position = if self.dnd_state.detection_state.is_dragging() {
                 animated_position
@@ -182,6 +237,7 @@ impl<'a> Item<'a> {
                         self.dnd_state,
                         hovering_over_any_handle,
                         rect.min,
+                        handle_mode,
                     ),
                     self.state,
                 )
@@ -190,14 +246,8 @@ impl<'a> Item<'a> {
             rect
         } else {
             let position = ui.next_widget_position();
-            let animated_position = animate_position(
-                ui,
-                id,
-                position,
-                self.dnd_state.swap_animation_time,
-                self.easing,
-                true,
-            );
+            let animated_position =
+                animate_item_position(ui, id, position, shift_animation_time, self.easing, true);
 
             let position = if self.dnd_state.detection_state.is_dragging() {
                 animated_position
@@ -208,7 +258,15 @@ impl<'a> Item<'a> {
             let size = ui.available_size();
 
             let mut child = ui.child_ui(ui.max_rect(), *ui.layout());
-            let response = child.allocate_ui_at_rect(Rect::from_min_size(position, size), |ui| {
+            let content_rect = content_rect(ui.layout(), position, size);
+            let response = child.allocate_ui_at_rect(content_rect, |ui| {
+                if let Some(locked_size) = locked_size {
+                    if ui.layout().main_dir().is_horizontal() {
+                        ui.set_min_height(locked_size.y);
+                    } else {
+                        ui.set_min_width(locked_size.x);
+                    }
+                }
                 drag_body(
                     ui,
                     Handle::new(
@@ -217,21 +275,56 @@ impl<'a> Item<'a> {
                         self.dnd_state,
                         hovering_over_any_handle,
                         animated_position,
+                        handle_mode,
                     ),
                     self.state,
                 )
             });
 
+            if !matches!(
+                ui.layout().main_dir(),
+                Direction::TopDown | Direction::LeftToRight
+            ) {
+                content_offset = response.response.rect.min - position;
+            }
+
             ui.allocate_space(response.response.rect.size()).1
         };
 
+        if cut {
+            fade_shapes(ui, shapes_start, CUT_ITEM_OPACITY);
+        }
+        if self.z_index != 0 {
+            let shapes_end = ui.painter().add(Shape::Noop);
+            self.dnd_state
+                .paint_order
+                .push((self.z_index, shapes_start.0..shapes_end.0));
+        }
+
+        // Items shown after the drag started in this frame, or added during the drag, keep the
+        // size they were first shown with
+        if lock_cross_axis_size
+            && (!was_dragging
+                || self.dnd_state.layout_changed
+                || !self.dnd_state.item_sizes.contains_key(&id))
+        {
+            self.dnd_state.item_sizes.insert(id, rect.size());
+        }
+
         if !was_dragging && self.dnd_state.detection_state.is_dragging() {
+            // Keyboard drags are placed at the gap, without an offset
+            if self.dnd_state.detection_state.is_keyboard_dragging() {
+                content_offset = Vec2::ZERO;
+            }
             if let DragDetectionState::Dragging {
-                dragged_item_size, ..
+                dragged_item_size,
+                offset,
+                ..
             } = &mut self.dnd_state.detection_state
             {
                 // We set this here because we don't know the size in the handle
                 *dragged_item_size = rect.size();
+                *offset += content_offset;
             }
         }
 
@@ -249,24 +342,118 @@ impl<'a> Item<'a> {
         size: Option<Vec2>,
         body: impl FnOnce(&mut Ui, Handle, ItemState),
     ) -> InnerResponse<Rect> {
-        egui::Area::new("draggable_item")
+        let layer_id = dnd_state.config(ui).floating_item_layer;
+        // The area isn't constrained to the screen, so the item stays under the pointer near the
+        // edges, and its ui isn't clipped by the list's clip rect
+        egui::Area::new(layer_id.id)
+            .order(layer_id.order)
             .interactable(false)
+            .constrain(false)
             .fixed_pos(pos)
             .show(ui.ctx(), |ui| {
-                ui.scope(|ui| {
-                    if let Some(size) = size.or(dnd_state.detection_state.dragged_item_size()) {
-                        ui.set_max_size(size);
-                    }
-                    body(
-                        ui,
-                        Handle::new(id, state.index, dnd_state, hovering_over_any_handle, pos),
-                        state,
-                    )
-                })
-                .response
-                .rect
+                ui.set_clip_rect(ui.ctx().screen_rect());
+                // The layer is shared with other lists, so the ghost only copies these shapes
+                let first_shape = ui.painter().add(Shape::Noop);
+                let rect = ui
+                    .scope(|ui| {
+                        if let Some(size) = size.or(dnd_state.detection_state.dragged_item_size()) {
+                            ui.set_max_size(size);
+                        }
+                        body(
+                            ui,
+                            Handle::new(
+                                id,
+                                state.index,
+                                dnd_state,
+                                hovering_over_any_handle,
+                                pos,
+                                HandleMode::Draggable,
+                            ),
+                            state,
+                        )
+                    })
+                    .response
+                    .rect;
+                let end_shape = ui.painter().add(Shape::Noop);
+                dnd_state.floating_shapes = Some(first_shape.0..end_shape.0);
+                rect
             })
     }
 }
 
 pub struct ItemResponse(pub(crate) Rect);
+
+/// The rect of the given size that contents starting at `position` are laid out in. In reversed
+/// layouts, like [egui::Layout::bottom_up], the contents start at the bottom or right edge.
+pub(crate) fn content_rect(layout: &Layout, position: Pos2, size: Vec2) -> Rect {
+    match layout.main_dir() {
+        Direction::BottomUp => Rect::from_min_size(position - Vec2::new(0.0, size.y), size),
+        Direction::RightToLeft => Rect::from_min_size(position - Vec2::new(size.x, 0.0), size),
+        Direction::TopDown | Direction::LeftToRight => Rect::from_min_size(position, size),
+    }
+}
+
+/// Animates the position of an item, with a spring if the `spring_animations` feature is enabled
+fn animate_item_position(
+    ui: &mut Ui,
+    id: Id,
+    value: Pos2,
+    time: f32,
+    easing: fn(f32) -> f32,
+    scroll_correction: bool,
+) -> Pos2 {
+    #[cfg(feature = "spring_animations")]
+    {
+        let _ = easing;
+        egui_animation::animate_position_spring(ui, id, value, time, scroll_correction)
+    }
+    #[cfg(not(feature = "spring_animations"))]
+    egui_animation::animate_position(ui, id, value, time, easing, scroll_correction)
+}
+
+/// Reorders the shapes painted to the layer of the ui since the first of the `ranges`, so the
+/// shapes of the ranges with a higher z index are painted above the others, see [Item::z_index].
+/// Shapes outside of the ranges have a z index of 0.
+pub(crate) fn sort_shapes_by_z_index(ui: &Ui, ranges: &[(i32, Range<usize>)]) {
+    let Some(start) = ranges.iter().map(|(_, range)| range.start).min() else {
+        return;
+    };
+    let layer_id = ui.layer_id();
+    ui.ctx().graphics_mut(|graphics| {
+        let list = graphics.list(layer_id);
+        let shapes: Vec<_> = list.all_entries().skip(start).cloned().collect();
+        let z_index = |idx: usize| {
+            ranges
+                .iter()
+                .find(|(_, range)| range.contains(&idx))
+                .map_or(0, |(z_index, _)| *z_index)
+        };
+        let mut order: Vec<_> = (start..start + shapes.len()).collect();
+        // The sort is stable, so shapes with the same z index keep their order
+        order.sort_by_key(|idx| z_index(*idx));
+        for (target, source) in (start..).zip(order) {
+            let ClippedShape { clip_rect, shape } = shapes[source - start].clone();
+            list.set(ShapeIdx(target), clip_rect, shape);
+        }
+    });
+}
+
+/// Fades the shapes painted to the layer of the ui since `start`
+pub(crate) fn fade_shapes(ui: &Ui, start: ShapeIdx, opacity: f32) {
+    let layer_id = ui.layer_id();
+    ui.ctx().graphics_mut(|graphics| {
+        let list = graphics.list(layer_id);
+        let shapes: Vec<_> = list.all_entries().skip(start.0).cloned().collect();
+        for (
+            offset,
+            ClippedShape {
+                clip_rect,
+                mut shape,
+            },
+        ) in shapes.into_iter().enumerate()
+        {
+            adjust_colors(&mut shape, &|color| *color = color.gamma_multiply(opacity));
+            list.set(ShapeIdx(start.0 + offset), clip_rect, shape);
+        }
+    });
+}
@@ -1,34 +1,76 @@
-use crate::item::{Item, ItemResponse};
-use crate::state::DragDetectionState;
+use crate::external::ExternalHover;
+use crate::item::{content_rect, Item, ItemResponse};
+use crate::state::{keyboard_focus_id, DragConstraints, DragDetectionState};
+use crate::transition::show_entering;
 use crate::{DragDropUi, ItemState};
-use egui::{Id, Layout, Pos2, Rect, Ui, Vec2};
+use egui::{Direction, Id, Layout, Pos2, Rect, Sense, Ui, Vec2};
+use std::collections::HashMap;
+#[cfg(debug_assertions)]
+use std::collections::HashSet;
 
 pub struct ItemIterator<'a> {
     state: &'a mut DragDropUi,
+    constraints: &'a DragConstraints<'a>,
     dragged_item_rect: Option<Rect>,
+    /// Something dragged in from outside of the list that a gap is opened for
+    external_hover: Option<ExternalHover>,
     hovering_item: Option<(Id, Pos2)>,
     layout: Layout,
     set_next_item_as_hovering_above: bool,
     pub(crate) hovering_last_item: bool,
     pub(crate) last_item: Option<(usize, Id, Pos2)>,
+    /// All items shown so far, used to move the gap to another item when snapping to an allowed slot
+    pub(crate) items: Vec<(usize, Id, Pos2)>,
 
     pub(crate) mark_next_as_closest_item: Option<(f32, Pos2)>,
 
     pub(crate) is_after_dragged_item: bool,
     pub(crate) is_after_hovered_item: bool,
     pub(crate) hovering_over_any_handle: bool,
+    /// The space reserved for the dragged item at the position it would be dropped at
+    pub(crate) gap_rect: Option<Rect>,
+    /// The area covered by all items shown so far
+    pub(crate) items_rect: Rect,
     pub(crate) source_item: Option<(usize, Id)>,
+    /// If the item is dragged with the keyboard, the slot the gap should be shown at.
+    /// In that case the gap position isn't calculated from the dragged item's position.
+    keyboard_target: Option<usize>,
+    /// The number of items shown so far, not counting the dragged item
+    pub(crate) non_dragged_items: usize,
+    /// The rects of the items shown so far, not counting the dragged item
+    pub(crate) item_rects: Vec<Rect>,
+    /// The responses for the rects of the items shown so far
+    pub(crate) item_responses: HashMap<Id, egui::Response>,
+    /// The item the pointer is over that the dragged item can be dropped onto, see
+    /// [crate::Dnd::drop_onto]
+    pub(crate) onto_item: Option<(usize, Id)>,
+    /// Whether dropping onto `onto_item` merges the items, see [crate::Dnd::merge]
+    pub(crate) onto_merges: bool,
+    /// The ids of all items shown so far, to detect items that share an id
+    #[cfg(debug_assertions)]
+    seen_ids: HashSet<Id>,
+    /// Ids used by more than one item, with the position of the item that reused it
+    #[cfg(debug_assertions)]
+    pub(crate) duplicate_ids: Vec<(Id, Pos2)>,
 
     #[allow(clippy::type_complexity)]
     pub(crate) closest_item: Option<(f32, Option<(usize, Id, Pos2)>)>,
 }
 
 impl<'a> ItemIterator<'a> {
-    pub fn new(state: &'a mut DragDropUi, dragged_item_rect: Option<Rect>, layout: Layout) -> Self {
+    pub fn new(
+        state: &'a mut DragDropUi,
+        constraints: &'a DragConstraints<'a>,
+        dragged_item_rect: Option<Rect>,
+        external_hover: Option<ExternalHover>,
+        layout: Layout,
+    ) -> Self {
+        let keyboard_target = state.detection_state.keyboard_drag().map(|k| k.target);
+
         let hovering_item = match state.detection_state {
             DragDetectionState::Dragging {
                 closest_item: item, ..
-            } => Some(item),
+            } if keyboard_target.is_none() => Some(item),
             _ => None,
         };
 
@@ -41,7 +83,9 @@ impl<'a> ItemIterator<'a> {
 
         Self {
             state,
+            constraints,
             dragged_item_rect,
+            external_hover,
             layout,
             set_next_item_as_hovering_above: false,
             closest_item: None,
@@ -49,11 +93,24 @@ impl<'a> ItemIterator<'a> {
             mark_next_as_closest_item: None,
             hovering_last_item,
             last_item: None,
+            items: Vec::new(),
 
             is_after_dragged_item: false,
             is_after_hovered_item: false,
             hovering_over_any_handle: false,
+            gap_rect: None,
+            items_rect: Rect::NOTHING,
             source_item: None,
+            keyboard_target,
+            non_dragged_items: 0,
+            item_rects: Vec::new(),
+            item_responses: HashMap::new(),
+            onto_item: None,
+            onto_merges: false,
+            #[cfg(debug_assertions)]
+            seen_ids: HashSet::new(),
+            #[cfg(debug_assertions)]
+            duplicate_ids: Vec::new(),
         }
     }
 
@@ -65,6 +122,11 @@ impl<'a> ItemIterator<'a> {
         add_surrounding_space_automatically: bool,
         content: impl FnOnce(&mut Ui, Item) -> ItemResponse,
     ) {
+        #[cfg(debug_assertions)]
+        if !self.seen_ids.insert(id) {
+            self.duplicate_ids.push((id, ui.next_widget_position()));
+        }
+
         let is_dragged_item = self.state.detection_state.is_dragging_item(id);
 
         if let Some((distance, pos)) = self.mark_next_as_closest_item {
@@ -76,29 +138,82 @@ impl<'a> ItemIterator<'a> {
             self.is_after_dragged_item = true;
         }
 
+        let preceding_items = self.non_dragged_items;
+        if !is_dragged_item {
+            if self.keyboard_target == Some(self.non_dragged_items) {
+                self.hovering_item = Some((id, ui.next_widget_position()));
+            }
+            self.non_dragged_items += 1;
+        }
+
         if let Some((hovering_id, _pos)) = self.hovering_item {
             if hovering_id == id {
                 self.is_after_hovered_item = true;
             }
         }
 
+        self.state.transitions.show_exiting(ui, Some(id));
+        let entering = if is_dragged_item {
+            None
+        } else {
+            self.state.transitions.enter_progress(ui, id)
+        };
+
+        if let Some(hover) = self
+            .external_hover
+            .filter(|hover| !is_dragged_item && hover.slot == preceding_items)
+        {
+            self.add_external_gap(ui, hover.size);
+        }
+
         if add_surrounding_space_automatically {
             self.space_before(ui, id, |_ui, _space| {})
         }
 
         let dragging = self.state.detection_state.is_dragging();
 
+        let (target_index, drag_delta, is_displaced) = if is_dragged_item {
+            let pointer_pos = ui.ctx().pointer_hover_pos();
+            let (target_index, drag_delta) = self
+                .state
+                .detection_state
+                .dragged_item_progress(pointer_pos)
+                .unwrap_or((idx, Vec2::ZERO));
+            (target_index, drag_delta, false)
+        } else if let Some((original_idx, target_index)) = self
+            .state
+            .detection_state
+            .displaced_indices(preceding_items)
+        {
+            (target_index, Vec2::ZERO, target_index != original_idx)
+        } else {
+            (idx, Vec2::ZERO, false)
+        };
+
         let item = Item::new(
             id,
             ItemState {
                 dragged: is_dragged_item,
                 index: idx,
+                target_index,
+                drag_delta,
+                is_displaced,
+                selected: ui.memory(|mem| mem.has_focus(keyboard_focus_id(id))),
+                cut: self.state.cut_item == Some(id),
+                hold_progress: self.state.hold_progress(ui, id),
             },
             self.state,
             &mut self.hovering_over_any_handle,
+            self.constraints.handle_mode(idx),
         );
+        let mut full_size = None;
         let rect = if is_dragged_item {
-            if let Some((_id, pos)) = self.hovering_item {
+            // When dragging with the keyboard, the gap might come after the dragged item
+            let floating_pos = self
+                .hovering_item
+                .map(|(_id, pos)| pos)
+                .or(self.keyboard_target.map(|_| ui.next_widget_position()));
+            if let Some(pos) = floating_pos {
                 let mut child = ui.child_ui(ui.available_rect_before_wrap(), *ui.layout());
                 let start = ui.next_widget_position();
                 let rect = child
@@ -107,14 +222,36 @@ impl<'a> ItemIterator<'a> {
                     })
                     .inner
                     .0;
-                Rect::from_min_size(start, rect.size())
+                content_rect(&self.layout, start, rect.size())
             } else {
                 content(ui, item).0
             }
+        } else if let Some(entering) = entering {
+            let (rect, full_rect) = show_entering(ui, entering, |ui| content(ui, item).0);
+            full_size = Some(full_rect.size());
+            rect
         } else {
             content(ui, item).0
         };
 
+        self.state
+            .transitions
+            .record_item(id, full_size.unwrap_or(rect.size()));
+
+        // This is registered after the item's contents, so the handle and any widgets in the
+        // item take precedence. It's not focusable, so tabbing through the list is unaffected.
+        let item_sense = Sense {
+            click: true,
+            drag: false,
+            focusable: false,
+        };
+        self.item_responses
+            .insert(id, ui.interact(rect, id.with("dnd_item"), item_sense));
+        self.items_rect = self.items_rect.union(rect);
+        if !is_dragged_item {
+            self.item_rects.push(rect);
+        }
+
         if dragging != self.state.detection_state.is_dragging() {
             self.set_next_item_as_hovering_above = true;
         }
@@ -123,9 +260,37 @@ impl<'a> ItemIterator<'a> {
             self.space_after(ui, id, |_ui, _space| {})
         }
 
-        if let Some(dragged_item_rect) = self.dragged_item_rect {
+        // Items without a size, like collapsed rows, can't be hovered. Letting them compete for
+        // the closest item makes the gap jump back and forth between them and their neighbours.
+        let hoverable = rect.is_positive();
+        let drop_onto = self.constraints.accepts_drop_onto(idx);
+        // Dropping into an item takes precedence over merging with it
+        let merge = !drop_onto
+            && self
+                .state
+                .detection_state
+                .source_idx()
+                .is_some_and(|from| self.constraints.accepts_merge(from, idx));
+        if hoverable
+            && !is_dragged_item
+            && self.keyboard_target.is_none()
+            && self.dragged_item_rect.is_some()
+            && (drop_onto || merge)
+            && ui
+                .input(|i| i.pointer.hover_pos())
+                .is_some_and(|pos| onto_zone(rect, &self.layout).contains(pos))
+        {
+            self.onto_item = Some((idx, id));
+            self.onto_merges = merge;
+        }
+        if let (Some(dragged_item_rect), None, true) =
+            (self.dragged_item_rect, self.keyboard_target, hoverable)
+        {
             if self.layout.main_wrap {
-                if rect.contains(dragged_item_rect.center()) {
+                // The spacing around the item counts towards it, so the slot doesn't depend on
+                // which item the dragged item hovered last when its center is between two items
+                let hover_rect = rect.expand2(ui.spacing().item_spacing / 2.0);
+                if hover_rect.contains(dragged_item_rect.center()) {
                     if self.is_after_hovered_item {
                         self.mark_next_as_closest_item = Some((0.0, rect.min));
                     } else {
@@ -143,25 +308,30 @@ impl<'a> ItemIterator<'a> {
         }
 
         self.last_item = Some((idx, id, rect.min));
+        self.items.push((idx, id, rect.min));
+    }
+
+    /// Opens a gap for something dragged in from outside of the list
+    pub(crate) fn add_external_gap(&mut self, ui: &mut Ui, size: Vec2) {
+        let rect = ui.allocate_exact_size(size, Sense::hover()).0;
+        self.gap_rect = Some(rect);
+        self.items_rect = self.items_rect.union(rect);
     }
 
     fn get_distance(&mut self, dragged_item_rect: Rect, rect: Rect) -> (f32, bool) {
-        let size_difference = dragged_item_rect.size() - rect.size();
-        let (distance, mark_next) = if self.layout.is_horizontal() {
-            let distance = dragged_item_rect.center().x - rect.center().x;
-            let mark_next = rect.center().x < dragged_item_rect.center().x;
-            (distance, mark_next)
+        // In reversed layouts, the items after this one are above or left of it
+        let reversed = matches!(
+            self.layout.main_dir(),
+            Direction::BottomUp | Direction::RightToLeft
+        );
+        let axis = if self.layout.is_horizontal() { 0 } else { 1 };
+        let distance = dragged_item_rect.center()[axis] - rect.center()[axis];
+        let mark_next = if (dragged_item_rect.size()[axis] - rect.size()[axis]).abs() > 0.0 {
+            (rect.center()[axis] < dragged_item_rect.center()[axis]) != reversed
         } else {
-            let distance = dragged_item_rect.center().y - rect.center().y;
-            let mark_next = if size_difference.y.abs() > 0.0 {
-                rect.center().y < dragged_item_rect.center().y
-            } else {
-                self.is_after_hovered_item
-            };
-            (distance, mark_next)
+            self.is_after_hovered_item
         };
-        let distance = distance.abs();
-        (distance, mark_next)
+        (distance.abs(), mark_next)
     }
 
     pub fn space_before(&mut self, ui: &mut Ui, id: Id, content: impl FnOnce(&mut Ui, Vec2)) {
@@ -192,8 +362,12 @@ impl<'a> ItemIterator<'a> {
                         })
                         .response
                         .rect;
-                    let (distance, _mark_next) = self.get_distance(dragged_item_rect, rect);
-                    self.check_closest_item(distance, rect.min, None, false);
+                    self.gap_rect = Some(rect);
+                    self.items_rect = self.items_rect.union(rect);
+                    if self.keyboard_target.is_none() {
+                        let (distance, _mark_next) = self.get_distance(dragged_item_rect, rect);
+                        self.check_closest_item(distance, rect.min, None, false);
+                    }
                 }
             }
         }
@@ -215,3 +389,13 @@ impl<'a> ItemIterator<'a> {
         }
     }
 }
+
+/// The middle half of an item along the main axis. Dropping an item there drops it onto the item,
+/// see [crate::Dnd::drop_onto], closer to its edges it's dropped in front of or behind it.
+fn onto_zone(rect: Rect, layout: &Layout) -> Rect {
+    if layout.is_horizontal() {
+        rect.shrink2(Vec2::new(rect.width() / 4.0, 0.0))
+    } else {
+        rect.shrink2(Vec2::new(0.0, rect.height() / 4.0))
+    }
+}
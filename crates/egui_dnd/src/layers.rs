@@ -0,0 +1,309 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use egui::{Id, Label, RichText, Sense, Ui};
+
+use crate::tree::{self, TreeNode};
+use crate::{dnd, DragDropResponse};
+
+/// A layer or a group of layers shown by [LayerPanel]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    /// Identifies the layer, it has to be unique within the whole tree, so the layer keeps its
+    /// selection when it's moved to another group
+    pub id: Id,
+    /// The name shown in the panel
+    pub name: String,
+    /// Whether the layer is visible, toggled with the eye button in front of it
+    pub visible: bool,
+    /// The layers in the group, or `None` if the layer isn't a group
+    pub children: Option<Vec<Layer>>,
+}
+
+impl Layer {
+    /// Creates a visible layer
+    pub fn new(id: impl Hash, name: impl Into<String>) -> Self {
+        Self {
+            id: Id::new(id),
+            name: name.into(),
+            visible: true,
+            children: None,
+        }
+    }
+
+    /// Creates a visible group with the given layers
+    pub fn group(id: impl Hash, name: impl Into<String>, children: Vec<Layer>) -> Self {
+        Self {
+            children: Some(children),
+            ..Self::new(id, name)
+        }
+    }
+
+    /// Whether the layer is a group
+    pub fn is_group(&self) -> bool {
+        self.children.is_some()
+    }
+}
+
+impl TreeNode for Layer {
+    fn id(&self, _parent: Id) -> Id {
+        self.id
+    }
+
+    fn children(&self) -> Option<&Vec<Self>> {
+        self.children.as_ref()
+    }
+
+    fn children_mut(&mut self) -> Option<&mut Vec<Self>> {
+        self.children.as_mut()
+    }
+}
+
+/// A layers panel like in an image editor. Created with [dnd_layer_panel].
+pub struct LayerPanel<'a> {
+    ui: &'a mut Ui,
+    id_source: Id,
+}
+
+/// The response of [LayerPanel::show]
+pub struct LayerPanelResponse {
+    /// The response of the list the visible layers are shown in
+    pub response: DragDropResponse,
+    /// The ids of the selected layers, in the order they are shown
+    pub selected: Vec<Id>,
+    /// Whether the layers were changed this frame, because layers were moved or their visibility
+    /// was toggled
+    pub changed: bool,
+}
+
+/// Shows a tree of layers and groups, like the layers panel of an image editor:
+/// - layers are selected by clicking their name, ctrl (cmd on mac) + click adds or removes a
+///   layer from the selection
+/// - dragging a layer moves it, also into and out of open groups. If it's selected, all selected
+///   layers are moved together and inserted next to each other, in the order they are shown
+/// - dropping layers onto a group moves them into the group, see [crate::Dnd::drop_onto]
+/// - the eye button in front of each layer toggles its visibility, clicking it never starts a
+///   drag
+///
+/// Groups are opened and closed with the arrow in front of them.
+/// Example usage:
+/// ```rust no_run
+/// use eframe::egui;
+/// use egui::CentralPanel;
+/// use egui_dnd::{dnd_layer_panel, Layer};
+///
+/// pub fn main() -> eframe::Result<()> {
+///     let mut layers = vec![
+///         Layer::group(0, "Foreground", vec![Layer::new(1, "Tree"), Layer::new(2, "Bird")]),
+///         Layer::new(3, "Background"),
+///     ];
+///
+///     eframe::run_simple_native("DnD Layers Example", Default::default(), move |ctx, _frame| {
+///         CentralPanel::default().show(ctx, |ui| {
+///             dnd_layer_panel(ui, "layers").show(&mut layers);
+///         });
+///     })
+/// }
+/// ```
+pub fn dnd_layer_panel(ui: &mut Ui, id_source: impl Hash) -> LayerPanel {
+    LayerPanel {
+        ui,
+        id_source: Id::new(id_source),
+    }
+}
+
+impl LayerPanel<'_> {
+    /// Shows the layers and applies the moves and visibility toggles to them
+    pub fn show(self, layers: &mut Vec<Layer>) -> LayerPanelResponse {
+        let LayerPanel { ui, id_source } = self;
+        let open_id = ui.id().with(id_source).with("dnd_layer_panel_open");
+        let selection_id = ui.id().with(id_source).with("dnd_layer_panel_selection");
+        let (mut open, mut selection) = ui.data(|data| {
+            (
+                data.get_temp::<HashSet<Id>>(open_id).unwrap_or_default(),
+                data.get_temp::<HashSet<Id>>(selection_id)
+                    .unwrap_or_default(),
+            )
+        });
+
+        let rows = tree::flatten(layers, id_source, &open);
+
+        let mut toggled = None;
+        let mut eye_clicked = None;
+        let mut clicked = None;
+        let response = dnd(ui, id_source)
+            .drop_onto(|idx| rows.get(idx).is_some_and(|row| row.open.is_some()))
+            .show(rows.iter().cloned(), |ui, row, handle, _state| {
+                let Some(layer) = tree::node_at(layers, &row.path) else {
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    // The eye is outside of the handle, so clicking it can't start a drag
+                    let mut eye = RichText::new("👁");
+                    if !layer.visible {
+                        eye = eye.weak();
+                    }
+                    if ui
+                        .small_button(eye)
+                        .on_hover_text("Toggle visibility")
+                        .clicked()
+                    {
+                        eye_clicked = Some(row.path.clone());
+                    }
+                    ui.add_space(ui.spacing().indent * row.depth() as f32);
+                    match row.open {
+                        Some(is_open) => {
+                            if ui.small_button(if is_open { "⏷" } else { "⏵" }).clicked() {
+                                toggled = Some(row.id);
+                            }
+                        }
+                        None => ui.add_space(ui.spacing().icon_width),
+                    }
+                    let response = handle.sense(Sense::click()).ui(ui, |ui| {
+                        let mut text = RichText::new(&layer.name);
+                        if selection.contains(&row.id) {
+                            text = text.color(ui.visuals().selection.stroke.color);
+                        }
+                        if !layer.visible {
+                            text = text.weak();
+                        }
+                        ui.add(Label::new(text).selectable(false));
+                    });
+                    if response.clicked() {
+                        clicked = Some((row.id, ui.input(|i| i.modifiers.command)));
+                    }
+                });
+            });
+
+        if let Some(id) = toggled {
+            if !open.remove(&id) {
+                open.insert(id);
+            }
+        }
+        match clicked {
+            Some((id, true)) => {
+                if !selection.remove(&id) {
+                    selection.insert(id);
+                }
+            }
+            Some((id, false)) => {
+                selection.clear();
+                selection.insert(id);
+            }
+            None => {}
+        }
+
+        // Highlight the group the layers would be dropped into
+        let onto = response
+            .update
+            .as_ref()
+            .filter(|_| response.is_dragging())
+            .and_then(|update| update.onto)
+            .and_then(|idx| rows.get(idx));
+        if let Some(item) = onto.and_then(|group| response.item_response(group.id)) {
+            ui.painter().rect_stroke(
+                item.rect,
+                ui.visuals().widgets.active.rounding,
+                ui.visuals().selection.stroke,
+            );
+        }
+
+        let mut changed = false;
+        if let Some(path) = eye_clicked {
+            if let Some(layer) = path.split_last().and_then(|(idx, parent)| {
+                tree::children_mut(layers, parent).and_then(|siblings| siblings.get_mut(*idx))
+            }) {
+                layer.visible = !layer.visible;
+                changed = true;
+            }
+        }
+        if let Some(update) = response.final_update() {
+            let target = tree::drop_target(&rows, layers.len(), &update);
+            if let (Some(dragged), Some(target)) = (rows.get(update.from), target) {
+                // A selected layer takes the other selected layers with it
+                let sources = if selection.contains(&dragged.id) {
+                    let mut sources = Vec::new();
+                    selected_paths(layers, &selection, &mut Vec::new(), &mut sources);
+                    sources
+                } else {
+                    vec![dragged.path.clone()]
+                };
+                changed |= move_layers(layers, &sources, target);
+            }
+        }
+
+        let mut ids = Vec::new();
+        layer_ids(layers, &mut ids);
+        // Layers that were removed can't be selected anymore
+        selection.retain(|id| ids.contains(id));
+        let selected = ids
+            .into_iter()
+            .filter(|id| selection.contains(id))
+            .collect();
+        ui.data_mut(|data| {
+            data.insert_temp(open_id, open);
+            data.insert_temp(selection_id, selection);
+        });
+
+        LayerPanelResponse {
+            response,
+            selected,
+            changed,
+        }
+    }
+}
+
+/// Adds the ids of all layers to `ids`, in the order they are shown if all groups are open
+fn layer_ids(layers: &[Layer], ids: &mut Vec<Id>) {
+    for layer in layers {
+        ids.push(layer.id);
+        if let Some(children) = &layer.children {
+            layer_ids(children, ids);
+        }
+    }
+}
+
+/// Adds the paths of the selected layers to `paths`, in the order they are shown. Layers in a
+/// selected group are skipped, since they are moved with the group.
+fn selected_paths(
+    layers: &[Layer],
+    selection: &HashSet<Id>,
+    path: &mut Vec<usize>,
+    paths: &mut Vec<Vec<usize>>,
+) {
+    for (idx, layer) in layers.iter().enumerate() {
+        path.push(idx);
+        if selection.contains(&layer.id) {
+            paths.push(path.clone());
+        } else if let Some(children) = &layer.children {
+            selected_paths(children, selection, path, paths);
+        }
+        path.pop();
+    }
+}
+
+/// Moves the layers at `sources`, which are in the order they are shown, so they end up next to
+/// each other at `target`. Returns false if the target is inside of one of the moved groups.
+fn move_layers(layers: &mut Vec<Layer>, sources: &[Vec<usize>], mut target: Vec<usize>) -> bool {
+    if sources.is_empty()
+        || sources
+            .iter()
+            .any(|source| tree::is_inside(&target, source))
+    {
+        return false;
+    }
+    // Removing from the back keeps the paths of the layers before valid
+    let mut moved: Vec<Layer> = sources
+        .iter()
+        .rev()
+        .filter_map(|source| tree::remove(layers, source, &mut target))
+        .collect();
+    moved.reverse();
+    for layer in moved {
+        tree::insert(layers, &target, layer);
+        if let Some(idx) = target.last_mut() {
+            *idx = idx.saturating_add(1);
+        }
+    }
+    true
+}
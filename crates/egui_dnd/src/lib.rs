@@ -2,30 +2,207 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
-use egui::{Id, Ui};
-pub use state::{DragDropConfig, DragDropItem, DragDropResponse, DragUpdate, Handle};
+pub use calendar::{dnd_calendar, Calendar, CalendarMove, CalendarResponse};
+pub use command_list::{dnd_command_list, CommandList};
+pub use container::{DndContainer, DndSortable};
+use egui::collapsing_header::{paint_default_icon, CollapsingState};
+use egui::mutex::Mutex;
+use egui::util::IdTypeMap;
+use egui::{Context, DragAndDrop, Id, Pos2, Ui};
+pub use external::{DndPayload, ExternalDrop};
+pub use file_list::{dnd_file_list, FileEntry, FileList, FileListResponse};
+pub use form_builder::{dnd_form_builder, FormBuilder, FormBuilderResponse};
+pub use gallery::{dnd_gallery, Gallery, GalleryResponse};
+pub use inspector::inspector;
+pub use layers::{dnd_layer_panel, Layer, LayerPanel, LayerPanelResponse};
+pub use playlist::{dnd_playlist, Playlist, PlaylistResponse};
+pub use ranked_list::RankedList;
+pub use sidebar::{dnd_sidebar, Sidebar};
+pub use state::{
+    AutoScroll, CancellationReason, ConfigWarning, DndEvent, DragCursors, DragDropConfig,
+    DragDropItem, DragDropResponse, DragMode, DragUpdate, DropDecision, DropPosition,
+    DropTargetHighlight, FocusLossBehavior, Handle,
+};
+pub use tabs::{dnd_tabs, DndTabs, TabsResponse};
+pub use todo_list::{dnd_todo_list, TodoEvent, TodoList, TodoListResponse};
 
+use crate::history::History;
+use crate::item::{Item, ItemResponse};
 use crate::item_iterator::ItemIterator;
-use crate::state::DragDropUi;
+use crate::state::{DragConstraints, DragDropUi};
+use std::any::Any;
 use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Duration;
 
+#[cfg(feature = "accesskit")]
+mod accessibility;
+mod calendar;
+mod command_list;
+mod container;
+mod external;
+mod file_list;
+mod form_builder;
+mod gallery;
+mod history;
+mod inspector;
+#[cfg(feature = "tracing")]
+mod instrument;
 mod item;
 mod item_iterator;
+mod layers;
+mod persist;
+mod playlist;
+mod ranked_list;
+mod sidebar;
 mod state;
+mod tabs;
+#[cfg(feature = "test_utils")]
+pub mod test_utils;
+mod todo_list;
+mod transition;
+mod tree;
 /// Helper functions to support the drag and drop functionality
 pub mod utils;
 
 /// Helper struct for ease of use.
 pub struct Dnd<'a> {
     id: Id,
+    /// The id of the id source, without the scope of the ui, see [DndPayload::is_from]
+    source: Id,
     ui: &'a mut Ui,
     drag_drop_ui: DragDropUi,
+    callbacks: Callbacks<'a>,
+    constraints: DragConstraints<'a>,
+    commit_strategy: CommitStrategy,
+    /// Where the order of the items is stored, see [Dnd::persist_order]
+    persist_order: Option<Id>,
+}
+
+/// When [Dnd::show_vec] and the other `_vec` methods reorder the items, see [Dnd::commit_strategy]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommitStrategy {
+    /// The items are reordered continuously while dragging, so they always match what is shown
+    #[default]
+    Live,
+    /// The items are only reordered once the dragged item is dropped. Until then, the new order
+    /// is only previewed, so anything else looking at the items, like an autosave or a sync,
+    /// never sees an intermediate order.
+    OnDrop,
+}
+
+impl CommitStrategy {
+    fn apply<T>(self, response: &DragDropResponse, items: &mut [T]) {
+        match self {
+            CommitStrategy::Live => response.update_vec(items),
+            CommitStrategy::OnDrop => {
+                if let Some(update) = response.final_update().filter(|u| u.onto.is_none()) {
+                    utils::shift_vec(update.from, update.to, items);
+                }
+            }
+        }
+    }
+}
+
+/// Callbacks that are invoked once per drag & drop event, see [Dnd::on_pickup]
+#[derive(Default)]
+struct Callbacks<'a> {
+    on_pickup: Option<Box<dyn FnMut() + 'a>>,
+    on_drag_start: Option<Box<dyn FnMut(Id, usize) + 'a>>,
+    on_slot_change: Option<Box<dyn FnMut() + 'a>>,
+    on_hover_index_changed: Option<Box<dyn FnMut(usize) + 'a>>,
+    on_drop: Option<Box<dyn FnMut(DragUpdate) + 'a>>,
+    on_cancel: Option<Box<dyn FnMut(CancellationReason) + 'a>>,
+    on_dragged_out_of_window: Option<Box<dyn FnMut(Id, Pos2) + 'a>>,
+    on_merge: Option<Box<dyn FnMut(usize, usize) + 'a>>,
+    on_spring_open: Option<Box<dyn FnMut(Id, usize) + 'a>>,
+    on_spring_close: Option<Box<dyn FnMut(Id, usize) + 'a>>,
+    on_item_context_menu: Option<Box<dyn FnMut(&mut Ui, usize) + 'a>>,
+}
+
+impl Callbacks<'_> {
+    /// Attaches the menu of [Dnd::on_item_context_menu] to the items. No menu is opened while an
+    /// item is dragged.
+    fn context_menus(&mut self, response: &DragDropResponse) {
+        let Some(menu) = self
+            .on_item_context_menu
+            .as_mut()
+            .filter(|_| !response.is_dragging())
+        else {
+            return;
+        };
+        for (idx, id) in response.shown_items() {
+            if let Some(item) = response.item_response(*id) {
+                item.clone().context_menu(|ui| menu(ui, *idx));
+            }
+        }
+    }
+
+    fn call(&mut self, response: &DragDropResponse) {
+        for event in response.events() {
+            match event {
+                DndEvent::DragStarted { id, index } => {
+                    if let Some(on_pickup) = &mut self.on_pickup {
+                        on_pickup();
+                    }
+                    if let Some(on_drag_start) = &mut self.on_drag_start {
+                        on_drag_start(*id, *index);
+                    }
+                }
+                DndEvent::TargetChanged { to, .. } => {
+                    if let Some(on_slot_change) = &mut self.on_slot_change {
+                        on_slot_change();
+                    }
+                    if let Some(on_hover_index_changed) = &mut self.on_hover_index_changed {
+                        on_hover_index_changed(*to);
+                    }
+                }
+                DndEvent::Dropped { update, .. } => {
+                    if let Some(on_drop) = &mut self.on_drop {
+                        on_drop(update.clone());
+                    }
+                }
+                DndEvent::DropVetoed { .. } => {}
+                DndEvent::DraggedOutOfWindow { id, pointer_pos } => {
+                    if let Some(on_dragged_out_of_window) = &mut self.on_dragged_out_of_window {
+                        on_dragged_out_of_window(*id, *pointer_pos);
+                    }
+                }
+                DndEvent::Merge { source, target, .. } => {
+                    if let Some(on_merge) = &mut self.on_merge {
+                        on_merge(*source, *target);
+                    }
+                }
+                DndEvent::SpringOpened { id, index } => {
+                    if let Some(on_spring_open) = &mut self.on_spring_open {
+                        on_spring_open(*id, *index);
+                    }
+                }
+                DndEvent::SpringClosed { id, index } => {
+                    if let Some(on_spring_close) = &mut self.on_spring_close {
+                        on_spring_close(*id, *index);
+                    }
+                }
+                DndEvent::Cancelled { reason, .. } => {
+                    if let Some(on_cancel) = &mut self.on_cancel {
+                        on_cancel(reason.clone());
+                    }
+                }
+            }
+        }
+    }
 }
 
 /// Main entry point for the drag and drop functionality.
 /// Loads and saves it's state from egui memory.
 /// Use either [Dnd::show] or [Dnd::show_vec] to display the drag and drop UI.
-/// You can use [Dnd::with_mouse_config] or [Dnd::with_touch_config] to configure the drag detection.
+/// You can use [Dnd::with_mouse_config], [Dnd::with_touch_config] or [Dnd::with_pen_config] to configure the drag detection.
+/// The state is scoped by the id of the `ui`, like [Ui::make_persistent_id], so lists with the
+/// same `id_source` in different windows or tabs don't interfere with each other.
+/// Use [Dnd::global] if the list moves between uis, e.g. from one window to another.
+/// Lists can be shown in reversed layouts like [egui::Layout::bottom_up], e.g. for a chat with the
+/// newest message at the bottom. Show the items in the order of your data, the indices of updates
+/// follow that order and the arrow keys move the dragged item in the direction they point.
 /// Example usage:
 /// ```rust no_run
 /// use std::hash::Hash;
@@ -52,17 +229,27 @@ pub struct Dnd<'a> {
 /// }
 /// ```
 pub fn dnd(ui: &mut Ui, id_source: impl Hash) -> Dnd {
-    let id = Id::new(id_source).with("dnd");
-    let mut dnd_ui: DragDropUi =
-        ui.data_mut(|data| (*data.get_temp_mut_or_default::<DragDropUi>(id)).clone());
+    let source = Id::new(id_source).with("dnd");
+    let id = ui.id().with(source);
+    Dnd::load(ui, id, source)
+}
 
-    dnd_ui.return_animation_time = ui.style().animation_time;
-    dnd_ui.swap_animation_time = ui.style().animation_time;
+/// The ids of the lists created with an id source, so [Dnd::forget] can remove all of them,
+/// or of all lists under [ListScopes::all_lists], for the [inspector].
+/// A list is only registered when its state is created, so this doesn't grow while it's shown.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ListScopes(pub(crate) Vec<Id>);
 
-    Dnd {
-        id,
-        ui,
-        drag_drop_ui: dnd_ui,
+impl ListScopes {
+    pub(crate) fn all_lists() -> Id {
+        Id::new("egui_dnd_lists")
+    }
+
+    fn register(data: &mut IdTypeMap, key: Id, id: Id) {
+        let scopes = data.get_temp_mut_or_default::<ListScopes>(key);
+        if !scopes.0.contains(&id) {
+            scopes.0.push(id);
+        }
     }
 }
 
@@ -72,6 +259,63 @@ impl<'a> Dnd<'a> {
         dnd(ui, id_source)
     }
 
+    /// Like [dnd], but the state isn't scoped by the id of the `ui`, so all lists with the same
+    /// `id_source` share it. This keeps a drag going if the list is shown in a different ui from
+    /// one frame to the next, e.g. when it's moved into another window or panel.
+    pub fn global(ui: &'a mut Ui, id_source: impl Hash) -> Self {
+        let id = Id::new(id_source).with("dnd");
+        Self::load(ui, id, id)
+    }
+
+    fn load(ui: &'a mut Ui, id: Id, source: Id) -> Self {
+        let mut dnd_ui: DragDropUi = ui.data_mut(|data| {
+            let mut is_new = false;
+            let dnd_ui = data
+                .get_temp_mut_or_insert_with(id, || {
+                    is_new = true;
+                    DragDropUi::default()
+                })
+                .clone();
+            if is_new {
+                ListScopes::register(data, ListScopes::all_lists(), id);
+                if id != source {
+                    ListScopes::register(data, source.with("scopes"), id);
+                }
+            }
+            dnd_ui
+        });
+
+        dnd_ui.return_animation_time = ui.style().animation_time;
+        dnd_ui.swap_animation_time = ui.style().animation_time;
+
+        Dnd {
+            id,
+            source,
+            ui,
+            drag_drop_ui: dnd_ui,
+            callbacks: Callbacks::default(),
+            constraints: DragConstraints::default(),
+            commit_strategy: CommitStrategy::default(),
+            persist_order: None,
+        }
+    }
+
+    /// Removes the state of the lists with the given `id_source` from egui's memory, in every
+    /// ui they were shown in, e.g. once a list with a unique id is closed for good.
+    /// See [utils::clear_dnd_state] to remove all of them.
+    pub fn forget(ctx: &Context, id_source: impl Hash) {
+        let source = Id::new(id_source).with("dnd");
+        ctx.data_mut(|data| {
+            let scopes = data
+                .get_temp::<ListScopes>(source.with("scopes"))
+                .unwrap_or_default();
+            for id in scopes.0.into_iter().chain([source]) {
+                data.remove::<DragDropUi>(id);
+            }
+            data.remove::<ListScopes>(source.with("scopes"));
+        });
+    }
+
     /// Sets the config used when dragging with the mouse or when no touch config is set
     pub fn with_mouse_config(mut self, config: DragDropConfig) -> Self {
         self.drag_drop_ui = self.drag_drop_ui.with_mouse_config(config);
@@ -88,6 +332,294 @@ impl<'a> Dnd<'a> {
         self
     }
 
+    /// Sets the config used when dragging with a pen / stylus
+    /// If None, pens use the touch config like any other touch
+    /// egui can't tell pens from fingers, so the detection is based on pressure: every touch that
+    /// reports a pressure uses this config. Browsers and Android report a pressure for fingers too,
+    /// so only set this if your app runs where only pens do.
+    /// The default is None
+    pub fn with_pen_config(mut self, config: Option<DragDropConfig>) -> Self {
+        self.drag_drop_ui = self.drag_drop_ui.with_pen_config(config);
+        self
+    }
+
+    /// Starts dragging the item with the given id (see [DragDropItem::id]) when it's shown,
+    /// e.g. from a context menu entry or a test. Has no effect if a drag is already in progress.
+    pub fn begin_drag(mut self, id: Id, mode: DragMode) -> Self {
+        self.drag_drop_ui.begin_drag(id, mode);
+        self
+    }
+
+    /// Forces the list into the given drag state without any input, e.g. to render a drag in
+    /// golden image tests. Pass it in every frame the state should be kept, see
+    /// [test_utils::DragSnapshot].
+    #[cfg(feature = "test_utils")]
+    pub fn snapshot_drag(mut self, snapshot: test_utils::DragSnapshot) -> Self {
+        self.drag_drop_ui.snapshot_drag(snapshot);
+        self
+    }
+
+    /// Cancels the drag in progress, moving the item back to where it was picked up.
+    /// Pass [CancellationReason::Programmatic], or [CancellationReason::Custom] with a message
+    /// that explains why, e.g. to show it in a snackbar. Has no effect if nothing is being dragged.
+    pub fn cancel_drag(mut self, reason: CancellationReason) -> Self {
+        self.drag_drop_ui.cancel_drag(reason);
+        self
+    }
+
+    /// Called once when an item is picked up, e.g. to trigger haptic feedback or play a sound.
+    pub fn on_pickup(mut self, on_pickup: impl FnMut() + 'a) -> Self {
+        self.callbacks.on_pickup = Some(Box::new(on_pickup));
+        self
+    }
+
+    /// Called once when an item is picked up, with its id (see [DragDropItem::id]) and index.
+    pub fn on_drag_start(mut self, on_drag_start: impl FnMut(Id, usize) + 'a) -> Self {
+        self.callbacks.on_drag_start = Some(Box::new(on_drag_start));
+        self
+    }
+
+    /// Called once each time the index the dragged item would be dropped at changes,
+    /// e.g. to give a short haptic tick while the item moves past other items.
+    pub fn on_slot_change(mut self, on_slot_change: impl FnMut() + 'a) -> Self {
+        self.callbacks.on_slot_change = Some(Box::new(on_slot_change));
+        self
+    }
+
+    /// Called once each time the index the dragged item would be dropped at changes,
+    /// with the new index, see [DragUpdate::target_index].
+    pub fn on_hover_index_changed(
+        mut self,
+        on_hover_index_changed: impl FnMut(usize) + 'a,
+    ) -> Self {
+        self.callbacks.on_hover_index_changed = Some(Box::new(on_hover_index_changed));
+        self
+    }
+
+    /// Called once when the dragged item is dropped, with the final update.
+    /// This isn't called when the drag is cancelled.
+    pub fn on_drop(mut self, on_drop: impl FnMut(DragUpdate) + 'a) -> Self {
+        self.callbacks.on_drop = Some(Box::new(on_drop));
+        self
+    }
+
+    /// Called once when a drag is cancelled after the item was picked up, e.g. with the
+    /// [DragDropConfig::cancel_key]. Presses that never picked an item up don't call this.
+    pub fn on_cancel(mut self, on_cancel: impl FnMut(CancellationReason) + 'a) -> Self {
+        self.callbacks.on_cancel = Some(Box::new(on_cancel));
+        self
+    }
+
+    /// Called once each time the pointer dragging an item leaves the window, with the id of the
+    /// item and the last known pointer position. egui can't start OS level drags,
+    /// but you can use this to start one with the platform APIs, e.g. to export the item as a file.
+    pub fn on_dragged_out_of_window(
+        mut self,
+        on_dragged_out_of_window: impl FnMut(Id, Pos2) + 'a,
+    ) -> Self {
+        self.callbacks.on_dragged_out_of_window = Some(Box::new(on_dragged_out_of_window));
+        self
+    }
+
+    /// Restricts where items can be dropped. `allow_drop` is called with the current index of the
+    /// dragged item and the index it would end up at (see [DragUpdate::target_index]).
+    /// While dragging, the gap snaps to the closest allowed position. If there is none, releasing
+    /// the item cancels the drag with [CancellationReason::InvalidDrop].
+    pub fn allow_drop(mut self, allow_drop: impl Fn(usize, usize) -> bool + 'a) -> Self {
+        self.constraints.allow_drop = Some(Box::new(allow_drop));
+        self
+    }
+
+    /// Prevents items from being dropped between two neighboring items, e.g. to keep a header
+    /// together with its first row. `dead_zone` is called with the current indices of the items
+    /// before and after a gap, not counting the dragged item, and returns true if nothing may be
+    /// dropped between them. The gap never opens there and a released item snaps to the closest
+    /// allowed position, like with [Dnd::allow_drop].
+    pub fn dead_zone(mut self, dead_zone: impl Fn(usize, usize) -> bool + 'a) -> Self {
+        self.constraints.dead_zone = Some(Box::new(dead_zone));
+        self
+    }
+
+    /// Decides whether the released item may be dropped, given the update that would be applied.
+    /// If it returns [DropDecision::Veto], the item isn't dropped or moved back, but stays attached
+    /// to the pointer until the next click, which tries to drop it again. This is useful if e.g.
+    /// a permission check for the drop fails. Vetoed drops are reported as [DndEvent::DropVetoed].
+    pub fn validate_drop(
+        mut self,
+        validate_drop: impl Fn(&DragUpdate) -> DropDecision + 'a,
+    ) -> Self {
+        self.constraints.validate_drop = Some(Box::new(validate_drop));
+        self
+    }
+
+    /// Accepts egui [DragAndDrop] payloads of type `P` that were set by other widgets, or the
+    /// [DndPayload] of another list. While such a payload is dragged over the items, a gap opens
+    /// where it would be inserted, see [DragDropResponse::external_hover_index]. Once it's released,
+    /// the payload is taken and returned by [DragDropResponse::dropped_payload], so you can insert
+    /// the item it stands for. Only one payload type can be accepted.
+    pub fn accept_payload<P: Any + Send + Sync>(mut self) -> Self {
+        self.constraints.accept_payload = Some(|ctx, take| {
+            let payload = if take {
+                DragAndDrop::take_payload::<P>(ctx)
+            } else {
+                DragAndDrop::payload::<P>(ctx)
+            };
+            payload.map(|payload| payload as Arc<dyn Any + Send + Sync>)
+        });
+        self
+    }
+
+    /// Accepts files dragged over the list from the OS. While they are hovering the items, a gap
+    /// opens where they would be inserted, see [DragDropResponse::external_hover_index]. Once
+    /// they are dropped, they are reported by [DragDropResponse::external_drop].
+    /// Note that some platforms don't report the pointer position while files are dragged
+    /// into the window, so the gap may only follow the pointer once they are dropped.
+    pub fn accept_files(mut self) -> Self {
+        self.constraints.accept_files = true;
+        self
+    }
+
+    /// Locks the items for which `locked` returns true, given their index. Locked items can't be
+    /// dragged, and other items can't be dropped anywhere that would move them, so they always
+    /// stay at the same index. This is useful for e.g. a header row that has to stay at the top.
+    pub fn locked(mut self, locked: impl Fn(usize) -> bool + 'a) -> Self {
+        self.constraints.locked = Some(Box::new(locked));
+        self
+    }
+
+    /// Makes the items for which `draggable` returns false, given their index, impossible to
+    /// pick up. Their handle is shown disabled, but unlike [Dnd::locked] other items can still be
+    /// dropped around them, moving them out of the way.
+    pub fn draggable(mut self, draggable: impl Fn(usize) -> bool + 'a) -> Self {
+        self.constraints.draggable = Some(Box::new(draggable));
+        self
+    }
+
+    /// Lets the dragged item be dropped onto the items for which `drop_onto` returns true, given
+    /// their index, e.g. to move a file into a folder. While the pointer is over the middle half of
+    /// such an item, the gap stays where it is and the update reports the item in
+    /// [DragUpdate::onto]. Dropping it there doesn't reorder the list, handle the drop with
+    /// [DragDropResponse::final_update] instead.
+    pub fn drop_onto(mut self, drop_onto: impl Fn(usize) -> bool + 'a) -> Self {
+        self.constraints.drop_onto = Some(Box::new(drop_onto));
+        self
+    }
+
+    /// Shows a context menu when an item is right clicked, anywhere on the item including its
+    /// handle. `menu` is called with the index of the item while the menu is open, add the
+    /// entries to the ui it's given. Since items are only dragged with
+    /// [DragDropConfig::drag_button], which is the primary button by default, right clicking
+    /// never starts a drag, and no menu is opened while an item is dragged.
+    /// See [DragDropConfig::context_menu] for a built-in menu to move the items.
+    pub fn on_item_context_menu(mut self, menu: impl FnMut(&mut Ui, usize) + 'a) -> Self {
+        self.callbacks.on_item_context_menu = Some(Box::new(menu));
+        self
+    }
+
+    /// Lets the dragged item be dropped onto the items it can be merged with, e.g. to create a
+    /// folder from two files or to group two tabs. `merge` is called with the current index of the
+    /// dragged item and the index of the item under the pointer. Like with [Dnd::drop_onto], the
+    /// gap stays where it is while the pointer is over the middle half of such an item, which is
+    /// highlighted with [DragDropConfig::merge_highlight]. Dropping it there doesn't reorder the
+    /// list, but reports a [DndEvent::Merge] and calls [Dnd::on_merge], so you can group the items.
+    /// Items that accept [Dnd::drop_onto] take precedence.
+    pub fn merge(mut self, merge: impl Fn(usize, usize) -> bool + 'a) -> Self {
+        self.constraints.merge = Some(Box::new(merge));
+        self
+    }
+
+    /// Called when the dragged item is dropped onto another item to merge them, with the index of
+    /// the dragged item and the index of the item it was dropped onto, see [Dnd::merge]
+    pub fn on_merge(mut self, on_merge: impl FnMut(usize, usize) + 'a) -> Self {
+        self.callbacks.on_merge = Some(Box::new(on_merge));
+        self
+    }
+
+    /// Expands collapsed groups while an item is dragged, so it can be dropped deep inside of a
+    /// tree in a single drag. Once the dragged item was held onto an item for which `collapsed`
+    /// returns true for `delay`, `on_open` is called with the id and index of the item, so you
+    /// can expand it. Items are only held onto if they accept it, see [Dnd::drop_onto].
+    /// Use [Dnd::on_spring_close] to collapse them again if the drag doesn't end up inside.
+    pub fn spring_open(
+        mut self,
+        delay: Duration,
+        collapsed: impl Fn(usize) -> bool + 'a,
+        on_open: impl FnMut(Id, usize) + 'a,
+    ) -> Self {
+        self.constraints.spring_open = Some((delay, Box::new(collapsed)));
+        self.callbacks.on_spring_open = Some(Box::new(on_open));
+        self
+    }
+
+    /// Called for each item expanded by [Dnd::spring_open] during a drag, with its id and index,
+    /// once the pointer leaves the list or the drag is cancelled. If the item is dropped, the
+    /// expanded items stay open.
+    pub fn on_spring_close(mut self, on_close: impl FnMut(Id, usize) + 'a) -> Self {
+        self.callbacks.on_spring_close = Some(Box::new(on_close));
+        self
+    }
+
+    /// Set this if the items are a filtered view of a larger list, e.g. filtered by a search box.
+    /// If the filter changes during a drag, the drag continues against the new view, and the
+    /// indices of the updates are indices into the shown items. If the dragged item is hidden by
+    /// the filter, there is no update until it's shown again, and dropping it while it's hidden
+    /// cancels the drag with [CancellationReason::ListChanged].
+    /// Otherwise, a drag is cancelled as soon as the dragged item isn't shown anymore.
+    pub fn filtered(mut self, filtered: bool) -> Self {
+        self.constraints.filtered = filtered;
+        self
+    }
+
+    /// If true, paints the rects of the items with their indices and ids, the boundaries of the
+    /// zones in which the dragged item counts as hovering an item, the center of the dragged item
+    /// that is compared against them and the slot it would be dropped at. This helps to find out
+    /// why a drop lands in an unexpected slot.
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.constraints.debug = debug;
+        self
+    }
+
+    /// If false, the items are shown just like before but can't be dragged, e.g. to prevent
+    /// reordering while the current order is being saved. A drag that is in progress is cancelled
+    /// with [CancellationReason::Disabled]. This is `true` by default.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.constraints.disabled = !enabled;
+        self
+    }
+
+    /// Sets when [Dnd::show_vec] and the other `_vec` methods reorder the items.
+    /// By default, they are reordered continuously while dragging.
+    pub fn commit_strategy(mut self, strategy: CommitStrategy) -> Self {
+        self.commit_strategy = strategy;
+        self
+    }
+
+    /// Remembers up to `capacity` moves made by dragging, so they can be undone with
+    /// [DragDropResponse::undo] and made again with [DragDropResponse::redo], e.g. when ctrl + z
+    /// is pressed. Once the history is full, the oldest moves are forgotten.
+    pub fn with_history(mut self, capacity: usize) -> Self {
+        self.drag_drop_ui
+            .history
+            .get_or_insert_with(|| Arc::new(Mutex::new(History::new(capacity))))
+            .lock()
+            .set_capacity(capacity);
+        self
+    }
+
+    /// Stores the order of the items in egui's persistent storage under `storage_key` and
+    /// restores it the first time the list is shown, so the order the user sorted the items in
+    /// survives restarts. Only has an effect on [Dnd::show_vec] and the other `_vec` methods,
+    /// and the ids of the items need to be the same after a restart, e.g. by using
+    /// [Dnd::show_vec_keyed] with a key that is saved with the item.
+    ///
+    /// Items that aren't in the stored order, e.g. because they were added since, are moved
+    /// behind the others. The order is only written to disk if egui's `persistence` feature is
+    /// enabled, e.g. by eframe's `persistence` feature.
+    pub fn persist_order(mut self, storage_key: impl Hash) -> Self {
+        self.persist_order = Some(Id::new(storage_key));
+        self
+    }
+
     /// Sets the animation time for the return animation (after dropping an item)
     /// The default is the same as the egui animation time
     /// If you want to disable the animation, set it to 0
@@ -127,14 +659,8 @@ impl<'a> Dnd<'a> {
         items: impl Iterator<Item = T>,
         mut item_ui: impl FnMut(&mut Ui, T, Handle, ItemState),
     ) -> DragDropResponse {
-        self._show_with_inner(|_id, ui, drag_drop_ui| {
-            drag_drop_ui.ui(ui, |ui, iter| {
-                items.enumerate().for_each(|(i, item)| {
-                    iter.next(ui, item.id(), i, true, |ui, item_handle| {
-                        item_handle.ui(ui, |ui, handle, state| item_ui(ui, item, handle, state))
-                    });
-                });
-            })
+        self._show_items(items, |ui, item, item_handle| {
+            item_handle.ui(ui, |ui, handle, state| item_ui(ui, item, handle, state))
         })
     }
 
@@ -149,77 +675,255 @@ impl<'a> Dnd<'a> {
         size: egui::Vec2,
         mut item_ui: impl FnMut(&mut Ui, T, Handle, ItemState),
     ) -> DragDropResponse {
-        self._show_with_inner(|_id, ui, drag_drop_ui| {
-            drag_drop_ui.ui(ui, |ui, iter| {
-                items.enumerate().for_each(|(i, item)| {
-                    iter.next(ui, item.id(), i, true, |ui, item_handle| {
-                        item_handle.ui_sized(ui, size, |ui, handle, state| {
-                            item_ui(ui, item, handle, state)
-                        })
-                    });
-                });
+        self._show_items(items, |ui, item, item_handle| {
+            item_handle.ui_sized(ui, size, |ui, handle, state| {
+                item_ui(ui, item, handle, state)
             })
         })
     }
 
-    /// Same as [Dnd::show], but automatically sorts the items.
+    /// Same as [Dnd::show], but automatically sorts the items, see [Dnd::commit_strategy].
     pub fn show_vec<T: Hash>(
-        self,
+        mut self,
         items: &mut [T],
         item_ui: impl FnMut(&mut Ui, &mut T, Handle, ItemState),
     ) -> DragDropResponse {
+        let (ctx, commit_strategy, persist_order) = self.prepare_vec(items, |item| Id::new(item));
         let response = self.show(items.iter_mut(), item_ui);
-        response.update_vec(items);
+        commit_strategy.apply(&response, items);
+        Self::save_order(&ctx, persist_order, items, |item| Id::new(item));
+        response
+    }
+
+    /// Same as [Dnd::show_vec], but shows each item as a collapsible section, like
+    /// [egui::CollapsingHeader]. The header, drawn by `header` next to the collapse button, is the
+    /// drag handle, so sections can be dragged whether they are open or closed. `body` is only
+    /// called while the section is open. Open sections are collapsed while they are dragged.
+    pub fn show_collapsing_vec<T: Hash>(
+        self,
+        items: &mut [T],
+        mut header: impl FnMut(&mut Ui, &mut T, ItemState),
+        mut body: impl FnMut(&mut Ui, &mut T),
+    ) -> DragDropResponse {
+        self.show_vec(items, |ui, item, handle, state| {
+            let id = Id::new(&*item).with("dnd_collapsing");
+            // The dragged section uses its own state, so it's shown collapsed without
+            // forgetting that it was open
+            let state_id = if state.dragged {
+                id.with("dragged")
+            } else {
+                id
+            };
+            let mut collapsing = CollapsingState::load_with_default_open(ui.ctx(), state_id, false);
+            let header_response = handle.ui(ui, |ui| {
+                ui.horizontal(|ui| {
+                    collapsing.show_toggle_button(ui, paint_default_icon);
+                    header(ui, item, state);
+                });
+            });
+            collapsing.show_body_indented(&header_response, ui, |ui| body(ui, item));
+        })
+    }
+
+    /// Same as [Dnd::show_vec], but only the key returned by `key` is hashed to identify each item,
+    /// so `T` doesn't need to implement [Hash]. This is useful if your items contain e.g. floats,
+    /// or if you want the id to stay the same while the item is edited.
+    /// The keys must be unique within the list.
+    pub fn show_vec_keyed<T, K: Hash>(
+        mut self,
+        items: &mut [T],
+        key: impl Fn(&T) -> K,
+        mut item_ui: impl FnMut(&mut Ui, &mut T, Handle, ItemState),
+    ) -> DragDropResponse {
+        let (ctx, commit_strategy, persist_order) =
+            self.prepare_vec(items, |item| Id::new(key(item)));
+        let keyed_items = items.iter_mut().map(|item| KeyedItem {
+            id: Id::new(key(item)),
+            item,
+        });
+        let response = self.show(keyed_items, |ui, keyed, handle, state| {
+            item_ui(ui, keyed.item, handle, state)
+        });
+        commit_strategy.apply(&response, items);
+        Self::save_order(&ctx, persist_order, items, |item| Id::new(key(item)));
         response
     }
 
     /// Same as [Dnd::show_sized], but automatically sorts the items.
     pub fn show_vec_sized<T: Hash>(
-        self,
+        mut self,
         items: &mut [T],
         size: egui::Vec2,
         item_ui: impl FnMut(&mut Ui, &mut T, Handle, ItemState),
     ) -> DragDropResponse {
+        let (ctx, commit_strategy, persist_order) = self.prepare_vec(items, |item| Id::new(item));
         let response = self.show_sized(items.iter_mut(), size, item_ui);
-        response.update_vec(items);
+        commit_strategy.apply(&response, items);
+        Self::save_order(&ctx, persist_order, items, |item| Id::new(item));
+        response
+    }
+
+    /// Shows the items of an [egui_infinite_scroll::InfiniteScroll] in a single column, like
+    /// [egui_infinite_scroll::InfiniteScroll::ui], and sorts them like [Dnd::show_vec].
+    /// Only the visible items are shown, and more items are loaded while scrolling, but they can
+    /// still be dragged anywhere in the list. Should be shown in a [egui::ScrollArea].
+    ///
+    /// If the infinite scroll has a filter, the indices passed to `item_ui` count only the shown
+    /// items, but the update of the response is mapped back to the index in
+    /// [egui_infinite_scroll::InfiniteScroll::items], so it can be applied there.
+    #[cfg(feature = "infinite_scroll")]
+    pub fn show_infinite_scroll<T, Cursor>(
+        self,
+        infinite_scroll: &mut egui_infinite_scroll::InfiniteScroll<T, Cursor>,
+        prefetch_count: usize,
+        mut item_ui: impl FnMut(&mut Ui, &mut T, Handle, ItemState),
+    ) -> DragDropResponse
+    where
+        T: Hash + std::fmt::Debug + Send + Sync + 'static,
+        Cursor: Clone + std::fmt::Debug + Send + 'static,
+    {
+        let commit_strategy = self.commit_strategy;
+        let mut response = self.show_custom(|ui, iter| {
+            infinite_scroll.ui(ui, prefetch_count, |ui, index, item| {
+                iter.next(ui, item.id(), index, true, |ui, item_handle| {
+                    item_handle.ui(ui, |ui, handle, state| item_ui(ui, item, handle, state))
+                });
+            });
+        });
+
+        if let Some(update) = &mut response.update {
+            let count = infinite_scroll.items.len();
+            update.from = infinite_scroll
+                .unfiltered_index(update.from)
+                .unwrap_or(update.from);
+            update.to = infinite_scroll.unfiltered_index(update.to).unwrap_or(count);
+        }
+        commit_strategy.apply(&response, &mut infinite_scroll.items);
         response
     }
 
     /// This will allow for very flexible UI. You can use it to e.g. render outlines around items
     /// or render items in complex layouts. This is **experimental**.
     pub fn show_custom(self, f: impl FnOnce(&mut Ui, &mut ItemIterator)) -> DragDropResponse {
-        self._show_with_inner(|_id, ui, drag_drop_ui| drag_drop_ui.ui(ui, f))
+        self._show_with_inner(|_id, ui, drag_drop_ui, constraints| {
+            drag_drop_ui.ui_with_constraints(ui, constraints, f)
+        })
     }
 
     /// Same as [Dnd::show_custom], but automatically sorts the items.
     pub fn show_custom_vec<T: Hash>(
-        self,
+        mut self,
         items: &mut [T],
         f: impl FnOnce(&mut Ui, &mut [T], &mut ItemIterator),
     ) -> DragDropResponse {
+        let (ctx, commit_strategy, persist_order) = self.prepare_vec(items, |item| Id::new(item));
         let response = self.show_custom(|ui, iter| f(ui, items, iter));
-        response.update_vec(items);
+        commit_strategy.apply(&response, items);
+        Self::save_order(&ctx, persist_order, items, |item| Id::new(item));
         response
     }
 
+    /// Restores the order stored by [Dnd::persist_order] the first time the items are shown and
+    /// returns what the `_vec` methods need once `self` is consumed
+    fn prepare_vec<T>(
+        &mut self,
+        items: &mut [T],
+        id: impl Fn(&T) -> Id,
+    ) -> (Context, CommitStrategy, Option<Id>) {
+        let ctx = self.ui.ctx().clone();
+        if let (Some(key), false) = (self.persist_order, self.drag_drop_ui.order_restored) {
+            persist::restore_order(&ctx, key, items, id);
+        }
+        self.drag_drop_ui.order_restored = true;
+        (ctx, self.commit_strategy, self.persist_order)
+    }
+
+    fn save_order<T>(ctx: &Context, persist_order: Option<Id>, items: &[T], id: impl Fn(&T) -> Id) {
+        if let Some(key) = persist_order {
+            persist::save_order(ctx, key, items.iter().map(id).collect());
+        }
+    }
+
+    fn _show_items<T: DragDropItem>(
+        self,
+        items: impl Iterator<Item = T>,
+        item_fn: impl FnMut(&mut Ui, T, Item) -> ItemResponse,
+    ) -> DragDropResponse {
+        self._show_with_inner(|_id, ui, drag_drop_ui, constraints| {
+            if drag_drop_ui.enter_exit_animations_enabled(ui) {
+                // We need to know the items upfront to know where removed items were
+                let items: Vec<T> = items.collect();
+                drag_drop_ui
+                    .transitions
+                    .set_upcoming_items(items.iter().map(|item| item.id()));
+                Self::_show_iter(ui, drag_drop_ui, constraints, items.into_iter(), item_fn)
+            } else {
+                Self::_show_iter(ui, drag_drop_ui, constraints, items, item_fn)
+            }
+        })
+    }
+
+    fn _show_iter<T: DragDropItem>(
+        ui: &mut Ui,
+        drag_drop_ui: &mut DragDropUi,
+        constraints: &DragConstraints,
+        items: impl Iterator<Item = T>,
+        mut item_fn: impl FnMut(&mut Ui, T, Item) -> ItemResponse,
+    ) -> DragDropResponse {
+        drag_drop_ui.ui_with_constraints(ui, constraints, |ui, iter| {
+            items.enumerate().for_each(|(i, item)| {
+                iter.next(ui, item.id(), i, true, |ui, item_handle| {
+                    item_fn(ui, item, item_handle)
+                });
+            });
+        })
+    }
+
     fn _show_with_inner(
         self,
-        inner_fn: impl FnOnce(Id, &mut Ui, &mut DragDropUi) -> DragDropResponse,
+        inner_fn: impl FnOnce(Id, &mut Ui, &mut DragDropUi, &DragConstraints) -> DragDropResponse,
     ) -> DragDropResponse {
         let Dnd {
             id,
+            source,
             ui,
             mut drag_drop_ui,
+            mut callbacks,
+            constraints,
+            ..
         } = self;
 
-        let response = inner_fn(id, ui, &mut drag_drop_ui);
+        #[cfg(feature = "tracing")]
+        let span = instrument::list_span(id).entered();
+        let response = inner_fn(id, ui, &mut drag_drop_ui, &constraints);
+        external::publish_payload(ui.ctx(), id, source, &response);
+        #[cfg(feature = "tracing")]
+        {
+            instrument::trace_response(id, &response, &drag_drop_ui);
+            drop(span);
+        }
 
         ui.ctx().data_mut(|data| data.insert_temp(id, drag_drop_ui));
 
+        callbacks.context_menus(&response);
+        callbacks.call(&response);
+
         response
     }
 }
 
+/// An item of [Dnd::show_vec_keyed], identified by its key instead of its hash
+pub(crate) struct KeyedItem<'t, T> {
+    pub(crate) id: Id,
+    pub(crate) item: &'t mut T,
+}
+
+impl<T> DragDropItem for KeyedItem<'_, T> {
+    fn id(&self) -> Id {
+        self.id
+    }
+}
+
 /// State of the current item.
 pub struct ItemState {
     /// True if the item is currently being dragged.
@@ -229,4 +933,22 @@ pub struct ItemState {
     /// of [Dnd::show_vec]), this index will updated while the item is being dragged.
     /// If you sort once after the item is dropped, the index will be stable during the drag.
     pub index: usize,
+    /// The index the item will have once the dragged item is dropped where it is currently
+    /// hovering. This is the same as [ItemState::index] if nothing is being dragged.
+    pub target_index: usize,
+    /// For the dragged item, how far it has been moved within the list since it was picked up.
+    /// This is zero for all other items.
+    pub drag_delta: egui::Vec2,
+    /// True if the item is currently shifted to make room for the dragged item
+    pub is_displaced: bool,
+    /// True if the handle has the keyboard focus, so pressing space or enter picks the item up.
+    /// See [DragDropConfig::keyboard_reordering].
+    pub selected: bool,
+    /// True if the item was cut with ctrl + x, it's painted faded until it's pasted in front of
+    /// another item with ctrl + v. See [DragDropConfig::keyboard_reordering].
+    pub cut: bool,
+    /// While the pointer is held on this item waiting for the [DragDropConfig::drag_delay], how
+    /// much of the delay has passed, from 0 to 1. Use this to draw a custom hold indicator, e.g.
+    /// a highlight that grows until the item is picked up.
+    pub hold_progress: Option<f32>,
 }
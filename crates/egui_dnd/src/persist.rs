@@ -0,0 +1,26 @@
+use std::collections::HashMap;
+
+use egui::{Context, Id};
+
+/// Sorts the items like the ids stored under `key`. Items that aren't in the stored order, e.g.
+/// because they were added since, are moved behind the others, keeping their order.
+pub(crate) fn restore_order<T>(ctx: &Context, key: Id, items: &mut [T], id: impl Fn(&T) -> Id) {
+    let Some(order) = ctx.data_mut(|data| data.get_persisted::<Vec<Id>>(key)) else {
+        return;
+    };
+    let positions: HashMap<Id, usize> = order
+        .into_iter()
+        .enumerate()
+        .map(|(position, id)| (id, position))
+        .collect();
+    items.sort_by_key(|item| positions.get(&id(item)).copied().unwrap_or(usize::MAX));
+}
+
+/// Stores the order of the items under `key`, if it changed
+pub(crate) fn save_order(ctx: &Context, key: Id, ids: Vec<Id>) {
+    ctx.data_mut(|data| {
+        if data.get_persisted::<Vec<Id>>(key).as_ref() != Some(&ids) {
+            data.insert_persisted(key, ids);
+        }
+    });
+}
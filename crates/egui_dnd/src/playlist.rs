@@ -0,0 +1,138 @@
+use std::hash::Hash;
+
+use egui::{Frame, Id, Label, Rect, Ui, WidgetText};
+
+use crate::utils::shift_vec;
+use crate::{dnd, DragDropResponse};
+
+/// A list of tracks where the one that is currently playing is pinned in place.
+/// Created with [dnd_playlist].
+pub struct Playlist<'a> {
+    ui: &'a mut Ui,
+    id_source: Id,
+    now_playing: Option<usize>,
+}
+
+/// The response of [Playlist::show]
+pub struct PlaylistResponse {
+    /// The response of the list the tracks are shown in
+    pub response: DragDropResponse,
+    /// The index of the track that is playing, after the tracks were reordered
+    pub now_playing: Option<usize>,
+    /// Whether a track was dropped onto the playing track this frame, which queued it next
+    pub queued_next: bool,
+}
+
+/// Shows a playlist, where the track that is currently playing is highlighted and locked in
+/// place, like with [crate::Dnd::locked]. The tracks before and after it are reordered on their
+/// side of it, and dropping a track directly onto it queues the track next, by moving it right
+/// behind it. The tracks are only reordered once a track is dropped, like with
+/// [crate::CommitStrategy::OnDrop].
+/// Example usage:
+/// ```rust no_run
+/// use eframe::egui;
+/// use egui::CentralPanel;
+/// use egui_dnd::dnd_playlist;
+///
+/// pub fn main() -> eframe::Result<()> {
+///     let mut tracks = vec!["Intro", "Overture", "Finale"];
+///     let mut playing = Some(1);
+///
+///     eframe::run_simple_native("DnD Playlist Example", Default::default(), move |ctx, _frame| {
+///         CentralPanel::default().show(ctx, |ui| {
+///             playing = dnd_playlist(ui, "playlist")
+///                 .now_playing(playing)
+///                 .show(&mut tracks, |track| (*track).into())
+///                 .now_playing;
+///         });
+///     })
+/// }
+/// ```
+pub fn dnd_playlist(ui: &mut Ui, id_source: impl Hash) -> Playlist {
+    Playlist {
+        ui,
+        id_source: Id::new(id_source),
+        now_playing: None,
+    }
+}
+
+impl Playlist<'_> {
+    /// Sets the index of the track that is currently playing
+    pub fn now_playing(mut self, index: Option<usize>) -> Self {
+        self.now_playing = index;
+        self
+    }
+
+    /// Shows the tracks with the titles returned by `title` and reorders them when a track is
+    /// dropped
+    pub fn show<T: Hash>(
+        self,
+        items: &mut [T],
+        mut title: impl FnMut(&T) -> WidgetText,
+    ) -> PlaylistResponse {
+        let Playlist {
+            ui,
+            id_source,
+            now_playing,
+        } = self;
+        let now_playing = now_playing.filter(|idx| *idx < items.len());
+        let playing_id = now_playing.map(|idx| Id::new(&items[idx]));
+        let mut playing_rect: Option<Rect> = None;
+
+        let response = dnd(ui, id_source)
+            .locked(|idx| Some(idx) == now_playing)
+            .show(items.iter(), |ui, item, handle, state| {
+                if Some(state.index) == now_playing {
+                    let dragging = ui.memory(|mem| mem.is_anything_being_dragged());
+                    let frame = Frame::group(ui.style()).fill(ui.visuals().selection.bg_fill);
+                    let response = frame
+                        .show(ui, |ui| {
+                            ui.set_width(ui.available_width());
+                            ui.horizontal(|ui| {
+                                ui.label("▶");
+                                ui.add(Label::new(title(item)).selectable(false));
+                            });
+                        })
+                        .response;
+                    // Hint that a track dropped here will be played next
+                    if dragging && response.contains_pointer() {
+                        ui.painter().rect_stroke(
+                            response.rect,
+                            ui.visuals().widgets.active.rounding,
+                            ui.visuals().selection.stroke,
+                        );
+                    }
+                    playing_rect = Some(response.rect);
+                } else {
+                    ui.horizontal(|ui| {
+                        handle.ui(ui, |ui| {
+                            ui.label("☰");
+                        });
+                        ui.add(Label::new(title(item)).selectable(false));
+                    });
+                }
+            });
+
+        let mut queued_next = false;
+        if let Some(update) = response.final_update() {
+            let onto_playing = update
+                .pointer_pos
+                .zip(playing_rect)
+                .is_some_and(|(pos, rect)| rect.contains(pos));
+            match now_playing {
+                Some(playing) if onto_playing => {
+                    shift_vec(update.from, playing + 1, items);
+                    queued_next = true;
+                }
+                _ => shift_vec(update.from, update.to, items),
+            }
+        }
+
+        PlaylistResponse {
+            now_playing: playing_id
+                .and_then(|id| items.iter().position(|item| Id::new(item) == id)),
+            response,
+            queued_next,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::hash::Hash;
+
+use egui::{Align, Id, Layout, TextStyle, Ui, Vec2};
+
+use crate::{dnd, DragDropResponse};
+
+/// A list of ranked items, like a "top 10" editor, with the rank of each item in a gutter in
+/// front of it. While an item is dragged, the ranks are numbered in the order the items will have
+/// once it's dropped, so they always read 1., 2., 3. from top to bottom.
+/// Example usage:
+/// ```rust no_run
+/// use eframe::egui;
+/// use egui::CentralPanel;
+/// use egui_dnd::RankedList;
+///
+/// pub fn main() -> eframe::Result<()> {
+///     let mut movies = vec!["Alien", "Brazil", "Casablanca", "Dune"];
+///
+///     eframe::run_simple_native("DnD Ranking Example", Default::default(), move |ctx, _frame| {
+///         CentralPanel::default().show(ctx, |ui| {
+///             RankedList::new(ui, "top_movies")
+///                 .top(3)
+///                 .show(&mut movies, |ui, movie, _rank| {
+///                     ui.label(*movie);
+///                 });
+///         });
+///     })
+/// }
+/// ```
+pub struct RankedList<'a> {
+    ui: &'a mut Ui,
+    id_source: Id,
+    top: Option<usize>,
+}
+
+impl<'a> RankedList<'a> {
+    /// Creates a ranked list, the state is stored like with [crate::dnd]
+    pub fn new(ui: &'a mut Ui, id_source: impl Hash) -> Self {
+        Self {
+            ui,
+            id_source: Id::new(id_source),
+            top: None,
+        }
+    }
+
+    /// Only the first `count` ranks are highlighted, the ranks of the items after them are shown
+    /// weaker, e.g. for the runners-up of a "top 10"
+    pub fn top(mut self, count: usize) -> Self {
+        self.top = Some(count);
+        self
+    }
+
+    /// Shows the items with a drag handle and their rank, and reorders them like
+    /// [crate::Dnd::show_vec]. `item_ui` is called with the rank the item will have once the
+    /// dragged item is dropped, starting at 1.
+    pub fn show<T: Hash>(
+        self,
+        items: &mut [T],
+        mut item_ui: impl FnMut(&mut Ui, &mut T, usize),
+    ) -> DragDropResponse {
+        let RankedList { ui, id_source, top } = self;
+        // Wide enough for the rank of the last item, so the items stay aligned
+        let digits = items.len().max(1).to_string().len() + 1;
+        let digit_width =
+            ui.fonts(|fonts| fonts.glyph_width(&TextStyle::Body.resolve(ui.style()), '0'));
+        let gutter = Vec2::new(digit_width * digits as f32, ui.spacing().interact_size.y);
+
+        dnd(ui, id_source).show_vec(items, |ui, item, handle, state| {
+            let rank = state.target_index + 1;
+            ui.horizontal(|ui| {
+                handle.ui(ui, |ui| {
+                    ui.label("☰");
+                });
+                ui.allocate_ui_with_layout(gutter, Layout::right_to_left(Align::Center), |ui| {
+                    let text = format!("{rank}.");
+                    if top.map_or(true, |top| rank <= top) {
+                        ui.strong(text);
+                    } else {
+                        ui.weak(text);
+                    }
+                });
+                item_ui(ui, item, rank);
+            });
+        })
+    }
+}
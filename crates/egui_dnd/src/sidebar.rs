@@ -0,0 +1,71 @@
+use std::hash::Hash;
+
+use egui::{Id, Label, Ui, WidgetText};
+
+use crate::{dnd, DragDropResponse};
+
+/// Collapsible sidebar sections that can be reordered. Created with [dnd_sidebar].
+pub struct Sidebar<'a> {
+    ui: &'a mut Ui,
+    id_source: Id,
+}
+
+/// Shows collapsible sections stacked on top of each other, like the panels in the sidebar of a
+/// code editor. The header of each section spans the whole width and is the drag handle, open
+/// sections are collapsed while they are dragged, see [crate::Dnd::show_collapsing_vec].
+///
+/// The order of the sections is stored with [crate::Dnd::persist_order] under the `id_source`,
+/// so the sections are restored in the order the user sorted them in after a restart.
+/// Example usage:
+/// ```rust no_run
+/// use eframe::egui;
+/// use egui::{CentralPanel, SidePanel};
+/// use egui_dnd::dnd_sidebar;
+///
+/// pub fn main() -> eframe::Result<()> {
+///     let mut sections = vec!["Explorer", "Search", "Source Control"];
+///
+///     eframe::run_simple_native("DnD Sidebar Example", Default::default(), move |ctx, _frame| {
+///         SidePanel::left("sidebar").show(ctx, |ui| {
+///             dnd_sidebar(ui, "sidebar").show(
+///                 &mut sections,
+///                 |section| (*section).into(),
+///                 |ui, section| {
+///                     ui.label(format!("The contents of {section}"));
+///                 },
+///             );
+///         });
+///         CentralPanel::default().show(ctx, |_ui| {});
+///     })
+/// }
+/// ```
+pub fn dnd_sidebar(ui: &mut Ui, id_source: impl Hash) -> Sidebar {
+    Sidebar {
+        ui,
+        id_source: Id::new(id_source),
+    }
+}
+
+impl Sidebar<'_> {
+    /// Shows the sections with the titles returned by `title` in their headers, and `body` below
+    /// the headers of the open sections. The sections are identified by their hash, like the items
+    /// of [crate::Dnd::show_vec].
+    pub fn show<T: Hash>(
+        self,
+        sections: &mut [T],
+        mut title: impl FnMut(&T) -> WidgetText,
+        body: impl FnMut(&mut Ui, &mut T),
+    ) -> DragDropResponse {
+        dnd(self.ui, self.id_source)
+            .persist_order(self.id_source.with("dnd_sidebar_order"))
+            .show_collapsing_vec(
+                sections,
+                |ui, section, _state| {
+                    // The whole header can be grabbed, not only the title
+                    ui.set_width(ui.available_width());
+                    ui.add(Label::new(title(section)).selectable(false));
+                },
+                body,
+            )
+    }
+}
@@ -1,14 +1,31 @@
+use std::any::Any;
+use std::collections::HashMap;
 use std::hash::Hash;
+use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{Duration, SystemTime};
 
-use egui::{CursorIcon, Id, Pos2, Rect, Sense, Ui, Vec2};
+use egui::epaint::shape_transform::adjust_colors;
+use egui::epaint::ClippedShape;
+use egui::mutex::Mutex;
+use egui::{
+    Color32, CursorIcon, Direction, Event, EventFilter, Id, InputState, Key, LayerId, Modifiers,
+    Order, PointerButton, Pos2, Rect, Sense, Shape, Stroke, Ui, Vec2,
+};
 
 #[cfg(target_arch = "wasm32")]
 use web_time::{Duration, SystemTime};
 
+#[cfg(feature = "accesskit")]
+use crate::accessibility::{self, Announcer};
+use crate::external::{external_hover, AcceptPayload, ExternalDrop};
+use crate::history::History;
+use crate::item::{fade_shapes, sort_shapes_by_z_index};
 use crate::item_iterator::ItemIterator;
-use crate::utils::shift_vec;
+#[cfg(feature = "test_utils")]
+use crate::test_utils::DragSnapshot;
+use crate::transition::ItemTransitions;
+use crate::{DndContainer, DndSortable};
 
 /// Item that can be reordered using drag and drop
 pub trait DragDropItem {
@@ -24,13 +41,241 @@ impl<T: Hash> DragDropItem for T {
 
 /// An instruction in what order to update the source list.
 /// The item at from should be removed from the list and inserted at to.
-/// You can use [shift_vec] to do this for a Vec.
+/// You can use [crate::utils::shift_vec] to do this for a Vec.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DragUpdate {
     /// Index of the item to move
     pub from: usize,
     /// Where to move the item to
     pub to: usize,
+    /// The modifier keys held in the frame of this update.
+    /// For the final update, these are the modifiers held when the item was dropped,
+    /// e.g. to copy the item instead of moving it while alt is held.
+    pub modifiers: Modifiers,
+    /// The id of the item to move
+    pub id: Id,
+    /// Where the item ends up relative to the other items, for collections keyed by id
+    pub position: DropPosition,
+    /// The pointer position in the frame of this update, for the final update where the item was
+    /// released. This is `None` when dragging with the keyboard.
+    pub pointer_pos: Option<Pos2>,
+    /// The index of the item the dragged item is dropped onto instead of next to, e.g. a folder,
+    /// see [crate::Dnd::drop_onto]. The list isn't reordered by such an update.
+    pub onto: Option<usize>,
+    /// Whether the dragged item is dropped onto [DragUpdate::onto] to merge the two items, see
+    /// [crate::Dnd::merge]
+    pub merge: bool,
+}
+
+/// Where the dragged item ends up relative to the other items, see [DragUpdate::position]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DropPosition {
+    /// Directly in front of the item with this id
+    Before(Id),
+    /// Directly behind the item with this id, which is the last item
+    After(Id),
+    /// There are no other items
+    End,
+}
+
+/// Whether a drop may happen, see [crate::Dnd::validate_drop]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropDecision {
+    /// The item is dropped as usual
+    #[default]
+    Accept,
+    /// The item isn't dropped and stays attached to the pointer until the next click,
+    /// which tries to drop it again. Keyboard drags simply continue.
+    Veto,
+}
+
+impl DragUpdate {
+    /// `items` are the indices and ids of the items shown in this frame
+    fn new(
+        id: Id,
+        from: usize,
+        to: usize,
+        modifiers: Modifiers,
+        pointer_pos: Option<Pos2>,
+        items: &[(usize, Id, Pos2)],
+    ) -> Self {
+        let target = target_index(from, to);
+        let next = if target >= from { target + 1 } else { target };
+        let previous = if target > from {
+            Some(target)
+        } else {
+            target.checked_sub(1)
+        };
+        let position = if let Some(next) = id_at(items, next) {
+            DropPosition::Before(next)
+        } else if let Some(previous) = previous.and_then(|previous| id_at(items, previous)) {
+            DropPosition::After(previous)
+        } else {
+            DropPosition::End
+        };
+
+        Self {
+            from,
+            to,
+            modifiers,
+            id,
+            position,
+            pointer_pos,
+            onto: None,
+            merge: false,
+        }
+    }
+
+    /// The index the item ends up at once the update is applied.
+    /// This differs from [DragUpdate::to] when the item is moved towards the end of the list,
+    /// since `to` counts the item itself.
+    pub fn target_index(&self) -> usize {
+        target_index(self.from, self.to)
+    }
+}
+
+/// The index the item at `from` ends up at when it's inserted in front of the item at `to`
+pub(crate) fn target_index(from: usize, to: usize) -> usize {
+    if to > from {
+        to - 1
+    } else {
+        to
+    }
+}
+
+/// The inverse of [target_index], the item the moved item is inserted in front of
+pub(crate) fn insertion_index(from: usize, target: usize) -> usize {
+    if target > from {
+        target + 1
+    } else {
+        target
+    }
+}
+
+/// Rules that are set per frame on [crate::Dnd] and can't be stored in [DragDropUi],
+/// since they may borrow from the surrounding code
+#[derive(Default)]
+pub(crate) struct DragConstraints<'a> {
+    /// See [crate::Dnd::allow_drop]
+    pub(crate) allow_drop: Option<Box<dyn Fn(usize, usize) -> bool + 'a>>,
+    /// See [crate::Dnd::dead_zone]
+    pub(crate) dead_zone: Option<Box<dyn Fn(usize, usize) -> bool + 'a>>,
+    /// See [crate::Dnd::locked]
+    pub(crate) locked: Option<Box<dyn Fn(usize) -> bool + 'a>>,
+    /// See [crate::Dnd::draggable]
+    pub(crate) draggable: Option<Box<dyn Fn(usize) -> bool + 'a>>,
+    /// See [crate::Dnd::drop_onto]
+    pub(crate) drop_onto: Option<Box<dyn Fn(usize) -> bool + 'a>>,
+    /// See [crate::Dnd::merge]
+    pub(crate) merge: Option<Box<dyn Fn(usize, usize) -> bool + 'a>>,
+    /// The delay and which items are collapsed, see [crate::Dnd::spring_open]
+    pub(crate) spring_open: Option<(Duration, Box<dyn Fn(usize) -> bool + 'a>)>,
+    /// See [crate::Dnd::enabled]
+    pub(crate) disabled: bool,
+    /// See [crate::Dnd::validate_drop]
+    pub(crate) validate_drop: Option<Box<dyn Fn(&DragUpdate) -> DropDecision + 'a>>,
+    /// See [crate::Dnd::accept_payload]
+    pub(crate) accept_payload: Option<AcceptPayload>,
+    /// See [crate::Dnd::accept_files]
+    pub(crate) accept_files: bool,
+    /// See [crate::Dnd::filtered]
+    pub(crate) filtered: bool,
+    /// See [crate::Dnd::debug]
+    pub(crate) debug: bool,
+}
+
+impl DragConstraints<'_> {
+    pub(crate) fn is_locked(&self, idx: usize) -> bool {
+        self.locked.as_ref().is_some_and(|locked| locked(idx))
+    }
+
+    pub(crate) fn accepts_drop_onto(&self, idx: usize) -> bool {
+        self.drop_onto
+            .as_ref()
+            .is_some_and(|drop_onto| drop_onto(idx))
+    }
+
+    pub(crate) fn accepts_merge(&self, from: usize, idx: usize) -> bool {
+        self.merge.as_ref().is_some_and(|merge| merge(from, idx))
+    }
+
+    pub(crate) fn handle_mode(&self, idx: usize) -> HandleMode {
+        if self
+            .draggable
+            .as_ref()
+            .is_some_and(|draggable| !draggable(idx))
+        {
+            HandleMode::Disabled
+        } else if self.disabled || self.is_locked(idx) {
+            HandleMode::Inert
+        } else {
+            HandleMode::Draggable
+        }
+    }
+
+    fn is_drop_vetoed(&self, update: &DragUpdate) -> bool {
+        self.validate_drop
+            .as_ref()
+            .is_some_and(|validate_drop| validate_drop(update) == DropDecision::Veto)
+    }
+
+    /// Whether the item at `from` may end up at `target`, in a list of `count` items
+    fn is_drop_allowed(&self, from: usize, target: usize, count: usize) -> bool {
+        // Moving the item shifts all items between its old and new index, so none of them may be locked
+        let displaces_locked_item =
+            (from.min(target)..=from.max(target)).any(|idx| idx != from && self.is_locked(idx));
+        !displaces_locked_item
+            && !self.is_dead_zone(from, target, count)
+            && self
+                .allow_drop
+                .as_ref()
+                .map_or(true, |allow_drop| allow_drop(from, target))
+    }
+
+    /// Whether the item at `from` would end up between two items that [crate::Dnd::dead_zone]
+    /// keeps apart
+    fn is_dead_zone(&self, from: usize, target: usize, count: usize) -> bool {
+        let Some(dead_zone) = &self.dead_zone else {
+            return false;
+        };
+        // The current index of the item at `slot` once the dragged item is removed
+        let index = |slot: usize| if slot < from { slot } else { slot + 1 };
+        let before = target.checked_sub(1).map(index);
+        let after = Some(index(target)).filter(|idx| *idx < count);
+        match (before, after) {
+            (Some(before), Some(after)) => dead_zone(before, after),
+            _ => false,
+        }
+    }
+
+    /// The allowed target index closest to `target` for the item at `from`, in a list of `count` items.
+    /// If two are equally close, the one after `target` is preferred if `prefer_forward` is true.
+    fn nearest_allowed_target(
+        &self,
+        from: usize,
+        target: usize,
+        count: usize,
+        prefer_forward: bool,
+    ) -> Option<usize> {
+        if self.is_drop_allowed(from, target, count) {
+            return Some(target);
+        }
+        (1..count).find_map(|distance| {
+            let forward = Some(target + distance).filter(|target| *target < count);
+            let backward = target.checked_sub(distance);
+            let candidates = if prefer_forward {
+                [forward, backward]
+            } else {
+                [backward, forward]
+            };
+            candidates
+                .into_iter()
+                .flatten()
+                .find(|target| self.is_drop_allowed(from, *target, count))
+        })
+    }
 }
 
 /// Response containing state of the drag & drop list and a potential update to the source list.
@@ -43,8 +288,98 @@ pub struct DragDropResponse {
     /// If you only want to update the source list when the drag & drop event has finished, use [DragDropResponse::final_update] instead.
     pub update: Option<DragUpdate>,
     finished: bool,
-    cancellation_reason: Option<&'static str>,
+    cancellation_reason: Option<CancellationReason>,
     has_changed: bool,
+    modifiers: Modifiers,
+    events: Vec<DndEvent>,
+    duplicate_ids: Vec<Id>,
+    item_responses: HashMap<Id, egui::Response>,
+    /// The indices and ids of the items shown in this frame, in the order of the source list
+    shown_items: Vec<(usize, Id)>,
+    hold_progress: Option<(Id, f32)>,
+    external_hover_index: Option<usize>,
+    dropped_payload: Option<(usize, Arc<dyn Any + Send + Sync>)>,
+    external_drop: Option<ExternalDrop>,
+    history: Option<Arc<Mutex<History>>>,
+}
+
+/// Something that happened during a drag & drop event, see [DragDropResponse::events].
+/// Each event is only reported in a single frame.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DndEvent {
+    /// The item with the given id was picked up at the given index
+    DragStarted {
+        /// The id of the item, see [DragDropItem::id]
+        id: Id,
+        /// The index the item was picked up at
+        index: usize,
+    },
+    /// The index the dragged item would be dropped at changed, see [DragUpdate::target_index]
+    TargetChanged {
+        /// The id of the dragged item
+        id: Id,
+        /// The previous target index
+        from: usize,
+        /// The new target index
+        to: usize,
+    },
+    /// The dragged item was dropped
+    Dropped {
+        /// The id of the dropped item
+        id: Id,
+        /// The final update, the same as [DragDropResponse::final_update]
+        update: DragUpdate,
+    },
+    /// The item was released, but [crate::Dnd::validate_drop] vetoed the drop, so it's still dragged
+    DropVetoed {
+        /// The id of the dragged item
+        id: Id,
+        /// The update that would have been applied
+        update: DragUpdate,
+    },
+    /// The pointer dragging the item left the window. This is reported once each time it leaves,
+    /// e.g. to start an OS level drag to export the item, which egui can't do by itself.
+    DraggedOutOfWindow {
+        /// The id of the dragged item
+        id: Id,
+        /// The last known pointer position
+        pointer_pos: Pos2,
+    },
+    /// The dragged item was dropped onto another item to merge them, see [crate::Dnd::merge].
+    /// This is reported after the [DndEvent::Dropped] of the drop.
+    Merge {
+        /// The id of the dropped item
+        id: Id,
+        /// The index of the dropped item
+        source: usize,
+        /// The index of the item it was dropped onto
+        target: usize,
+    },
+    /// The dragged item was held onto a collapsed item, so it should be expanded, see
+    /// [crate::Dnd::spring_open]
+    SpringOpened {
+        /// The id of the item to expand
+        id: Id,
+        /// The index of the item to expand
+        index: usize,
+    },
+    /// The drag left the list or was cancelled, so an item that was expanded during it should be
+    /// collapsed again, see [crate::Dnd::on_spring_close]
+    SpringClosed {
+        /// The id of the item to collapse
+        id: Id,
+        /// The index of the item to collapse
+        index: usize,
+    },
+    /// The drag was cancelled after the item was picked up, e.g. with the [DragDropConfig::cancel_key].
+    /// Presses that never picked an item up are only reported via [DragDropResponse::cancellation_reason].
+    Cancelled {
+        /// The id of the item that was put back
+        id: Id,
+        /// Why the drag was cancelled
+        reason: CancellationReason,
+    },
 }
 
 impl DragDropResponse {
@@ -58,27 +393,186 @@ impl DragDropResponse {
         self.state.is_dragging()
     }
 
+    /// While the pointer is held on an item waiting for the [DragDropConfig::drag_delay], returns
+    /// the id of the item and how much of the delay has passed, from 0 to 1.
+    /// Use this to draw your own indicator, or enable [DragDropConfig::hold_indicator].
+    /// The progress is also available as [crate::ItemState::hold_progress].
+    pub fn hold_progress(&self) -> Option<(Id, f32)> {
+        self.hold_progress
+    }
+
     /// Returns the id of the item that is currently being dragged.
     pub fn dragged_item_id(&self) -> Option<Id> {
         self.state.dragged_item()
     }
 
+    /// Returns the index the dragged item would end up at if it was dropped now, e.g. to show
+    /// "Will move to position 4" somewhere else. See [DragUpdate::target_index].
+    /// Returns None if no item is being dragged.
+    pub fn hovering_index(&self) -> Option<usize> {
+        self.update
+            .as_ref()
+            .filter(|_| self.is_dragging())
+            .map(DragUpdate::target_index)
+    }
+
     /// Returns true if the drag & drop event has finished and the item has been dropped.
     /// The update should be applied to the source list.
     pub fn is_drag_finished(&self) -> bool {
         self.finished
     }
 
+    /// Returns true if an item was picked up in this frame.
+    /// This is only true for a single frame per drag, e.g. to trigger haptic feedback.
+    pub fn is_drag_started(&self) -> bool {
+        self.events
+            .iter()
+            .any(|event| matches!(event, DndEvent::DragStarted { .. }))
+    }
+
+    /// Returns true if the index the dragged item would be dropped at changed in this frame.
+    /// This is only true once per change, e.g. to play a sound when the item moves to another slot.
+    pub fn has_slot_changed(&self) -> bool {
+        self.events
+            .iter()
+            .any(|event| matches!(event, DndEvent::TargetChanged { .. }))
+    }
+
+    /// How many slots the dragged item moved in this frame, e.g. `2` if it moved two slots towards
+    /// the end of the list and `-1` if it moved one slot towards the start.
+    /// This is `0` if the drop position didn't change, see [DragDropResponse::has_slot_changed].
+    pub fn slot_delta(&self) -> isize {
+        self.events
+            .iter()
+            .map(|event| match event {
+                DndEvent::TargetChanged { from, to, .. } => *to as isize - *from as isize,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Returns true if [crate::Dnd::validate_drop] vetoed a drop in this frame.
+    /// The item is still being dragged.
+    pub fn is_drop_vetoed(&self) -> bool {
+        self.events
+            .iter()
+            .any(|event| matches!(event, DndEvent::DropVetoed { .. }))
+    }
+
+    /// Undoes the last move recorded by [crate::Dnd::with_history] by moving the item back in
+    /// `container`. Returns false if there is nothing to undo.
+    ///
+    /// ```rust
+    /// use egui::{Key, Modifiers};
+    /// use egui_dnd::dnd;
+    ///
+    /// fn sortable_list(ui: &mut egui::Ui, items: &mut Vec<u32>) {
+    ///     let response = dnd(ui, "list")
+    ///         .with_history(100)
+    ///         .show_vec(items, |ui, item, handle, _state| {
+    ///             handle.ui(ui, |ui| {
+    ///                 ui.label(item.to_string());
+    ///             });
+    ///         });
+    ///     if ui.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::Z)) {
+    ///         response.undo(items);
+    ///     }
+    /// }
+    /// ```
+    pub fn undo<C: DndContainer + ?Sized>(&self, container: &mut C) -> bool {
+        self.apply_history(container, History::undo)
+    }
+
+    /// Makes the last move that was undone with [DragDropResponse::undo] again.
+    /// Returns false if there is nothing to redo.
+    pub fn redo<C: DndContainer + ?Sized>(&self, container: &mut C) -> bool {
+        self.apply_history(container, History::redo)
+    }
+
+    /// Returns true if there is a move that can be undone with [DragDropResponse::undo]
+    pub fn can_undo(&self) -> bool {
+        self.history
+            .as_ref()
+            .is_some_and(|history| history.lock().can_undo())
+    }
+
+    /// Returns true if there is a move that can be made again with [DragDropResponse::redo]
+    pub fn can_redo(&self) -> bool {
+        self.history
+            .as_ref()
+            .is_some_and(|history| history.lock().can_redo())
+    }
+
+    fn apply_history<C: DndContainer + ?Sized>(
+        &self,
+        container: &mut C,
+        next_move: fn(&mut History) -> Option<(usize, usize)>,
+    ) -> bool {
+        let next_move = self
+            .history
+            .as_ref()
+            .and_then(|history| next_move(&mut history.lock()));
+        match next_move {
+            Some((from, to)) if from < container.item_count() && to <= container.item_count() => {
+                container.shift_item(from, to);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the events that happened in this frame, in the order they happened.
+    /// Each event is only reported once, so this can be used to drive state machines in your app.
+    pub fn events(&self) -> &[DndEvent] {
+        &self.events
+    }
+
+    fn finish(&mut self, id: Id) {
+        self.finished = true;
+        if let Some(update) = self.update.clone() {
+            let merge = update.onto.filter(|_| update.merge);
+            let source = update.from;
+            self.events.push(DndEvent::Dropped { id, update });
+            if let Some(target) = merge {
+                self.events.push(DndEvent::Merge { id, source, target });
+            }
+        }
+    }
+
     /// Utility function to update a Vec with the current drag & drop state.
     /// You can use this to consistently update the source list while the drag & drop event is ongoing.
     pub fn update_vec<T>(&self, vec: &mut [T]) {
+        self.update(vec);
+    }
+
+    /// Same as [DragDropResponse::update_vec], but works with any [DndContainer],
+    /// e.g. a [std::collections::VecDeque] or, behind the `indexmap` feature, an `IndexMap`.
+    /// This applies every change while dragging, use [DragDropResponse::final_update] to only
+    /// apply the drop, e.g. to a database.
+    pub fn update<C: DndContainer + ?Sized>(&self, container: &mut C) {
+        self.apply(container);
+    }
+
+    /// Same as [DragDropResponse::update], but works with any [DndSortable], e.g. an undo
+    /// command stack that doesn't know how many items the list has.
+    /// Like [DragDropResponse::update], this moves the item for every change while dragging.
+    pub fn apply<S: DndSortable + ?Sized>(&self, model: &mut S) {
         if self.has_changed || self.finished {
-            if let Some(update) = &self.update {
-                shift_vec(update.from, update.to, vec);
+            // Dropping onto another item doesn't reorder the list
+            if let Some(update) = self.update.as_ref().filter(|update| update.onto.is_none()) {
+                model.move_item(update.from, update.to);
             }
         }
     }
 
+    /// Applies the update to several containers at once, e.g. when the data of each item is
+    /// split across parallel vectors. All containers need to have the same length, otherwise
+    /// this panics before any of them is changed.
+    /// Pass them as a tuple, e.g. `response.update_parallel((&mut names, &mut colors, &mut sizes))`.
+    pub fn update_parallel(&self, mut containers: impl DndContainer) {
+        self.update(&mut containers);
+    }
+
     /// Returns the update if the drag & drop event has finished and the item has been dropped.
     /// Useful for the if let syntax.
     pub fn final_update(&self) -> Option<DragUpdate> {
@@ -89,22 +583,264 @@ impl DragDropResponse {
         }
     }
 
-    /// Returns a [Option<&str>] with the reason if a drag & drop event was cancelled.
-    pub fn cancellation_reason(&self) -> Option<&'static str> {
-        self.cancellation_reason
+    /// The ids that were used by more than one item in this frame. Items need unique ids,
+    /// otherwise dragging them behaves unpredictably.
+    /// This is only checked in debug builds, where the duplicates are also logged and marked on screen.
+    pub fn duplicate_ids(&self) -> &[Id] {
+        &self.duplicate_ids
+    }
+
+    /// The response for the rect of the item with the given id (see [DragDropItem::id]), e.g. to
+    /// show a context menu or tooltip or to react to double clicks on the whole row.
+    /// It senses clicks, but widgets in the item, including the handle, take precedence.
+    /// Returns `None` if the item wasn't shown in this frame.
+    pub fn item_response(&self, id: Id) -> Option<&egui::Response> {
+        self.item_responses.get(&id)
+    }
+
+    /// The indices and ids of the items shown in this frame, in the order of the source list
+    pub(crate) fn shown_items(&self) -> &[(usize, Id)] {
+        &self.shown_items
+    }
+
+    /// The indices of the shown items in the order they are displayed, with the dragged item at the
+    /// position it would be dropped at. This is useful with [crate::CommitStrategy::OnDrop], where
+    /// the source list isn't reordered until the item is dropped, to mirror the preview elsewhere,
+    /// e.g. to show "position 3 of 9" in a details pane.
+    /// The indices refer to the source list as it was passed in this frame.
+    pub fn current_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = self.shown_items.iter().map(|(idx, _)| *idx).collect();
+        if let Some(update) = self
+            .update
+            .as_ref()
+            .filter(|_| self.is_dragging() || self.finished)
+        {
+            let position = |idx: usize| order.iter().position(|item_idx| *item_idx == idx);
+            if let Some(from) = position(update.from) {
+                let to = position(update.to).unwrap_or(order.len());
+                crate::utils::shift_vec(from, to, &mut order);
+            }
+        }
+        order
+    }
+
+    /// The index a payload accepted with [crate::Dnd::accept_payload] or files accepted with
+    /// [crate::Dnd::accept_files] would be inserted at, if they are dragged over the items in this frame
+    pub fn external_hover_index(&self) -> Option<usize> {
+        self.external_hover_index
+    }
+
+    /// The payload accepted with [crate::Dnd::accept_payload] that was dropped on the list in this
+    /// frame, with the index it should be inserted at.
+    /// Returns `None` if nothing was dropped or the payload isn't of type `P`.
+    pub fn dropped_payload<P: Any + Send + Sync>(&self) -> Option<(usize, Arc<P>)> {
+        let (index, payload) = self.dropped_payload.clone()?;
+        Some((index, payload.downcast().ok()?))
+    }
+
+    /// The files that were dropped on the list in this frame, if it accepts them,
+    /// see [crate::Dnd::accept_files]
+    pub fn external_drop(&self) -> Option<&ExternalDrop> {
+        self.external_drop.as_ref()
+    }
+
+    /// The responses of all items shown in this frame, see [DragDropResponse::item_response]
+    pub fn item_responses(&self) -> &HashMap<Id, egui::Response> {
+        &self.item_responses
+    }
+
+    /// Returns the reason if a drag & drop event was cancelled.
+    pub fn cancellation_reason(&self) -> Option<CancellationReason> {
+        self.cancellation_reason.clone()
+    }
+
+    /// Returns the modifier keys held in this frame.
+    /// When [DragDropResponse::is_drag_finished] returns true, these are the modifiers held when the item was dropped.
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+}
+
+/// Why a drag & drop event was cancelled, see [DragDropResponse::cancellation_reason]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CancellationReason {
+    /// The pointer moved further than [DragDropConfig::scroll_tolerance] before the drag started
+    ScrollToleranceExceeded,
+    /// The pointer wasn't over any item handle when it was pressed
+    NotOverHandle,
+    /// The pointer didn't start dragging any item
+    NotOverTarget,
+    /// The [DragDropConfig::cancel_key] was pressed while dragging.
+    /// The update of the response moves the item back to where it was picked up.
+    CancelKey,
+    /// Another pointer button was pressed while dragging, see [DragDropConfig::cancel_on_other_button].
+    /// The update of the response moves the item back to where it was picked up.
+    OtherButtonPressed,
+    /// The handle was pressed without being tapped right before, see [DragDropConfig::double_tap_and_hold]
+    NotDoubleTapped,
+    /// The item was released where [crate::Dnd::allow_drop] doesn't allow dropping it.
+    /// This only happens if there is no allowed position to snap to.
+    /// The update of the response moves the item back to where it was picked up.
+    InvalidDrop,
+    /// A widget that senses drags inside a handle drawn with [Handle::fill_item] was pressed
+    WidgetPressed,
+    /// The [crate::Dnd] was disabled with [crate::Dnd::enabled] while dragging.
+    /// The update of the response moves the item back to where it was picked up.
+    Disabled,
+    /// The dragged item isn't shown anymore, e.g. because it was removed from the list.
+    /// There is no update, since the item can't be moved back.
+    ListChanged,
+    /// The window lost focus while dragging, so the release of the pointer might never arrive.
+    /// Also used when the pointer left the window, see [DragDropConfig::focus_loss] and
+    /// [DragDropConfig::pointer_left_window].
+    /// The update of the response moves the item back to where it was picked up.
+    WindowUnfocused,
+    /// The drag was cancelled with [crate::Dnd::cancel_drag] without a specific reason.
+    /// The update of the response moves the item back to where it was picked up.
+    Programmatic,
+    /// The drag was cancelled with [crate::Dnd::cancel_drag] and this message, e.g. to show it in
+    /// a snackbar or to report it as telemetry.
+    /// The update of the response moves the item back to where it was picked up.
+    Custom(String),
+}
+
+impl std::fmt::Display for CancellationReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CancellationReason::ScrollToleranceExceeded => {
+                "Drag distance exceeded scroll threshold"
+            }
+            CancellationReason::NotOverHandle => "Cursor not hovering over any item handle",
+            CancellationReason::NotOverTarget => "Not hovering over any target",
+            CancellationReason::CancelKey => "Drag was cancelled by pressing the cancel key",
+            CancellationReason::OtherButtonPressed => {
+                "Drag was cancelled by pressing another pointer button"
+            }
+            CancellationReason::NotDoubleTapped => "Handle was not tapped before being pressed",
+            CancellationReason::InvalidDrop => "Item can't be dropped at this position",
+            CancellationReason::WidgetPressed => "A widget inside the item was pressed",
+            CancellationReason::Disabled => "Drag & drop was disabled while dragging",
+            CancellationReason::ListChanged => "The dragged item was removed from the list",
+            CancellationReason::WindowUnfocused => {
+                "The window lost focus or the pointer left it while dragging"
+            }
+            CancellationReason::Programmatic => "Drag was cancelled by the application",
+            CancellationReason::Custom(message) => message,
+        })
     }
 }
 
 /// Holds the data needed to draw the floating item while it is being dragged
 /// Deprecated: Use [crate::dnd] or [crate::Dnd::new] instead
+///
+/// With the `serde` feature, only the configs and animation times are serialized. A drag that is
+/// in progress is not, so restoring the state never resumes a drag whose pointer is long gone.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DragDropUi {
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) detection_state: DragDetectionState,
     /// If the mobile config is set, we will use it if we detect a touch event
     touch_config: Option<DragDropConfig>,
+    /// If the pen config is set, we will use it if the touches report a pressure
+    pen_config: Option<DragDropConfig>,
     mouse_config: DragDropConfig,
+    /// Whether the last touch event reported a pressure, which we take as a sign of pen input
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pen_active: bool,
     pub(crate) swap_animation_time: f32,
     pub(crate) return_animation_time: f32,
+    /// The sizes of the items shown in the last frame before the drag started, used by
+    /// [DragDropConfig::lock_cross_axis_size]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) item_sizes: HashMap<Id, Vec2>,
+    /// The z indices and shape ranges of the items shown this frame with a z index other than
+    /// 0, see [crate::item::Item::z_index]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) paint_order: Vec<(i32, std::ops::Range<usize>)>,
+    /// The range of the shapes the dragged item of this list painted on
+    /// [DragDropConfig::floating_item_layer] this frame, copied by [DragDropConfig::ghost_opacity]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) floating_shapes: Option<std::ops::Range<usize>>,
+    /// Where the dragged item would be placed in the slot it hovers, see
+    /// [DragDropConfig::slot_snapping]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) snap_position: Option<Pos2>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) transitions: ItemTransitions,
+    /// An item that should start being dragged the next time it's shown, see [DragDropUi::begin_drag]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pending_drag: Option<(Id, DragMode)>,
+    /// A drag state to force the list into, see [DragDropUi::snapshot_drag]
+    #[cfg(feature = "test_utils")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pending_snapshot: Option<DragSnapshot>,
+    /// Cancels the current drag in the next frame, see [DragDropUi::cancel_drag]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pending_cancel: Option<CancellationReason>,
+    /// Whether the pointer dragging the item is outside of the window,
+    /// so [DndEvent::DraggedOutOfWindow] is only reported once
+    #[cfg_attr(feature = "serde", serde(skip))]
+    dragged_out_of_window: bool,
+    /// The size of the list's ui and of the screen in the last frame, to notice when the layout
+    /// changes during a drag, e.g. because the window or a panel is resized
+    #[cfg_attr(feature = "serde", serde(skip))]
+    layout_size: Option<(Vec2, Rect)>,
+    /// The position of the list and the pixels per point in the last frame, to move the positions
+    /// stored for a drag along when the list moves or the zoom changes
+    #[cfg_attr(feature = "serde", serde(skip))]
+    list_anchor: Option<(Pos2, f32)>,
+    /// Whether the layout changed in this frame, so the items are moved to their new positions
+    /// right away instead of being animated there
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) layout_changed: bool,
+    /// The moves that can be undone, see [crate::Dnd::with_history]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) history: Option<Arc<Mutex<History>>>,
+    /// Whether the order stored by [crate::Dnd::persist_order] was applied to the items yet
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) order_restored: bool,
+    /// The item that was cut with ctrl + x and is moved with the next paste
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) cut_item: Option<Id>,
+    /// The item that had the focus when ctrl + v was pressed, the cut item is moved in front of it
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pending_paste: Option<Id>,
+    /// When and where the last tap was, used by [DragDropConfig::double_tap_and_hold]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_tap: Option<(SystemTime, Pos2)>,
+    /// The rects of the items and the area covered by them in the last frame, to find where a
+    /// payload or files dragged in from outside of the list would be inserted
+    #[cfg_attr(feature = "serde", serde(skip))]
+    external_rects: (Vec<Rect>, Rect),
+    /// Whether the configs were checked with [DragDropConfig::validate] yet
+    #[cfg(debug_assertions)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    config_validated: bool,
+    /// Ids shared by multiple items that were already logged
+    #[cfg(debug_assertions)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    reported_duplicate_ids: std::collections::HashSet<Id>,
+    /// The number of items shown in the last frame
+    #[cfg_attr(feature = "serde", serde(skip))]
+    item_count: usize,
+    /// The item the pointer was over in the last frame, see [Handle::show_on_hover]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    hovered_item: Option<Id>,
+    /// The item the pointer was pressed on while waiting for the [DragDropConfig::drag_delay]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pressed_item: Option<Id>,
+    /// The collapsed item the dragged item is held onto and since when, see
+    /// [crate::Dnd::spring_open]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    spring_hover: Option<(Id, f64)>,
+    /// The items that were expanded during the current drag
+    #[cfg_attr(feature = "serde", serde(skip))]
+    spring_opened: Vec<Id>,
+    #[cfg(feature = "accesskit")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    announcer: Announcer,
 }
 
 impl Default for DragDropUi {
@@ -112,13 +848,55 @@ impl Default for DragDropUi {
         DragDropUi {
             detection_state: DragDetectionState::None,
             touch_config: Some(DragDropConfig::touch()),
+            pen_config: None,
             mouse_config: DragDropConfig::mouse(),
+            pen_active: false,
             swap_animation_time: 0.2,
             return_animation_time: 0.2,
+            item_sizes: HashMap::new(),
+            paint_order: Vec::new(),
+            floating_shapes: None,
+            snap_position: None,
+            transitions: ItemTransitions::default(),
+            pending_drag: None,
+            #[cfg(feature = "test_utils")]
+            pending_snapshot: None,
+            pending_cancel: None,
+            dragged_out_of_window: false,
+            layout_size: None,
+            list_anchor: None,
+            layout_changed: false,
+            history: None,
+            order_restored: false,
+            cut_item: None,
+            pending_paste: None,
+            last_tap: None,
+            external_rects: (Vec::new(), Rect::NOTHING),
+            #[cfg(debug_assertions)]
+            config_validated: false,
+            #[cfg(debug_assertions)]
+            reported_duplicate_ids: Default::default(),
+            item_count: 0,
+            hovered_item: None,
+            pressed_item: None,
+            spring_hover: None,
+            spring_opened: Vec::new(),
+            #[cfg(feature = "accesskit")]
+            announcer: Announcer::default(),
         }
     }
 }
 
+/// Whether a [Handle] can start a drag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HandleMode {
+    Draggable,
+    /// The handle looks normal but doesn't start a drag, e.g. because the item is locked
+    Inert,
+    /// The handle is shown disabled and doesn't start a drag
+    Disabled,
+}
+
 /// [Handle::ui] is used to draw the drag handle
 pub struct Handle<'a> {
     id: Id,
@@ -126,11 +904,15 @@ pub struct Handle<'a> {
     state: &'a mut DragDropUi,
     hovering_over_any_handle: &'a mut bool,
     item_pos: Pos2,
+    mode: HandleMode,
 
     // Configurable options
     sense: Option<Sense>,
     show_drag_cursor_on_hover: bool,
+    show_on_hover: bool,
     disable_selectable_labels: bool,
+    /// Set by [Handle::fill_item], lets widgets in the handle that sense drags win the pointer
+    yield_to_widgets: bool,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -144,10 +926,20 @@ pub(crate) enum DragDetectionState {
         pressed_at: SystemTime,
     },
     CouldBeValidDrag,
-    Cancelled(&'static str),
+    Cancelled(CancellationReason),
     Dragging {
         id: Id,
         source_idx: usize,
+        /// The index of the item when it was picked up, used to move it back if the drag is cancelled
+        start_idx: usize,
+        /// The item in front of which the dragged item is put back. If items are added or removed
+        /// during the drag, `start_idx` is updated to stay in front of it.
+        /// `None` if the dragged item was the last item.
+        start_next: Option<Id>,
+        /// The index the item would land at when dropped, as of the last reported slot change
+        slot: usize,
+        /// Where the item was picked up, to calculate [crate::ItemState::drag_delta]
+        start_pos: Pos2,
         offset: Vec2,
         dragged_item_size: Vec2,
         closest_item: (Id, Pos2),
@@ -156,6 +948,7 @@ pub(crate) enum DragDetectionState {
 
         // These should only be used for output, as to not cause issues when item indexes change
         hovering_idx: usize,
+        input: DragInput,
     },
     TransitioningBackAfterDragFinished {
         id: Id,
@@ -163,7 +956,98 @@ pub(crate) enum DragDetectionState {
     },
 }
 
+/// How an item picked up with [crate::Dnd::begin_drag] is moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DragMode {
+    /// The item follows the pointer without a button being held and is dropped with the next click
+    Pointer,
+    /// The item is moved with the arrow keys and dropped with enter, like when it's picked up
+    /// with the keyboard, see [DragDropConfig::keyboard_reordering]
+    Keyboard,
+}
+
+/// How the dragged item is moved
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum DragInput {
+    /// The item follows the pointer while a button is held and is dropped when it's released
+    Pointer,
+    /// The item follows the pointer without a button being held and is dropped with the next click
+    StickyPointer {
+        /// Whether a button was pressed since the item was picked up,
+        /// so the release of the click that picked it up doesn't drop it
+        pressed: bool,
+    },
+    Keyboard(KeyboardDrag),
+}
+
+/// State of an item that is moved with the keyboard instead of the pointer
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct KeyboardDrag {
+    /// The slot the item would be dropped at, counting only the items that aren't being dragged
+    pub(crate) target: usize,
+    /// If true, the item is dropped in the next frame, e.g. because a screen reader requested a move
+    drop_requested: bool,
+}
+
+/// How far apart the two taps of [DragDropConfig::double_tap_and_hold] may be
+const DOUBLE_TAP_DISTANCE: f32 = 20.0;
+
+/// The id used to give keyboard focus to the handle of the item with the given id
+pub(crate) fn keyboard_focus_id(item_id: Id) -> Id {
+    item_id.with("dnd_keyboard_focus")
+}
+
+/// Consumes a cut command. Most backends report ctrl + x as [Event::Cut] instead of a key press.
+fn consume_cut(input: &mut InputState) -> bool {
+    let had_event = input.events.iter().any(|event| matches!(event, Event::Cut));
+    input.events.retain(|event| !matches!(event, Event::Cut));
+    input.consume_key(Modifiers::COMMAND, Key::X) || had_event
+}
+
+/// Consumes a paste command. Most backends report ctrl + v as [Event::Paste] instead of a key press.
+fn consume_paste(input: &mut InputState) -> bool {
+    let had_event = input
+        .events
+        .iter()
+        .any(|event| matches!(event, Event::Paste(_)));
+    input
+        .events
+        .retain(|event| !matches!(event, Event::Paste(_)));
+    input.consume_key(Modifiers::COMMAND, Key::V) || had_event
+}
+
+/// Keeps the arrow keys and escape from moving the focus away while an item is moved with the keyboard
+fn lock_keyboard_focus(ui: &Ui, focus_id: Id) {
+    ui.memory_mut(|mem| {
+        mem.set_focus_lock_filter(
+            focus_id,
+            EventFilter {
+                horizontal_arrows: true,
+                vertical_arrows: true,
+                escape: true,
+                ..Default::default()
+            },
+        );
+    });
+}
+
 impl DragDetectionState {
+    /// The name of the variant, without its fields
+    fn name(&self) -> &'static str {
+        match self {
+            DragDetectionState::None => "None",
+            DragDetectionState::PressedWaitingForDelay { .. } => "PressedWaitingForDelay",
+            DragDetectionState::WaitingForClickThreshold { .. } => "WaitingForClickThreshold",
+            DragDetectionState::CouldBeValidDrag => "CouldBeValidDrag",
+            DragDetectionState::Cancelled(_) => "Cancelled",
+            DragDetectionState::Dragging { .. } => "Dragging",
+            DragDetectionState::TransitioningBackAfterDragFinished { .. } => {
+                "TransitioningBackAfterDragFinished"
+            }
+        }
+    }
+
     fn is_evaluating_drag(&self) -> bool {
         matches!(self, DragDetectionState::WaitingForClickThreshold { .. })
             || matches!(self, DragDetectionState::PressedWaitingForDelay { .. })
@@ -198,6 +1082,103 @@ impl DragDetectionState {
         }
     }
 
+    pub(crate) fn keyboard_drag(&self) -> Option<KeyboardDrag> {
+        match self {
+            DragDetectionState::Dragging {
+                input: DragInput::Keyboard(keyboard),
+                ..
+            } => Some(*keyboard),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_keyboard_dragging(&self) -> bool {
+        self.keyboard_drag().is_some()
+    }
+
+    fn is_sticky_dragging(&self) -> bool {
+        matches!(
+            self,
+            DragDetectionState::Dragging {
+                input: DragInput::StickyPointer { .. },
+                ..
+            }
+        )
+    }
+
+    /// The item that is drawn floating above the list, either while it's dragged
+    /// or while it moves back into place after being dropped
+    fn floating_item(&self) -> Option<Id> {
+        match self {
+            DragDetectionState::Dragging { id, .. }
+            | DragDetectionState::TransitioningBackAfterDragFinished { id, .. } => Some(*id),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn source_idx(&self) -> Option<usize> {
+        match self {
+            DragDetectionState::Dragging { source_idx, .. } => Some(*source_idx),
+            _ => None,
+        }
+    }
+
+    /// The original and the prospective index of an item that isn't dragged, given how many
+    /// items that aren't dragged come before it
+    pub(crate) fn displaced_indices(&self, preceding_items: usize) -> Option<(usize, usize)> {
+        match self {
+            DragDetectionState::Dragging {
+                start_idx, slot, ..
+            } => Some((
+                preceding_items + usize::from(*start_idx <= preceding_items),
+                preceding_items + usize::from(*slot <= preceding_items),
+            )),
+            _ => None,
+        }
+    }
+
+    /// The index the dragged item would land at and how far it was moved since it was picked up
+    pub(crate) fn dragged_item_progress(&self, pointer_pos: Option<Pos2>) -> Option<(usize, Vec2)> {
+        match self {
+            DragDetectionState::Dragging {
+                slot,
+                start_pos,
+                last_pointer_pos,
+                input,
+                ..
+            } => {
+                let pos = match input {
+                    DragInput::Keyboard(_) => *last_pointer_pos,
+                    _ => pointer_pos.unwrap_or(*last_pointer_pos),
+                };
+                Some((*slot, pos - *start_pos))
+            }
+            _ => None,
+        }
+    }
+
+    /// Moves the positions stored for the drag along with the list, which moved by `offset`.
+    /// The pointer stays where it is on the screen, so when the zoom changes its last known
+    /// position is scaled by `pointer_scale`, the ratio of the old to the new pixels per point.
+    fn reproject(&mut self, offset: Vec2, pointer_scale: f32) {
+        if let DragDetectionState::Dragging {
+            start_pos,
+            closest_item,
+            last_pointer_pos,
+            input,
+            ..
+        } = self
+        {
+            *start_pos += offset;
+            closest_item.1 += offset;
+            *last_pointer_pos = match input {
+                // Follows the gap, which moves with the list
+                DragInput::Keyboard(_) => *last_pointer_pos + offset,
+                _ => (last_pointer_pos.to_vec2() * pointer_scale).to_pos2(),
+            };
+        }
+    }
+
     pub(crate) fn last_pointer_pos(&self) -> Option<Pos2> {
         match self {
             DragDetectionState::Dragging {
@@ -215,6 +1196,7 @@ impl<'a> Handle<'a> {
         state: &'a mut DragDropUi,
         hovering_over_any_handle: &'a mut bool,
         item_pos: Pos2,
+        mode: HandleMode,
     ) -> Self {
         Handle {
             id,
@@ -222,10 +1204,13 @@ impl<'a> Handle<'a> {
             state,
             hovering_over_any_handle,
             item_pos,
+            mode,
 
             sense: None,
             show_drag_cursor_on_hover: true,
+            show_on_hover: false,
             disable_selectable_labels: true,
+            yield_to_widgets: false,
         }
     }
 
@@ -233,6 +1218,9 @@ impl<'a> Handle<'a> {
     /// **Warning**: This will make anything sensing clicks in the handle not draggable
     /// Make sure to not set this if your handle consists of a single button, and directly
     /// query the button for clicks.
+    ///
+    /// How far and how long the pointer may move before a press becomes a drag instead of a click
+    /// is set per input device, see [DragDropConfig::click_threshold].
     pub fn sense(mut self, sense: Sense) -> Self {
         self.sense = Some(sense);
         self
@@ -245,6 +1233,23 @@ impl<'a> Handle<'a> {
         self
     }
 
+    /// If `true`, the contents of the handle fade in while the pointer is over the item, or the
+    /// item is dragged or focused, and are invisible otherwise, like the grip icons of many task
+    /// and note apps. The handle still takes up its space and can be grabbed while it's hidden.
+    pub fn show_on_hover(mut self, show_on_hover: bool) -> Self {
+        self.show_on_hover = show_on_hover;
+        self
+    }
+
+    /// If false, the handle is shown disabled and can't be used to drag the item, e.g. while the
+    /// item is being edited. Other items can still be moved around it.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        if !enabled {
+            self.mode = HandleMode::Disabled;
+        }
+        self
+    }
+
     /// By default, selectable labels are disabled in the handle, to not interfere with dragging.
     /// You can use this to re-enable them.
     /// Note that if you disable selectable labels globally, this won't have any effect.
@@ -269,14 +1274,34 @@ impl<'a> Handle<'a> {
             None
         };
 
-        let response = ui.scope(contents);
+        let mode = self.mode;
+        let opacity = self.hover_opacity(ui);
+        let first_shape = ui.painter().add(Shape::Noop);
+        let dragged_before = ui.memory(|mem| mem.dragged_id());
+        let response = ui.scope(|ui| {
+            if mode == HandleMode::Disabled {
+                ui.set_enabled(false);
+            }
+            contents(ui);
+        });
+        if let Some(opacity) = opacity {
+            fade_shapes(ui, first_shape, opacity);
+        }
 
         if let Some((selectable_labels, multi_widget_text_select)) = disabled {
             ui.style_mut().interaction.selectable_labels = selectable_labels;
             ui.style_mut().interaction.multi_widget_text_select = multi_widget_text_select;
         }
 
-        self.handle_response(response.response, ui)
+        self.handle_response(response.response, ui, dragged_before)
+    }
+
+    /// Makes the whole item the drag handle: draw all of the item's contents in here.
+    /// Buttons in the item are still clickable, and widgets that sense drags, like sliders or
+    /// text edits, win the pointer when pressed, so only pressing anywhere else drags the item.
+    pub fn fill_item(mut self, ui: &mut Ui, contents: impl FnOnce(&mut Ui)) -> egui::Response {
+        self.yield_to_widgets = true;
+        self.ui(ui, contents)
     }
 
     /// This is useful if you want to sort items in a horizontal_wrapped.
@@ -287,7 +1312,13 @@ impl<'a> Handle<'a> {
         size: Vec2,
         add_contents: impl FnOnce(&mut Ui),
     ) -> egui::Response {
+        let opacity = self.hover_opacity(ui);
+        let first_shape = ui.painter().add(Shape::Noop);
+        let dragged_before = ui.memory(|mem| mem.dragged_id());
         let response = ui.allocate_ui(size, |ui| {
+            if self.mode == HandleMode::Disabled {
+                ui.set_enabled(false);
+            }
             if self.disable_selectable_labels {
                 ui.style_mut().interaction.selectable_labels = false;
                 ui.style_mut().interaction.multi_widget_text_select = false;
@@ -295,23 +1326,120 @@ impl<'a> Handle<'a> {
             // We somehow have to push a new id here or there will be an id clash at response.interact
             ui.push_id(self.id.with("handle"), add_contents)
         });
-        self.handle_response(response.response, ui)
+        if let Some(opacity) = opacity {
+            fade_shapes(ui, first_shape, opacity);
+        }
+        self.handle_response(response.response, ui, dragged_before)
+    }
+
+    /// The opacity of the contents if they are only shown on hover, see [Handle::show_on_hover]
+    fn hover_opacity(&self, ui: &Ui) -> Option<f32> {
+        if !self.show_on_hover {
+            return None;
+        }
+        let visible = self.state.hovered_item == Some(self.id)
+            || self.state.detection_state.is_dragging_item(self.id)
+            || ui.memory(|mem| mem.has_focus(keyboard_focus_id(self.id)));
+        let opacity = ui
+            .ctx()
+            .animate_bool(self.id.with("dnd_handle_visible"), visible);
+        Some(opacity).filter(|opacity| *opacity < 1.0)
     }
 
-    fn handle_response(&mut self, response: egui::Response, ui: &mut Ui) -> egui::Response {
+    /// `dragged_before` is the widget that was dragged before the contents of the handle were shown
+    fn handle_response(
+        &mut self,
+        response: egui::Response,
+        ui: &mut Ui,
+        dragged_before: Option<Id>,
+    ) -> egui::Response {
         let response = if let Some(sense) = self.sense {
             response.interact(sense)
         } else {
             response
         };
 
+        if self.mode != HandleMode::Draggable {
+            return response;
+        }
+
+        // Widgets in the item that sense drags register before the handle, so if one of them
+        // was pressed it is already being dragged by now. Widgets around the item, like a
+        // ScrollArea or a movable Window, took the drag before the contents were shown.
+        let widget_pressed = self.yield_to_widgets
+            && ui
+                .memory(|mem| mem.dragged_id())
+                .is_some_and(|id| id != self.id && Some(id) != dragged_before);
+
         if response.contains_pointer() {
-            if self.show_drag_cursor_on_hover {
-                ui.output_mut(|o| o.cursor_icon = CursorIcon::Grab);
+            if let Some(cursor) = self
+                .state
+                .config(ui)
+                .cursors
+                .hover
+                .filter(|_| self.show_drag_cursor_on_hover)
+                // Keep the cursor a widget in the item has set, e.g. the text cursor of a TextEdit
+                .filter(|_| {
+                    !self.yield_to_widgets || ui.output(|o| o.cursor_icon) == CursorIcon::Default
+                })
+            {
+                ui.output_mut(|o| o.cursor_icon = cursor);
             }
             *self.hovering_over_any_handle = true;
         }
 
+        if widget_pressed
+            && self.state.detection_state.is_evaluating_drag()
+            && response
+                .rect
+                .contains(ui.input(|input| input.pointer.press_origin().unwrap_or_default()))
+        {
+            self.state.detection_state =
+                DragDetectionState::Cancelled(CancellationReason::WidgetPressed);
+            return response;
+        }
+
+        if self.state.config(ui).keyboard_reordering {
+            self.handle_keyboard(&response, ui);
+        }
+        if self.state.config(ui).context_menu {
+            self.context_menu(&response);
+        }
+
+        if let Some((_, mode)) = self.state.pending_drag.filter(|(id, _)| *id == self.id) {
+            self.state.pending_drag = None;
+            if matches!(
+                self.state.detection_state,
+                DragDetectionState::None
+                    | DragDetectionState::TransitioningBackAfterDragFinished { .. }
+            ) {
+                match mode {
+                    DragMode::Pointer => {
+                        let pointer_pos =
+                            ui.input(|i| i.pointer.hover_pos()).unwrap_or(self.item_pos);
+                        self.start_drag(
+                            self.item_pos - pointer_pos,
+                            pointer_pos,
+                            DragInput::StickyPointer { pressed: false },
+                        );
+                    }
+                    DragMode::Keyboard => {
+                        self.start_keyboard_drag(self.idx, false);
+                        ui.memory_mut(|mem| mem.request_focus(keyboard_focus_id(self.id)));
+                    }
+                }
+            }
+        }
+
+        #[cfg(feature = "test_utils")]
+        if let Some(snapshot) = self
+            .state
+            .pending_snapshot
+            .filter(|snapshot| snapshot.item == self.id)
+        {
+            self.apply_snapshot(snapshot);
+        }
+
         let offset = self.item_pos.to_vec2()
             - ui.ctx()
                 .input(|i| i.pointer.hover_pos())
@@ -324,8 +1452,22 @@ impl<'a> Handle<'a> {
             .length()
         });
 
-        let click_threshold = 1.0;
-        let is_above_click_threshold = drag_distance > click_threshold;
+        // With sticky drag, a click picks the item up instead of being ignored
+        let drag_button = self.state.config(ui).drag_button;
+        if self.state.config(ui).sticky_drag
+            && self.state.detection_state.is_evaluating_drag()
+            && response.contains_pointer()
+            && ui.input(|i| i.pointer.button_clicked(drag_button))
+        {
+            let pointer_pos = ui.input(|i| i.pointer.hover_pos()).unwrap_or(self.item_pos);
+            self.start_drag(
+                offset,
+                pointer_pos,
+                DragInput::StickyPointer { pressed: false },
+            );
+        }
+
+        let is_above_click_threshold = drag_distance > self.state.config(ui).click_tolerance;
 
         if response.contains_pointer()
             && response
@@ -353,35 +1495,170 @@ impl<'a> Handle<'a> {
                 DragDetectionState::CouldBeValidDrag
             )
         {
-            self.state.detection_state = DragDetectionState::Dragging {
-                id: self.id,
-                offset,
-                // We set this in the Item
-                dragged_item_size: Default::default(),
-                closest_item: (self.id, self.item_pos),
-                source_idx: self.idx,
-                hovering_idx: self.idx,
-                last_pointer_pos: ui
-                    .ctx()
-                    .input(|i| i.pointer.hover_pos())
-                    .unwrap_or_default(),
-                hovering_last_item: false,
-            };
+            let pointer_pos = ui.input(|i| i.pointer.hover_pos()).unwrap_or_default();
+            self.start_drag(offset, pointer_pos, DragInput::Pointer);
             ui.memory_mut(|mem| mem.set_dragged_id(self.id));
         }
 
         response
     }
+
+    /// Makes the handle focusable and picks the item up when space or enter is pressed
+    fn handle_keyboard(&mut self, response: &egui::Response, ui: &mut Ui) {
+        let focus_id = keyboard_focus_id(self.id);
+
+        // While the item is floating, the focus is kept by [DragDropUi::ui]
+        if self.state.detection_state.floating_item() != Some(self.id) {
+            ui.interact(response.rect, focus_id, Sense::focusable_noninteractive());
+        }
+        #[cfg(feature = "accesskit")]
+        self.handle_accesskit(ui, focus_id);
+
+        if !ui.memory(|mem| mem.has_focus(focus_id)) {
+            return;
+        }
+
+        ui.painter().rect_stroke(
+            response.rect,
+            ui.visuals().widgets.noninteractive.rounding,
+            ui.visuals().selection.stroke,
+        );
+
+        if matches!(self.state.detection_state, DragDetectionState::None)
+            && ui.input_mut(|i| {
+                i.consume_key(Modifiers::NONE, Key::Space)
+                    || i.consume_key(Modifiers::NONE, Key::Enter)
+            })
+        {
+            self.start_keyboard_drag(self.idx, false);
+            lock_keyboard_focus(ui, focus_id);
+        }
+
+        // Cut the item, or paste the cut item in front of this one
+        if matches!(self.state.detection_state, DragDetectionState::None) {
+            let (cut, paste) = ui.input_mut(|i| (consume_cut(i), consume_paste(i)));
+            if cut && self.mode == HandleMode::Draggable {
+                // Cutting the item again takes it back
+                self.state.cut_item = if self.state.cut_item == Some(self.id) {
+                    None
+                } else {
+                    Some(self.id)
+                };
+            }
+            if paste && self.state.cut_item.is_some() {
+                self.state.pending_paste = Some(self.id);
+            }
+        }
+    }
+
+    /// Moves the item in a single step, when a screen reader requests it
+    #[cfg(feature = "accesskit")]
+    fn handle_accesskit(&mut self, ui: &Ui, focus_id: Id) {
+        let grabbed = self.state.detection_state.dragged_item() == Some(self.id);
+        accessibility::item_node(ui, focus_id, self.idx, self.state.item_count, grabbed);
+
+        if let (Some(target), DragDetectionState::None) = (
+            accessibility::requested_move(ui, focus_id, self.idx, self.state.item_count),
+            &self.state.detection_state,
+        ) {
+            self.start_keyboard_drag(target, true);
+        }
+    }
+
+    /// Shows the context menu of [DragDropConfig::context_menu]
+    fn context_menu(&mut self, response: &egui::Response) {
+        let last = self.state.item_count.saturating_sub(1);
+        let idx = self.idx;
+        let mut target = None;
+        response.clone().context_menu(|ui| {
+            for (label, enabled, to) in [
+                ("Move up", idx > 0, idx.saturating_sub(1)),
+                ("Move down", idx < last, idx + 1),
+                ("Move to top", idx > 0, 0),
+                ("Move to bottom", idx < last, last),
+            ] {
+                if ui.add_enabled(enabled, egui::Button::new(label)).clicked() {
+                    target = Some(to);
+                    ui.close_menu();
+                }
+            }
+        });
+
+        // The move is made like a single step of a keyboard drag, dropped in the next frame
+        if let (Some(target), false) = (target, self.state.detection_state.is_dragging()) {
+            self.start_keyboard_drag(target, true);
+            response.ctx.request_repaint();
+        }
+    }
+
+    /// Holds the item like a keyboard drag, which doesn't depend on the pointer, with the gap
+    /// and offset of the snapshot
+    #[cfg(feature = "test_utils")]
+    fn apply_snapshot(&mut self, snapshot: DragSnapshot) {
+        if !(self.state.detection_state.is_dragging_item(self.id)
+            && self.state.detection_state.is_keyboard_dragging())
+        {
+            self.start_keyboard_drag(snapshot.slot, snapshot.settle);
+        }
+        if let DragDetectionState::Dragging {
+            offset,
+            input: DragInput::Keyboard(keyboard),
+            ..
+        } = &mut self.state.detection_state
+        {
+            *offset = snapshot.offset;
+            keyboard.target = snapshot.slot;
+            keyboard.drop_requested = snapshot.settle;
+        }
+    }
+
+    fn start_keyboard_drag(&mut self, target: usize, drop_requested: bool) {
+        self.start_drag(
+            Vec2::ZERO,
+            self.item_pos,
+            DragInput::Keyboard(KeyboardDrag {
+                target,
+                drop_requested,
+            }),
+        );
+    }
+
+    fn start_drag(&mut self, offset: Vec2, last_pointer_pos: Pos2, input: DragInput) {
+        self.state.detection_state = DragDetectionState::Dragging {
+            id: self.id,
+            offset,
+            // We set this in the Item
+            dragged_item_size: Default::default(),
+            closest_item: (self.id, self.item_pos),
+            source_idx: self.idx,
+            start_idx: self.idx,
+            start_next: None,
+            slot: self.idx,
+            start_pos: last_pointer_pos,
+            hovering_idx: self.idx,
+            last_pointer_pos,
+            hovering_last_item: false,
+            input,
+        };
+    }
 }
 
 /// Configuration for drag detection.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DragDropConfig {
     /// How long does the user have to keep pressing until a drag may begin?
     /// This is useful when dragging and dropping on a touch screen in a scrollable area.
     pub drag_delay: Duration,
     /// How far can the pointer move during the [DragDropConfig::drag_delay] before the drag is cancelled?
     pub scroll_tolerance: Option<f32>,
+    /// If set, the direction decides what happens when the pointer moves further than the
+    /// [DragDropConfig::scroll_tolerance] during the [DragDropConfig::drag_delay]:
+    /// If the movement is at least this angle (in radians) away from the main axis of the list,
+    /// the drag starts right away, otherwise the drag is cancelled so a surrounding ScrollArea
+    /// can scroll. In a vertical list, `Some(FRAC_PI_4)` drags on mostly horizontal swipes
+    /// and scrolls on mostly vertical ones.
+    pub drag_angle: Option<f32>,
     /// How far does the pointer have to move before a drag starts?
     /// This is useful when the handle is also a button.
     /// If the pointer is released before this threshold, the drag never starts and the button / handle can be clicked.
@@ -389,6 +1666,234 @@ pub struct DragDropConfig {
     pub click_tolerance: f32,
     /// If we have been holding longer than this duration, a drag will be started even if the pointer has not moved above [DragDropConfig::click_tolerance].
     pub click_tolerance_timeout: Duration,
+    /// How long it takes for the neighboring items to slide out of the way of the dragged item.
+    /// If None, the swap animation time set via [crate::Dnd::with_swap_animation_time] is used,
+    /// which defaults to the egui animation time.
+    /// Set this to [Duration::ZERO] to disable the animation.
+    pub shift_animation_duration: Option<Duration>,
+    /// If true, each item keeps the cross-axis size (width in a vertical list, height in a horizontal list)
+    /// it had before the drag started, until the drag is finished.
+    /// This prevents full-width items from shrinking when the dragged item leaves the layout flow.
+    pub lock_cross_axis_size: bool,
+    /// If false, all animations (items shifting out of the way, the dragged item flying back
+    /// into place after being dropped) are disabled.
+    /// This is useful for accessibility (reduced motion) and for screenshot-based tests.
+    /// Setting egui's [egui::style::Style::animation_time] to 0 has the same effect.
+    pub animations: bool,
+    /// If true, items that are added to or removed from the list from outside (not by drag and drop)
+    /// are animated growing in and collapsing out, instead of the list jumping.
+    /// Removed items are only animated when using [crate::Dnd::show] or [crate::Dnd::show_sized]
+    /// (or their _vec variants), since we need to know the items before they are shown.
+    pub enter_exit_animations: bool,
+    /// If set, a faded copy of the dragged item is shown at the position it would be dropped at,
+    /// while the item itself follows the pointer. The value is the opacity of the copy, between 0 and 1.
+    /// If None, an empty gap is shown instead.
+    pub ghost_opacity: Option<f32>,
+    /// How the position the dragged item would be dropped at is highlighted.
+    pub drop_target_highlight: DropTargetHighlight,
+    /// How the item the dragged item would be merged with is highlighted, see [crate::Dnd::merge].
+    pub merge_highlight: DropTargetHighlight,
+    /// The cursor icons shown when hovering a handle and while dragging.
+    pub cursors: DragCursors,
+    /// The layer the dragged item is painted on while it follows the pointer.
+    /// It isn't clipped by the list, so it stays fully visible when the list is in a ScrollArea
+    /// or a small child ui. By default this is a layer with [Order::Tooltip], so it's painted
+    /// above windows and popups. Use e.g. [Order::Middle] to let windows cover it.
+    pub floating_item_layer: LayerId,
+    /// If true, the handles can be focused with tab. The focused item can be picked up with
+    /// space or enter, moved with the arrow keys, dropped with enter or space and put back with
+    /// the [DragDropConfig::cancel_key].
+    /// The focused item can also be cut with ctrl + x (cmd + x on mac) and pasted in front of
+    /// another focused item with ctrl + v, see [crate::ItemState::cut].
+    /// This is off by default, since a focused handle consumes these keys.
+    pub keyboard_reordering: bool,
+    /// Pressing this key while dragging puts the item back where it was picked up.
+    /// If None, drags can't be cancelled with the keyboard.
+    pub cancel_key: Option<Key>,
+    /// If true, pressing another pointer button while dragging (e.g. a right click during a
+    /// left button drag) puts the item back where it was picked up.
+    /// When dragging with the keyboard, any click cancels the drag.
+    pub cancel_on_other_button: bool,
+    /// The pointer button that starts a drag, e.g. [PointerButton::Middle] to reorder with the middle button
+    /// while the primary button is used for selecting items.
+    pub drag_button: PointerButton,
+    /// Scrolls the enclosing ScrollArea when the dragged item is held close to its edge.
+    /// If None, the list is not scrolled automatically while dragging with the pointer.
+    pub auto_scroll: Option<AutoScroll>,
+    /// If true, clicking a handle picks the item up. It then follows the pointer without a button
+    /// being held and is dropped with the next click. Pressing and dragging still works as usual.
+    /// This is easier for users that have trouble holding a button down and for long drags on a trackpad.
+    /// Note that clicking a button within the handle also picks the item up while this is enabled.
+    pub sticky_drag: bool,
+    /// If set, a drag is only started when the handle is tapped and then pressed and held again
+    /// within this duration. This is an alternative to a long press for lists in a ScrollArea,
+    /// where a long press usually opens a context menu. Other presses are cancelled with
+    /// [CancellationReason::NotDoubleTapped], so they can scroll the ScrollArea.
+    /// The hold after the second tap still has to last [DragDropConfig::drag_delay].
+    pub double_tap_and_hold: Option<Duration>,
+    /// If true, right clicking a handle opens a context menu with entries to move the item up,
+    /// down, to the top or to the bottom. The moves are reported like keyboard drags, with the
+    /// same [DragUpdate]s and events.
+    pub context_menu: bool,
+    /// If true, a ring around the pointer fills up while the pointer is held on an item waiting
+    /// for the [DragDropConfig::drag_delay], so users learn how long they have to hold.
+    /// See [DragDropResponse::hold_progress] to draw your own indicator instead.
+    pub hold_indicator: bool,
+    /// If set, the dragged item is pulled toward the slot it would be dropped in, instead of
+    /// following the pointer 1:1. The value is the strength, from 0 (follows the pointer) to 1
+    /// (sits in the slot). Keyboard drags always move the item to the slot.
+    pub slot_snapping: Option<f32>,
+    /// If true, widgets outside of the dragged item don't react to the pointer while an item is
+    /// dragged, so labels and buttons under the pointer don't show hover effects and text can't be
+    /// selected. Text inputs lose focus when the drag starts, which also clears their selection.
+    /// The blocked layers include ScrollAreas, so they don't see the mouse wheel themselves. The
+    /// list still forwards the wheel to the ScrollArea it's in while the pointer is over the list,
+    /// other ScrollAreas don't scroll during the drag. [DragDropConfig::auto_scroll] still works.
+    ///
+    /// Only the layers below [DragDropConfig::floating_item_layer] are blocked. Widgets that accept
+    /// drops by checking if they're hovered, like [egui::Ui::dnd_drop_zone], don't see the pointer
+    /// either, so they can't receive the [crate::DndPayload] of the dragged item. Other lists
+    /// using [crate::Dnd::accept_payload] still can.
+    pub block_interaction: bool,
+    /// What happens to a drag when the window loses focus, e.g. because the user switched to
+    /// another application. The release of the pointer might never arrive while unfocused.
+    /// The default is [FocusLossBehavior::Hold].
+    pub focus_loss: FocusLossBehavior,
+    /// What happens to a drag with the pointer when the pointer leaves the window.
+    /// [DndEvent::DraggedOutOfWindow] is reported either way.
+    pub pointer_left_window: FocusLossBehavior,
+}
+
+/// How the enclosing ScrollArea is scrolled while an item is held close to its edge,
+/// see [DragDropConfig::auto_scroll]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AutoScroll {
+    /// How close to the edge of the visible area the pointer has to be for scrolling to start, in points.
+    pub margin: f32,
+    /// The scroll speed in points per second when the pointer is at or beyond the edge of the visible area.
+    pub speed: f32,
+    /// How the speed increases as the pointer moves from the inner side of the margin towards the edge.
+    /// The speed is `speed * depth.powf(acceleration)`, where depth goes from 0 to 1.
+    /// 1.0 increases the speed linearly, higher values keep it gentle until the pointer is close to the edge.
+    pub acceleration: f32,
+}
+
+impl Default for AutoScroll {
+    fn default() -> Self {
+        Self {
+            margin: 30.0,
+            speed: 600.0,
+            acceleration: 2.0,
+        }
+    }
+}
+
+impl AutoScroll {
+    /// The scroll delta for this frame, if the pointer is within the margin of the `viewport`
+    fn delta(&self, viewport: Rect, pointer_pos: Pos2, dt: f32) -> Vec2 {
+        let margin = self.margin.max(f32::EPSILON);
+        let axis_speed = |pos: f32, min: f32, max: f32| {
+            let depth = |distance: f32| (1.0 - distance / margin).clamp(0.0, 1.0);
+            let speed = |depth: f32| self.speed * depth.powf(self.acceleration);
+            // A positive delta moves the content down / right, scrolling towards the start
+            speed(depth(pos - min)) - speed(depth(max - pos))
+        };
+        Vec2::new(
+            axis_speed(pointer_pos.x, viewport.min.x, viewport.max.x),
+            axis_speed(pointer_pos.y, viewport.min.y, viewport.max.y),
+        ) * dt
+    }
+}
+
+/// Cursor icons shown during drag and drop, see [DragDropConfig::cursors]
+/// If a cursor is None, egui_dnd won't change the cursor in that situation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DragCursors {
+    /// Shown while hovering over a drag handle.
+    /// Can also be disabled per handle via [Handle::show_drag_cursor_on_hover].
+    pub hover: Option<CursorIcon>,
+    /// Shown while dragging.
+    pub dragging: Option<CursorIcon>,
+    /// Shown while dragging with the command (ctrl on windows / linux) key held.
+    /// If None, [DragCursors::dragging] is used.
+    pub dragging_with_command: Option<CursorIcon>,
+    /// Shown while dragging when the item can't be dropped at the current position,
+    /// e.g. when the pointer is outside of the list.
+    /// If None, [DragCursors::dragging] is used.
+    pub invalid_target: Option<CursorIcon>,
+}
+
+impl Default for DragCursors {
+    fn default() -> Self {
+        Self {
+            hover: Some(CursorIcon::Grab),
+            dragging: Some(CursorIcon::Grabbing),
+            dragging_with_command: None,
+            invalid_target: None,
+        }
+    }
+}
+
+impl DragCursors {
+    fn dragging_cursor(&self, ui: &Ui, valid_target: bool) -> Option<CursorIcon> {
+        let command = ui.input(|i| i.modifiers.command);
+        let cursor = if !valid_target {
+            self.invalid_target
+        } else if command {
+            self.dragging_with_command
+        } else {
+            None
+        };
+        cursor.or(self.dragging)
+    }
+}
+
+/// How the position the dragged item would be dropped at is highlighted, see [DragDropConfig::drop_target_highlight]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DropTargetHighlight {
+    /// The drop target is not highlighted
+    #[default]
+    None,
+    /// Tint the drop target with the given color.
+    /// If None, a faded version of the selection color from the visuals is used.
+    Fill(Option<Color32>),
+    /// Draw an outline around the drop target with the given stroke.
+    /// If None, the selection stroke from the visuals is used.
+    Outline(Option<Stroke>),
+}
+
+impl DropTargetHighlight {
+    fn paint(&self, ui: &Ui, rect: Rect) {
+        let visuals = ui.visuals();
+        let rounding = visuals.widgets.noninteractive.rounding;
+        match *self {
+            DropTargetHighlight::None => {}
+            DropTargetHighlight::Fill(color) => {
+                let color = color.unwrap_or_else(|| visuals.selection.bg_fill.gamma_multiply(0.4));
+                ui.painter().rect_filled(rect, rounding, color);
+            }
+            DropTargetHighlight::Outline(stroke) => {
+                let stroke = stroke.unwrap_or(visuals.selection.stroke);
+                ui.painter().rect_stroke(rect, rounding, stroke);
+            }
+        }
+    }
+}
+
+/// What happens to a drag when the window loses focus or the pointer leaves the window,
+/// see [DragDropConfig::focus_loss] and [DragDropConfig::pointer_left_window]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FocusLossBehavior {
+    /// The item is put back where it was picked up, with [CancellationReason::WindowUnfocused]
+    Cancel,
+    /// The item stays picked up until the focus or the pointer returns
+    Hold,
+    /// The item is dropped at the last slot it was hovering, if it may be dropped there
+    Drop,
 }
 
 impl Default for DragDropConfig {
@@ -404,7 +1909,30 @@ impl DragDropConfig {
             click_tolerance: 1.0,
             drag_delay: Duration::from_millis(0),
             scroll_tolerance: None,
+            drag_angle: None,
             click_tolerance_timeout: Duration::from_millis(250),
+            shift_animation_duration: None,
+            lock_cross_axis_size: false,
+            animations: true,
+            enter_exit_animations: false,
+            ghost_opacity: None,
+            drop_target_highlight: DropTargetHighlight::None,
+            merge_highlight: DropTargetHighlight::Outline(None),
+            cursors: DragCursors::default(),
+            floating_item_layer: LayerId::new(Order::Tooltip, Id::new("draggable_item")),
+            keyboard_reordering: false,
+            cancel_key: Some(Key::Escape),
+            cancel_on_other_button: false,
+            drag_button: PointerButton::Primary,
+            auto_scroll: Some(AutoScroll::default()),
+            sticky_drag: false,
+            double_tap_and_hold: None,
+            context_menu: false,
+            hold_indicator: false,
+            slot_snapping: None,
+            block_interaction: false,
+            focus_loss: FocusLossBehavior::Hold,
+            pointer_left_window: FocusLossBehavior::Hold,
         }
     }
 
@@ -412,10 +1940,8 @@ impl DragDropConfig {
     /// Has a higher click tolerance than [DragDropConfig::mouse]
     pub fn touch() -> Self {
         Self {
-            scroll_tolerance: None,
             click_tolerance: 3.0,
-            drag_delay: Duration::from_millis(0),
-            click_tolerance_timeout: Duration::from_millis(250),
+            ..Self::mouse()
         }
     }
 
@@ -423,10 +1949,164 @@ impl DragDropConfig {
     pub fn touch_scroll() -> Self {
         Self {
             scroll_tolerance: Some(6.0),
-            click_tolerance: 3.0,
             drag_delay: Duration::from_millis(300),
-            click_tolerance_timeout: Duration::from_millis(250),
+            ..Self::touch()
+        }
+    }
+
+    /// Optimized for desktop usage, the same as [DragDropConfig::mouse]
+    pub fn desktop() -> Self {
+        Self::mouse()
+    }
+
+    /// Optimized for public touch screens like kiosks or info terminals, where taps are often
+    /// inaccurate and there is no keyboard.
+    /// Drags start after a short delay and with a high click tolerance, so sloppy taps don't move
+    /// items, and keyboard reordering is disabled.
+    pub fn kiosk() -> Self {
+        Self {
+            click_tolerance: 10.0,
+            drag_delay: Duration::from_millis(150),
+            scroll_tolerance: Some(10.0),
+            keyboard_reordering: false,
+            cancel_key: None,
+            ..Self::touch()
+        }
+    }
+
+    /// Optimized for pen / stylus usage
+    /// Starts dragging immediately like [DragDropConfig::mouse], but with a slightly higher click tolerance
+    /// since it's hard to hold a pen completely still
+    pub fn pen() -> Self {
+        Self {
+            click_tolerance: 2.0,
+            ..Self::mouse()
+        }
+    }
+
+    /// Sets the [Order] of the layer the dragged item is painted on, see [DragDropConfig::floating_item_layer]
+    pub fn floating_item_order(mut self, order: Order) -> Self {
+        self.floating_item_layer.order = order;
+        self
+    }
+
+    /// Sets how far the pointer may move ([DragDropConfig::click_tolerance]) and how long it may be held
+    /// ([DragDropConfig::click_tolerance_timeout]) before a press on a handle starts a drag instead of being a click.
+    /// Set a different threshold for each input device with [crate::Dnd::with_mouse_config],
+    /// [crate::Dnd::with_touch_config] and [crate::Dnd::with_pen_config].
+    ///
+    /// ```rust
+    /// use egui_dnd::DragDropConfig;
+    /// use std::time::Duration;
+    ///
+    /// let mouse = DragDropConfig::mouse().click_threshold(2.0, Duration::from_millis(200));
+    /// let touch = DragDropConfig::touch().click_threshold(8.0, Duration::from_millis(400));
+    /// assert_eq!(touch.click_tolerance, 8.0);
+    /// assert_eq!(mouse.click_tolerance_timeout, Duration::from_millis(200));
+    /// ```
+    pub fn click_threshold(mut self, distance: f32, timeout: Duration) -> Self {
+        self.click_tolerance = distance;
+        self.click_tolerance_timeout = timeout;
+        self
+    }
+
+    /// Enable or disable all animations, see [DragDropConfig::animations]
+    pub fn animations(mut self, enabled: bool) -> Self {
+        self.animations = enabled;
+        self
+    }
+
+    /// Checks for combinations of options that probably don't do what you want.
+    /// In debug builds, the warnings for the configs of a [crate::Dnd] are logged when it is first shown.
+    ///
+    /// ```rust
+    /// use egui_dnd::{ConfigWarning, DragDropConfig};
+    /// use std::time::Duration;
+    ///
+    /// assert!(DragDropConfig::kiosk().validate().is_empty());
+    ///
+    /// let config = DragDropConfig {
+    ///     drag_delay: Duration::ZERO,
+    ///     scroll_tolerance: Some(6.0),
+    ///     ..DragDropConfig::touch()
+    /// };
+    /// assert_eq!(config.validate(), vec![ConfigWarning::ScrollToleranceWithoutDelay]);
+    /// ```
+    pub fn validate(&self) -> Vec<ConfigWarning> {
+        let mut warnings = Vec::new();
+        if self.drag_delay.is_zero() {
+            if self.click_tolerance <= 0.0 {
+                warnings.push(ConfigWarning::NoDragThreshold);
+            }
+            if self.scroll_tolerance.is_some() {
+                warnings.push(ConfigWarning::ScrollToleranceWithoutDelay);
+            }
+        }
+        if self.click_tolerance_timeout.is_zero() {
+            warnings.push(ConfigWarning::NoClickTimeout);
         }
+        if self.sticky_drag && self.double_tap_and_hold.is_some() {
+            warnings.push(ConfigWarning::StickyDragWithDoubleTap);
+        }
+        if self
+            .auto_scroll
+            .is_some_and(|auto_scroll| auto_scroll.margin <= 0.0 || auto_scroll.speed <= 0.0)
+        {
+            warnings.push(ConfigWarning::AutoScrollNeverScrolls);
+        }
+        if self.drag_angle.is_some() && self.scroll_tolerance.is_none() {
+            warnings.push(ConfigWarning::DragAngleWithoutScrollTolerance);
+        }
+        warnings
+    }
+}
+
+/// A combination of options that probably doesn't do what you want, see [DragDropConfig::validate]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConfigWarning {
+    /// [DragDropConfig::drag_delay] and [DragDropConfig::click_tolerance] are zero, so any
+    /// movement while pressing a handle starts a drag. A ScrollArea containing the list can't be
+    /// scrolled by dragging, use [DragDropConfig::touch_scroll] for that.
+    NoDragThreshold,
+    /// [DragDropConfig::scroll_tolerance] only applies during the [DragDropConfig::drag_delay],
+    /// which is zero
+    ScrollToleranceWithoutDelay,
+    /// [DragDropConfig::click_tolerance_timeout] is zero, so every press on a handle starts a drag
+    /// and widgets in the handle can't be clicked
+    NoClickTimeout,
+    /// With [DragDropConfig::double_tap_and_hold], presses that weren't preceded by a tap are
+    /// cancelled, so [DragDropConfig::sticky_drag] only works for the second tap
+    StickyDragWithDoubleTap,
+    /// The margin or speed of [DragDropConfig::auto_scroll] is zero, so it never scrolls
+    AutoScrollNeverScrolls,
+    /// [DragDropConfig::drag_angle] is only checked once the pointer moved further than the
+    /// [DragDropConfig::scroll_tolerance], which is None
+    DragAngleWithoutScrollTolerance,
+}
+
+impl std::fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ConfigWarning::NoDragThreshold => {
+                "drag_delay and click_tolerance are zero, dragging a surrounding ScrollArea will move items instead"
+            }
+            ConfigWarning::ScrollToleranceWithoutDelay => {
+                "scroll_tolerance has no effect since drag_delay is zero"
+            }
+            ConfigWarning::NoClickTimeout => {
+                "click_tolerance_timeout is zero, widgets in the handle can't be clicked"
+            }
+            ConfigWarning::StickyDragWithDoubleTap => {
+                "sticky_drag only picks items up on the second tap with double_tap_and_hold"
+            }
+            ConfigWarning::AutoScrollNeverScrolls => {
+                "auto_scroll has a margin or speed of zero and never scrolls"
+            }
+            ConfigWarning::DragAngleWithoutScrollTolerance => {
+                "drag_angle has no effect since scroll_tolerance is None"
+            }
+        })
     }
 }
 
@@ -448,26 +2128,307 @@ impl DragDropUi {
         self
     }
 
-    fn config(&self, ui: &Ui) -> &DragDropConfig {
+    /// Sets the config used when dragging with a pen / stylus
+    /// If None, pens use the touch config like any other touch
+    /// egui can't tell pens from fingers, so the detection is based on pressure: every touch that
+    /// reports a pressure uses this config. Browsers and Android report a pressure for fingers too,
+    /// so only set this if your app runs where only pens do.
+    /// The default is None
+    pub fn with_pen_config(mut self, config: Option<DragDropConfig>) -> Self {
+        self.pen_config = config;
+        self
+    }
+
+    /// Starts dragging the item with the given id the next time it's shown, e.g. from a
+    /// context menu entry or a test. Has no effect if a drag is already in progress.
+    pub fn begin_drag(&mut self, id: Id, mode: DragMode) {
+        self.pending_drag = Some((id, mode));
+    }
+
+    /// Forces the list into the given drag state the next time it's shown, see [DragSnapshot]
+    #[cfg(feature = "test_utils")]
+    pub fn snapshot_drag(&mut self, snapshot: DragSnapshot) {
+        self.pending_snapshot = Some(snapshot);
+    }
+
+    /// Cancels the drag in progress the next time the list is shown, e.g. when the app decides
+    /// the item can't be moved after all. Has no effect if nothing is being dragged.
+    pub fn cancel_drag(&mut self, reason: CancellationReason) {
+        self.pending_cancel = Some(reason);
+    }
+
+    /// The approximate size of the state in egui's memory in bytes, including what it allocated
+    #[cfg(feature = "tracing")]
+    pub(crate) fn approximate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.item_sizes.capacity() * std::mem::size_of::<(Id, Vec2)>()
+            + self.external_rects.0.capacity() * std::mem::size_of::<Rect>()
+    }
+
+    /// Shows the state of the list, see [crate::inspector]
+    pub(crate) fn inspection_ui(&self, ui: &mut Ui) {
+        egui::Grid::new("dnd_state").num_columns(2).show(ui, |ui| {
+            ui.label("Detection state");
+            ui.label(self.detection_state.name());
+            ui.end_row();
+            ui.label("Dragged item");
+            ui.label(
+                self.detection_state
+                    .dragged_item()
+                    .map_or_else(|| "None".to_owned(), |id| id.short_debug_format()),
+            );
+            ui.end_row();
+            ui.label("Swap animation time");
+            ui.label(format!("{:.2} s", self.swap_animation_time));
+            ui.end_row();
+            ui.label("Return animation time");
+            ui.label(format!("{:.2} s", self.return_animation_time));
+            ui.end_row();
+            ui.label("Measured item sizes");
+            ui.label(self.item_sizes.len().to_string());
+            ui.end_row();
+        });
+        ui.collapsing("Drag details", |ui| {
+            ui.monospace(format!("{:#?}", self.detection_state));
+        });
+        ui.collapsing("Mouse config", |ui| {
+            ui.monospace(format!("{:#?}", self.mouse_config));
+        });
+        for (name, config, fallback) in [
+            ("Touch config", &self.touch_config, "Uses the mouse config"),
+            ("Pen config", &self.pen_config, "Uses the touch config"),
+        ] {
+            ui.collapsing(name, |ui| match config {
+                Some(config) => ui.monospace(format!("{config:#?}")),
+                None => ui.label(fallback),
+            });
+        }
+    }
+
+    pub(crate) fn config(&self, ui: &Ui) -> &DragDropConfig {
         if ui.input(|i| i.any_touches()) {
-            self.touch_config.as_ref().unwrap_or(&self.mouse_config)
+            match &self.pen_config {
+                Some(pen_config) if self.pen_active => pen_config,
+                _ => self.touch_config.as_ref().unwrap_or(&self.mouse_config),
+            }
         } else {
             &self.mouse_config
         }
     }
 
+    /// Logs the problems [DragDropConfig::validate] finds in any of the configs
+    #[cfg(debug_assertions)]
+    fn log_config_warnings(&self) {
+        let configs = [
+            ("mouse", Some(&self.mouse_config)),
+            ("touch", self.touch_config.as_ref()),
+            ("pen", self.pen_config.as_ref()),
+        ];
+        for (name, config) in configs {
+            for warning in config.map(DragDropConfig::validate).unwrap_or_default() {
+                log::warn!("egui_dnd {name} config: {warning}");
+            }
+        }
+    }
+
+    /// Expands the collapsed item the dragged item is held onto, see [crate::Dnd::spring_open],
+    /// and collapses the items expanded during the drag again once it leaves the list or is
+    /// cancelled. Items stay expanded if the dragged item is dropped.
+    fn update_springs(
+        &mut self,
+        ui: &Ui,
+        constraints: &DragConstraints,
+        items: &[(usize, Id, Pos2)],
+        items_rect: Rect,
+        response: &mut DragDropResponse,
+    ) {
+        let Some((delay, collapsed)) = &constraints.spring_open else {
+            return;
+        };
+        let dragging =
+            self.detection_state.is_dragging() && !self.detection_state.is_keyboard_dragging();
+        let cancelled = response
+            .events
+            .iter()
+            .any(|event| matches!(event, DndEvent::Cancelled { .. }));
+        let over_list = ui.input(|i| {
+            i.pointer
+                .hover_pos()
+                .is_some_and(|pos| items_rect.contains(pos))
+        });
+        if (dragging && !over_list) || cancelled {
+            for id in self.spring_opened.drain(..) {
+                if let Some((index, _, _)) = items.iter().find(|(_, item_id, _)| *item_id == id) {
+                    response
+                        .events
+                        .push(DndEvent::SpringClosed { id, index: *index });
+                }
+            }
+        }
+        if !dragging {
+            self.spring_hover = None;
+            self.spring_opened.clear();
+            return;
+        }
+
+        let now = ui.input(|i| i.time);
+        let held = response
+            .update
+            .as_ref()
+            .and_then(|update| update.onto)
+            .filter(|index| collapsed(*index))
+            .and_then(|index| items.iter().find(|(idx, _, _)| *idx == index))
+            .filter(|(_, id, _)| !self.spring_opened.contains(id));
+        match (held, self.spring_hover) {
+            (Some((index, id, _)), Some((hover_id, since))) if hover_id == *id => {
+                if now - since >= delay.as_secs_f64() {
+                    self.spring_hover = None;
+                    self.spring_opened.push(*id);
+                    response.events.push(DndEvent::SpringOpened {
+                        id: *id,
+                        index: *index,
+                    });
+                } else {
+                    ui.ctx().request_repaint();
+                }
+            }
+            (Some((_, id, _)), _) => {
+                self.spring_hover = Some((*id, now));
+                ui.ctx().request_repaint();
+            }
+            (None, _) => self.spring_hover = None,
+        }
+    }
+
+    /// Warns about items that share an id, since dragging them behaves unpredictably.
+    /// Each id is logged once, and marked on screen if egui's `warn_on_id_clash` option is set.
+    #[cfg(debug_assertions)]
+    fn report_duplicate_ids(&mut self, ui: &Ui, duplicates: Vec<(Id, Pos2)>) -> Vec<Id> {
+        let show_warning = ui.ctx().options(|options| options.warn_on_id_clash);
+        duplicates
+            .into_iter()
+            .map(|(id, pos)| {
+                if self.reported_duplicate_ids.insert(id) {
+                    log::warn!(
+                        "egui_dnd: multiple items have the id {id:?}, every item needs a unique id"
+                    );
+                }
+                if show_warning {
+                    ui.ctx()
+                        .debug_painter()
+                        .error(pos, format!("Duplicate dnd item id {id:?}"));
+                }
+                id
+            })
+            .collect()
+    }
+
+    pub(crate) fn enter_exit_animations_enabled(&self, ui: &Ui) -> bool {
+        self.config(ui).enter_exit_animations
+    }
+
+    /// The animation time in seconds used when items shift to make room for the dragged item
+    pub(crate) fn shift_animation_time(&self, ui: &Ui) -> f32 {
+        let config = self.config(ui);
+        if !config.animations || self.layout_changed {
+            return 0.0;
+        }
+        config
+            .shift_animation_duration
+            .map_or(self.swap_animation_time, |duration| duration.as_secs_f32())
+    }
+
+    /// How much of the [DragDropConfig::drag_delay] has passed for the item with the given id,
+    /// if the pointer is held on it
+    pub(crate) fn hold_progress(&self, ui: &Ui, id: Id) -> Option<f32> {
+        let DragDetectionState::PressedWaitingForDelay { pressed_at } = self.detection_state else {
+            return None;
+        };
+        if self.pressed_item != Some(id) {
+            return None;
+        }
+        let delay = self.config(ui).drag_delay.as_secs_f32();
+        let elapsed = pressed_at.elapsed().unwrap_or_default().as_secs_f32();
+        Some(if delay > 0.0 {
+            (elapsed / delay).min(1.0)
+        } else {
+            1.0
+        })
+    }
+
+    /// The animation time in seconds used when the dragged item moves back into place after being dropped
+    pub(crate) fn return_animation_time(&self, ui: &Ui) -> f32 {
+        if self.config(ui).animations {
+            self.return_animation_time
+        } else {
+            0.0
+        }
+    }
+
     /// Draw the items and handle drag & drop stuff
     pub fn ui(
         &mut self,
         ui: &mut Ui,
         callback: impl FnOnce(&mut Ui, &mut ItemIterator),
+    ) -> DragDropResponse {
+        self.ui_with_constraints(ui, &DragConstraints::default(), callback)
+    }
+
+    pub(crate) fn ui_with_constraints(
+        &mut self,
+        ui: &mut Ui,
+        constraints: &DragConstraints,
+        callback: impl FnOnce(&mut Ui, &mut ItemIterator),
     ) -> DragDropResponse {
         // During the first frame, we check if the pointer is actually over any of the item handles and cancel the drag if it isn't
         let mut first_frame = false;
+        let was_dragging = self.detection_state.is_dragging();
+        ui.input(|i| {
+            for event in &i.events {
+                if let Event::Touch { force, .. } = event {
+                    self.pen_active = force.is_some();
+                }
+            }
+        });
+        #[cfg(debug_assertions)]
+        if !self.config_validated {
+            self.config_validated = true;
+            self.log_config_warnings();
+        }
         let config = self.config(ui).clone();
 
+        // If the window or a panel is resized during a drag, the items jump to their new positions
+        // and are measured again, so the gap and the dragged item don't lag behind
+        let layout_size = (ui.max_rect().size(), ui.ctx().screen_rect());
+        self.layout_changed = self.detection_state.is_dragging()
+            && self
+                .layout_size
+                .is_some_and(|last_size| last_size != layout_size);
+        self.layout_size = Some(layout_size);
+
+        // The stored positions are in points, which are stale if the list moved or the zoom or
+        // the monitor's dpi changed, so they are reprojected relative to the list
+        let list_anchor = (ui.max_rect().min, ui.ctx().pixels_per_point());
+        if let Some((last_origin, last_pixels_per_point)) = self.list_anchor {
+            if self.detection_state.is_dragging() && self.list_anchor != Some(list_anchor) {
+                self.detection_state.reproject(
+                    list_anchor.0 - last_origin,
+                    last_pixels_per_point / list_anchor.1,
+                );
+                self.layout_changed |= last_pixels_per_point != list_anchor.1;
+            }
+        }
+        self.list_anchor = Some(list_anchor);
+
+        if self.layout_changed {
+            self.item_sizes.clear();
+            // The gap and the position of a keyboard drag are only updated at the end of the frame
+            ui.ctx().request_repaint();
+        }
+
+        let main_axis_vertical = ui.layout().main_dir().is_vertical();
         ui.input(|i| {
-            if i.pointer.any_down() {
+            if i.pointer.button_down(config.drag_button) {
                 if matches!(self.detection_state, DragDetectionState::None)
                     || matches!(
                         self.detection_state,
@@ -478,13 +2439,32 @@ impl DragDropUi {
                     self.detection_state = DragDetectionState::PressedWaitingForDelay {
                         pressed_at: SystemTime::now(),
                     };
+
+                    if let Some(timeout) = config.double_tap_and_hold {
+                        let press_origin = i.pointer.press_origin().unwrap_or_default();
+                        let is_double_tap = self.last_tap.take().is_some_and(|(tapped_at, pos)| {
+                            tapped_at.elapsed().unwrap_or_default() <= timeout
+                                && pos.distance(press_origin) <= DOUBLE_TAP_DISTANCE
+                        });
+                        if !is_double_tap {
+                            self.detection_state =
+                                DragDetectionState::Cancelled(CancellationReason::NotDoubleTapped);
+                        }
+                    }
                 }
 
-                let drag_distance = (i.pointer.hover_pos().unwrap_or_default()
-                    - i.pointer.press_origin().unwrap_or_default())
-                .length();
+                let drag_delta = i.pointer.hover_pos().unwrap_or_default()
+                    - i.pointer.press_origin().unwrap_or_default();
                 let is_below_scroll_threshold =
-                    drag_distance < config.scroll_tolerance.unwrap_or(f32::INFINITY);
+                    drag_delta.length() < config.scroll_tolerance.unwrap_or(f32::INFINITY);
+                // Past the scroll tolerance, swipes across the list start the drag instead of scrolling
+                let scrolled_state = if config.drag_angle.is_some_and(|angle| {
+                    angle_to_main_axis(drag_delta, main_axis_vertical) >= angle
+                }) {
+                    DragDetectionState::CouldBeValidDrag
+                } else {
+                    DragDetectionState::Cancelled(CancellationReason::ScrollToleranceExceeded)
+                };
 
                 if let DragDetectionState::PressedWaitingForDelay { pressed_at } =
                     self.detection_state
@@ -494,14 +2474,10 @@ impl DragDropUi {
                             self.detection_state =
                                 DragDetectionState::WaitingForClickThreshold { pressed_at };
                         } else {
-                            self.detection_state = DragDetectionState::Cancelled(
-                                "Drag distance exceeded scroll threshold",
-                            );
+                            self.detection_state = scrolled_state;
                         }
                     } else if !is_below_scroll_threshold {
-                        self.detection_state = DragDetectionState::Cancelled(
-                            "Drag distance exceeded scroll threshold",
-                        );
+                        self.detection_state = scrolled_state;
                     }
                 }
                 if let DragDetectionState::WaitingForClickThreshold { pressed_at } =
@@ -514,9 +2490,124 @@ impl DragDropUi {
             }
         });
 
-        let pointer_pos = ui
-            .input(|i| i.pointer.hover_pos())
-            .or_else(|| self.detection_state.last_pointer_pos());
+        if config.double_tap_and_hold.is_some() {
+            ui.input(|i| {
+                if let (true, Some(pos)) = (
+                    i.pointer.button_clicked(config.drag_button),
+                    i.pointer.interact_pos(),
+                ) {
+                    self.last_tap = Some((SystemTime::now(), pos));
+                }
+            });
+        }
+
+        if let DragDetectionState::Dragging {
+            input: DragInput::StickyPointer { pressed },
+            ..
+        } = &mut self.detection_state
+        {
+            *pressed |= ui.input(|i| i.pointer.button_pressed(config.drag_button));
+        }
+
+        let mut cancel = None;
+        // Set if the drag is dropped because of [DragDropConfig::focus_loss] or
+        // [DragDropConfig::pointer_left_window]
+        let mut drop_interrupted = false;
+        let pending_cancel = self.pending_cancel.take();
+        if self.detection_state.is_dragging() {
+            let is_keyboard_drag = self.detection_state.is_keyboard_dragging();
+            ui.input_mut(|i| {
+                if config
+                    .cancel_key
+                    .is_some_and(|key| i.consume_key(Modifiers::NONE, key))
+                {
+                    cancel = Some(CancellationReason::CancelKey);
+                }
+                // When dragging with the keyboard, any click cancels the drag
+                let other_button_pressed = i.events.iter().any(|event| {
+                    matches!(event, Event::PointerButton { pressed: true, button, .. }
+                        if is_keyboard_drag || *button != config.drag_button)
+                });
+                if config.cancel_on_other_button && other_button_pressed {
+                    cancel = Some(CancellationReason::OtherButtonPressed);
+                }
+            });
+            if constraints.disabled {
+                cancel = Some(CancellationReason::Disabled);
+            }
+            let pointer_left_window = !is_keyboard_drag
+                && ui.input(|i| {
+                    i.pointer
+                        .hover_pos()
+                        .map_or(true, |pos| !i.screen_rect.contains(pos))
+                });
+            for (interrupted, behavior) in [
+                (!ui.input(|i| i.focused), config.focus_loss),
+                (pointer_left_window, config.pointer_left_window),
+            ] {
+                match behavior {
+                    FocusLossBehavior::Cancel if interrupted => {
+                        cancel = Some(CancellationReason::WindowUnfocused);
+                    }
+                    FocusLossBehavior::Drop if interrupted => drop_interrupted = true,
+                    _ => {}
+                }
+            }
+            if pending_cancel.is_some() {
+                cancel = pending_cancel;
+            }
+        }
+
+        // Move the item with the arrow keys if it was picked up with the keyboard
+        let mut keyboard_drop = false;
+        let mut keyboard_forward = true;
+        // The arrow keys move the item in the direction they point, also in reversed layouts
+        let (backward_keys, forward_keys) = match ui.layout().main_dir() {
+            Direction::BottomUp => (
+                [Key::ArrowDown, Key::ArrowLeft],
+                [Key::ArrowUp, Key::ArrowRight],
+            ),
+            Direction::RightToLeft => (
+                [Key::ArrowUp, Key::ArrowRight],
+                [Key::ArrowDown, Key::ArrowLeft],
+            ),
+            Direction::TopDown | Direction::LeftToRight => (
+                [Key::ArrowUp, Key::ArrowLeft],
+                [Key::ArrowDown, Key::ArrowRight],
+            ),
+        };
+        if let DragDetectionState::Dragging {
+            input: DragInput::Keyboard(keyboard),
+            ..
+        } = &mut self.detection_state
+        {
+            ui.input_mut(|i| {
+                if backward_keys
+                    .iter()
+                    .any(|key| i.consume_key(Modifiers::NONE, *key))
+                {
+                    keyboard.target = keyboard.target.saturating_sub(1);
+                    keyboard_forward = false;
+                }
+                if forward_keys
+                    .iter()
+                    .any(|key| i.consume_key(Modifiers::NONE, *key))
+                {
+                    keyboard.target += 1;
+                }
+                keyboard_drop = keyboard.drop_requested
+                    || i.consume_key(Modifiers::NONE, Key::Enter)
+                    || i.consume_key(Modifiers::NONE, Key::Space);
+            });
+        }
+
+        // When dragging with the keyboard, the last pointer pos is the position of the gap
+        let pointer_pos = if self.detection_state.is_keyboard_dragging() {
+            self.detection_state.last_pointer_pos()
+        } else {
+            ui.input(|i| i.pointer.hover_pos())
+                .or_else(|| self.detection_state.last_pointer_pos())
+        };
 
         let dragged_item_rect = if let DragDetectionState::Dragging {
             offset,
@@ -532,9 +2623,67 @@ impl DragDropUi {
             None
         };
 
-        let mut item_iter = ItemIterator::new(self, dragged_item_rect, *ui.layout());
+        // Don't animate items appearing or disappearing while we are dragging
+        let animate_transitions = config.animations && !self.detection_state.is_dragging();
+        self.transitions.begin_frame(
+            ui,
+            config.enter_exit_animations,
+            animate_transitions,
+            self.shift_animation_time(ui),
+        );
+
+        let floating_item = self.detection_state.floating_item();
+
+        // Something dragged in from outside of the list, like a payload of another widget or files
+        let accept_payload = constraints.accept_payload;
+        let accepts_external = accept_payload.is_some() || constraints.accept_files;
+        let files_hovered = constraints.accept_files
+            && ui.input(|i| !i.raw.hovered_files.is_empty() || !i.raw.dropped_files.is_empty());
+        let external_hover = if !self.detection_state.is_dragging()
+            && (files_hovered
+                || accept_payload.is_some_and(|accept| accept(ui.ctx(), false).is_some()))
+        {
+            ui.input(|i| i.pointer.hover_pos().or(i.pointer.latest_pos()))
+                .and_then(|pos| {
+                    let (rects, area) = &self.external_rects;
+                    external_hover(ui.layout(), rects, *area, pos)
+                })
+        } else {
+            None
+        };
+
+        self.floating_shapes = None;
+        // Outside of a drag, the sizes are measured again for the items shown this frame, so the
+        // sizes of removed items don't pile up
+        if !self.detection_state.is_dragging() {
+            self.item_sizes.clear();
+        }
+        let mut item_iter = ItemIterator::new(
+            self,
+            constraints,
+            dragged_item_rect,
+            external_hover,
+            *ui.layout(),
+        );
+        #[cfg(feature = "accesskit")]
+        {
+            let list_id = ui.id().with("dnd_list");
+            accessibility::list_node(ui, list_id);
+            let ctx = ui.ctx().clone();
+            ctx.with_accessibility_parent(list_id, || callback(ui, &mut item_iter));
+        }
+        #[cfg(not(feature = "accesskit"))]
         callback(ui, &mut item_iter);
 
+        if let Some(hover) =
+            external_hover.filter(|hover| hover.slot >= item_iter.non_dragged_items)
+        {
+            item_iter.add_external_gap(ui, hover.size);
+        }
+
+        #[cfg(debug_assertions)]
+        let duplicate_ids = std::mem::take(&mut item_iter.duplicate_ids);
+
         let ItemIterator {
             source_item,
             hovering_over_any_handle,
@@ -542,8 +2691,109 @@ impl DragDropUi {
             mark_next_as_closest_item,
             last_item,
             hovering_last_item,
+            mut gap_rect,
+            items_rect,
+            non_dragged_items,
+            items,
+            item_responses,
+            item_rects,
+            onto_item,
+            onto_merges,
             ..
         } = item_iter;
+        // The items are shown in list order, the shapes of items with a z index are sorted after
+        sort_shapes_by_z_index(ui, &std::mem::take(&mut self.paint_order));
+        self.external_rects = if accepts_external {
+            (item_rects, items_rect)
+        } else {
+            (Vec::new(), Rect::NOTHING)
+        };
+
+        if config.block_interaction && self.detection_state.is_dragging() {
+            if !was_dragging {
+                ui.memory_mut(|mem| mem.stop_text_input());
+            }
+            block_interaction(ui, config.floating_item_layer);
+        }
+
+        // The dragged item wasn't shown, so it's not in the list anymore. In a filtered view it
+        // may only be hidden by the filter, so the drag continues until the item is dropped.
+        let dragged_item_hidden = self.detection_state.is_dragging() && source_item.is_none();
+        // A sticky drag is dropped when the next click is released, not the one that picked it up
+        let waiting_for_click = matches!(
+            self.detection_state,
+            DragDetectionState::Dragging {
+                input: DragInput::StickyPointer { pressed: false },
+                ..
+            }
+        );
+        let released_hidden_item = keyboard_drop
+            || drop_interrupted
+            || (ui.input(|i| i.pointer.button_released(config.drag_button)) && !waiting_for_click);
+        if dragged_item_hidden && (!constraints.filtered || released_hidden_item) {
+            cancel = Some(CancellationReason::ListChanged);
+        }
+
+        if let (true, None, Some(dragged_item_rect)) = (
+            self.detection_state.is_keyboard_dragging(),
+            gap_rect,
+            dragged_item_rect,
+        ) {
+            // The item was moved behind the last item
+            gap_rect = Some(
+                ui.allocate_exact_size(dragged_item_rect.size(), Sense::hover())
+                    .0,
+            );
+        }
+
+        // The handle of the floating item doesn't take the focus, so we keep it here
+        if let (Some(id), true) = (floating_item, config.keyboard_reordering) {
+            let focus_id = keyboard_focus_id(id);
+            ui.interact(
+                gap_rect.or(dragged_item_rect).unwrap_or(Rect::NOTHING),
+                focus_id,
+                Sense::focusable_noninteractive(),
+            );
+            if self.detection_state.is_keyboard_dragging() {
+                lock_keyboard_focus(ui, focus_id);
+            }
+        }
+
+        if self.detection_state.is_dragging() && !self.detection_state.is_keyboard_dragging() {
+            let valid_target = pointer_pos.is_some_and(|pos| items_rect.contains(pos));
+            if let Some(cursor) = config.cursors.dragging_cursor(ui, valid_target) {
+                ui.output_mut(|o| o.cursor_icon = cursor);
+            }
+        }
+
+        if let Some(gap_rect) = gap_rect {
+            config.drop_target_highlight.paint(ui, gap_rect);
+        }
+        // Outside of the list, the item follows the pointer again
+        self.snap_position = gap_rect
+            .map(|gap_rect| gap_rect.min)
+            .filter(|_| config.slot_snapping.is_some());
+        if let (Some((_, id)), true) = (onto_item, onto_merges) {
+            if let Some(item) = item_responses.get(&id) {
+                config.merge_highlight.paint(ui, item.rect);
+            }
+        }
+        if let (Some(opacity), Some(gap_rect), Some(dragged_item_rect), Some(shapes)) = (
+            config.ghost_opacity,
+            gap_rect,
+            dragged_item_rect,
+            self.floating_shapes.clone(),
+        ) {
+            paint_ghost(
+                ui,
+                config.floating_item_layer,
+                shapes,
+                gap_rect.min - dragged_item_rect.min,
+                opacity,
+            );
+        }
+
+        self.transitions.end_frame(ui, config.enter_exit_animations);
 
         // This is only some if we're hoving over the last item
         let hovering_last_item = if mark_next_as_closest_item.is_some() {
@@ -555,18 +2805,51 @@ impl DragDropUi {
             false
         };
 
-        let pointer_released = ui.input(|i| i.pointer.any_released());
-        let should_update = closest_item.map(|i| i.1.is_some()).unwrap_or(false);
+        let pointer_released = ui.input(|i| i.pointer.button_released(config.drag_button));
+        let modifiers = ui.input(|i| i.modifiers);
+        let should_update = closest_item.map(|i| i.1.is_some()).unwrap_or(false)
+            || self.detection_state.is_keyboard_dragging();
 
         // The cursor is not hovering over any item, so cancel
         if first_frame && !hovering_over_any_handle {
-            self.detection_state =
-                DragDetectionState::Cancelled("Cursor not hovering over any item handle");
+            self.detection_state = DragDetectionState::Cancelled(CancellationReason::NotOverHandle);
         }
 
         let drag_phase_changed_this_frame = false;
 
-        let hovering_item = closest_item.and_then(|i| i.1);
+        let mut hovering_item = closest_item.and_then(|i| i.1);
+        let mut hovering_last_item = hovering_last_item;
+        // While the pointer is over an item that can be dropped onto, the gap stays where it is
+        if onto_item.is_some() {
+            hovering_item = None;
+        }
+
+        // If the item can't be dropped where it's hovering, snap the gap to the closest allowed slot
+        let mut drop_allowed = true;
+        if let (false, Some((idx, _, _)), Some(source_idx)) = (
+            self.detection_state.is_keyboard_dragging(),
+            hovering_item,
+            source_item
+                .map(|(idx, _)| idx)
+                .or(self.detection_state.source_idx()),
+        ) {
+            let count = items.len();
+            let target = target_index(source_idx, idx + usize::from(hovering_last_item));
+            match constraints.nearest_allowed_target(source_idx, target, count, target < source_idx)
+            {
+                Some(allowed) if allowed != target => {
+                    let to = insertion_index(source_idx, allowed);
+                    hovering_last_item = to >= count;
+                    hovering_item = items.get(to.min(count - 1)).copied();
+                }
+                Some(_) => {}
+                None => {
+                    drop_allowed = false;
+                    hovering_last_item = false;
+                    hovering_item = items.get(source_idx).copied();
+                }
+            }
+        }
 
         if let DragDetectionState::Dragging {
             closest_item: closest_out,
@@ -590,15 +2873,113 @@ impl DragDropUi {
             }
         }
 
+        // Items may be added or removed during the drag, e.g. by a background sync. The dragged
+        // item is found by its id, so we only need to keep the indices within the list.
+        if let (
+            Some(_),
+            DragDetectionState::Dragging {
+                source_idx,
+                start_idx,
+                start_next,
+                hovering_idx,
+                ..
+            },
+        ) = (source_item, &mut self.detection_state)
+        {
+            let count = last_item.map_or(0, |(idx, _, _)| idx + 1);
+            let index_of = |id: Id| {
+                items
+                    .iter()
+                    .find(|(_, item_id, _)| *item_id == id)
+                    .map(|(idx, _, _)| *idx)
+            };
+            // Until the end of the first frame, we don't know which item was behind the dragged item
+            if was_dragging {
+                if let Some(to) = start_next.map_or(Some(count), index_of) {
+                    *start_idx = target_index(*source_idx, to);
+                }
+            }
+            *start_idx = (*start_idx).min(count.saturating_sub(1));
+            // If the item is at its start index, the item behind it is the next one
+            let next_idx =
+                insertion_index(*source_idx, *start_idx) + usize::from(*start_idx == *source_idx);
+            *start_next = id_at(&items, next_idx);
+            *hovering_idx = (*hovering_idx).min(count.saturating_sub(1));
+        }
+
+        if let DragDetectionState::Dragging {
+            source_idx,
+            hovering_idx,
+            last_pointer_pos,
+            input: DragInput::Keyboard(keyboard),
+            ..
+        } = &mut self.detection_state
+        {
+            keyboard.target = keyboard.target.min(non_dragged_items);
+            if let Some(target) = constraints.nearest_allowed_target(
+                *source_idx,
+                keyboard.target,
+                non_dragged_items + 1,
+                keyboard_forward,
+            ) {
+                keyboard.target = target;
+            } else {
+                keyboard.target = *source_idx;
+                drop_allowed = false;
+            }
+            // The target doesn't count the dragged item, the index does
+            *hovering_idx = if keyboard.target < *source_idx {
+                keyboard.target
+            } else {
+                keyboard.target + 1
+            };
+            if let Some(gap_rect) = gap_rect {
+                *last_pointer_pos = gap_rect.min;
+            }
+        }
+
         if self.detection_state.is_dragging() {
-            if let Some(pointer_pos) = pointer_pos {
-                // If we are in a ScrollArea, allow for scrolling while dragging
-                ui.scroll_to_rect(Rect::from_center_size(pointer_pos, Vec2::splat(50.0)), None);
+            // If we are in a ScrollArea, allow for scrolling while dragging
+            if self.detection_state.is_keyboard_dragging() {
+                if let Some(gap_rect) = gap_rect {
+                    ui.scroll_to_rect(gap_rect, None);
+                }
+            } else if let (Some(auto_scroll), Some(pointer_pos)) = (config.auto_scroll, pointer_pos)
+            {
+                let dt = ui.input(|i| i.stable_dt).min(0.1);
+                let delta = auto_scroll.delta(ui.clip_rect(), pointer_pos, dt);
+                if delta != Vec2::ZERO {
+                    ui.scroll_with_delta(delta);
+                    ui.ctx().request_repaint();
+                }
+            }
+
+            // The floating item covers the ScrollArea, so it won't see the scroll wheel.
+            // We forward the scroll delta instead and take it, so it's not applied twice.
+            // The content of a ScrollArea extends past its clip rect along the scrolled axis.
+            let in_scroll_area = !ui.clip_rect().contains_rect(ui.max_rect());
+            let pointer_over_list = ui
+                .input(|i| i.pointer.hover_pos())
+                .is_some_and(|pos| ui.clip_rect().contains(pos));
+            if in_scroll_area && pointer_over_list {
+                let scroll_delta = ui.input_mut(|i| std::mem::take(&mut i.smooth_scroll_delta));
+                if scroll_delta != Vec2::ZERO {
+                    ui.scroll_with_delta(scroll_delta);
+                    // Update the drop target once the content has moved
+                    ui.ctx().request_repaint();
+                }
             }
         }
 
+        let pointer_pos = if self.detection_state.is_keyboard_dragging() {
+            None
+        } else {
+            ui.input(|i| i.pointer.latest_pos())
+        };
+
         let mut response = if !drag_phase_changed_this_frame {
             if let DragDetectionState::Dragging {
+                id,
                 source_idx,
                 hovering_idx,
                 hovering_last_item,
@@ -607,17 +2988,31 @@ impl DragDropUi {
             {
                 DragDropResponse {
                     finished: false,
-                    update: Some(DragUpdate {
-                        from: source_idx,
-                        to: if hovering_last_item {
+                    update: Some(DragUpdate::new(
+                        id,
+                        source_idx,
+                        if hovering_last_item {
                             hovering_idx + 1
                         } else {
                             hovering_idx
                         },
-                    }),
+                        modifiers,
+                        pointer_pos,
+                        &items,
+                    )),
                     state: self.detection_state.clone(),
                     cancellation_reason: None,
                     has_changed: should_update,
+                    modifiers,
+                    events: Vec::new(),
+                    duplicate_ids: Vec::new(),
+                    item_responses: HashMap::new(),
+                    shown_items: Vec::new(),
+                    hold_progress: None,
+                    external_hover_index: None,
+                    dropped_payload: None,
+                    external_drop: None,
+                    history: None,
                 }
             } else {
                 DragDropResponse {
@@ -626,6 +3021,16 @@ impl DragDropUi {
                     state: self.detection_state.clone(),
                     cancellation_reason: None,
                     has_changed: false,
+                    modifiers,
+                    events: Vec::new(),
+                    duplicate_ids: Vec::new(),
+                    item_responses: HashMap::new(),
+                    shown_items: Vec::new(),
+                    hold_progress: None,
+                    external_hover_index: None,
+                    dropped_payload: None,
+                    external_drop: None,
+                    history: None,
                 }
             }
         } else {
@@ -635,12 +3040,115 @@ impl DragDropUi {
                 state: self.detection_state.clone(),
                 cancellation_reason: None,
                 has_changed: false,
+                modifiers,
+                events: Vec::new(),
+                duplicate_ids: Vec::new(),
+                item_responses: HashMap::new(),
+                shown_items: Vec::new(),
+                hold_progress: None,
+                external_hover_index: None,
+                dropped_payload: None,
+                external_drop: None,
+                history: None,
+            }
+        };
+
+        if let Some(update) = &mut response.update {
+            update.onto = onto_item.map(|(idx, _)| idx);
+            update.merge = onto_item.is_some() && onto_merges;
+        }
+
+        // There is nothing to update while the dragged item is hidden by a filter
+        if dragged_item_hidden {
+            response.update = None;
+            response.has_changed = false;
+        }
+
+        if let (false, Some(id), Some(update)) = (
+            was_dragging,
+            self.detection_state.dragged_item(),
+            &response.update,
+        ) {
+            response.events.push(DndEvent::DragStarted {
+                id,
+                index: update.from,
+            });
+        }
+        if let (Some(update), DragDetectionState::Dragging { id, slot, .. }) =
+            (&response.update, &mut self.detection_state)
+        {
+            // The index the item ends up at, which doesn't change when the source list is updated during the drag
+            let new_slot = update.target_index();
+            if new_slot != *slot {
+                response.events.push(DndEvent::TargetChanged {
+                    id: *id,
+                    from: *slot,
+                    to: new_slot,
+                });
+                *slot = new_slot;
             }
+        }
+
+        let pointer_outside = ui.input(|i| {
+            i.pointer
+                .hover_pos()
+                .map_or(true, |pos| !i.screen_rect.contains(pos))
+        });
+        if let DragDetectionState::Dragging {
+            id,
+            last_pointer_pos,
+            input: DragInput::Pointer | DragInput::StickyPointer { .. },
+            ..
+        } = &self.detection_state
+        {
+            if pointer_outside && !self.dragged_out_of_window {
+                response.events.push(DndEvent::DraggedOutOfWindow {
+                    id: *id,
+                    pointer_pos: *last_pointer_pos,
+                });
+            }
+            self.dragged_out_of_window = pointer_outside;
+        } else {
+            self.dragged_out_of_window = false;
+        }
+
+        // Where the item was picked up, to undo the whole drag at once
+        let start_idx = match &self.detection_state {
+            DragDetectionState::Dragging { start_idx, .. } => Some(*start_idx),
+            _ => None,
         };
 
-        if pointer_released {
+        let dropped = !self.detection_state.is_keyboard_dragging()
+            && ((pointer_released && !waiting_for_click) || drop_interrupted);
+        keyboard_drop |= drop_interrupted && self.detection_state.is_keyboard_dragging();
+        let vetoed = cancel.is_none()
+            && drop_allowed
+            && ((dropped && source_item.is_some()) || keyboard_drop)
+            && response
+                .update
+                .as_ref()
+                .is_some_and(|update| constraints.is_drop_vetoed(update));
+        if !drop_allowed && (dropped || keyboard_drop) {
+            cancel = Some(CancellationReason::InvalidDrop);
+        } else if vetoed {
+            keyboard_drop = false;
+            if let (DragDetectionState::Dragging { id, input, .. }, Some(update)) =
+                (&mut self.detection_state, &response.update)
+            {
+                // Keep the item attached to the pointer, so the next click tries to drop it again
+                match input {
+                    DragInput::Keyboard(keyboard) => keyboard.drop_requested = false,
+                    _ => *input = DragInput::StickyPointer { pressed: false },
+                }
+                response.events.push(DndEvent::DropVetoed {
+                    id: *id,
+                    update: update.clone(),
+                });
+            }
+            response.state = self.detection_state.clone();
+        } else if dropped && source_item.is_some() {
             if let Some(dragged_item) = self.detection_state.dragged_item() {
-                response.finished = true;
+                response.finish(dragged_item);
 
                 self.detection_state = DragDetectionState::TransitioningBackAfterDragFinished {
                     dragged_item_size: self.detection_state.dragged_item_size(),
@@ -649,15 +3157,105 @@ impl DragDropUi {
             }
         }
 
+        if let (
+            Some(reason),
+            DragDetectionState::Dragging {
+                id,
+                source_idx,
+                start_idx,
+                ..
+            },
+        ) = (cancel, &self.detection_state)
+        {
+            // If the item is gone, there is nothing to move back
+            let item_shown = reason != CancellationReason::ListChanged;
+            if item_shown {
+                // Move the item back to where it was picked up
+                response.update = Some(DragUpdate::new(
+                    *id,
+                    *source_idx,
+                    insertion_index(*source_idx, *start_idx),
+                    modifiers,
+                    pointer_pos,
+                    &items,
+                ));
+                response.has_changed = true;
+            }
+            response.events.push(DndEvent::Cancelled {
+                id: *id,
+                reason: reason.clone(),
+            });
+
+            // If a button is still held, we wait until it's released so no new drag is started
+            if ui.input(|i| i.pointer.button_down(config.drag_button)) {
+                self.detection_state = DragDetectionState::Cancelled(reason);
+            } else {
+                response.cancellation_reason = Some(reason);
+                self.detection_state = if item_shown {
+                    DragDetectionState::TransitioningBackAfterDragFinished {
+                        dragged_item_size: self.detection_state.dragged_item_size(),
+                        id: *id,
+                    }
+                } else {
+                    DragDetectionState::None
+                };
+            }
+            response.state = self.detection_state.clone();
+        } else if let (true, Some(id)) = (keyboard_drop, self.detection_state.dragged_item()) {
+            response.finish(id);
+            self.detection_state = DragDetectionState::TransitioningBackAfterDragFinished {
+                dragged_item_size: self.detection_state.dragged_item_size(),
+                id,
+            };
+        }
+
+        // Move the cut item in front of the item it was pasted at, like a keyboard drop
+        if let (Some(cut), Some(paste), None) =
+            (self.cut_item, self.pending_paste.take(), &response.update)
+        {
+            let index_of = |id: Id| {
+                items
+                    .iter()
+                    .find(|(_, item_id, _)| *item_id == id)
+                    .map(|(idx, _, _)| *idx)
+            };
+            if let (Some(from), Some(to)) = (index_of(cut), index_of(paste)) {
+                let update = DragUpdate::new(cut, from, to, modifiers, None, &items);
+                if constraints.is_drop_allowed(from, update.target_index(), items.len())
+                    && !constraints.is_drop_vetoed(&update)
+                {
+                    self.cut_item = None;
+                    response.update = Some(update);
+                    response.has_changed = true;
+                    response.finish(cut);
+                }
+            }
+        }
+
+        if let (true, Some(update), Some(history)) = (
+            response.finished,
+            response
+                .update
+                .as_ref()
+                .filter(|update| update.onto.is_none()),
+            &self.history,
+        ) {
+            history
+                .lock()
+                .push(start_idx.unwrap_or(update.from), update.target_index());
+        }
+
         ui.input(|input| {
-            if !input.pointer.any_down()
+            if !input.pointer.button_down(config.drag_button)
+                && !self.detection_state.is_keyboard_dragging()
+                && !self.detection_state.is_sticky_dragging()
                 && !matches!(
                     self.detection_state,
                     DragDetectionState::TransitioningBackAfterDragFinished { .. }
                 )
             {
-                if let DragDetectionState::Cancelled(msg) = self.detection_state {
-                    response.cancellation_reason = Some(msg);
+                if let DragDetectionState::Cancelled(reason) = &self.detection_state {
+                    response.cancellation_reason = Some(reason.clone());
                 }
                 self.detection_state = DragDetectionState::None;
             }
@@ -665,7 +3263,13 @@ impl DragDropUi {
 
         // We are not over any target, cancel the drag
         if let DragDetectionState::CouldBeValidDrag = self.detection_state {
-            self.detection_state = DragDetectionState::Cancelled("Not hovering over any target");
+            self.detection_state = DragDetectionState::Cancelled(CancellationReason::NotOverTarget);
+        }
+
+        self.pending_drag = None;
+        #[cfg(feature = "test_utils")]
+        {
+            self.pending_snapshot = None;
         }
 
         // Repaint continuously while we are evaluating the drag
@@ -673,6 +3277,219 @@ impl DragDropUi {
             ui.ctx().request_repaint();
         }
 
+        self.item_count = last_item.map_or(0, |(idx, _, _)| idx + 1);
+        #[cfg(feature = "accesskit")]
+        {
+            self.announcer.update(&response, self.item_count);
+            self.announcer.ui(ui, ui.id().with("dnd_announcements"));
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            response.duplicate_ids = self.report_duplicate_ids(ui, duplicate_ids);
+        }
+        if constraints.debug {
+            let item_rects: Vec<_> = items
+                .iter()
+                .filter(|(_, id, _)| self.detection_state.dragged_item() != Some(*id))
+                .filter_map(|(idx, id, _)| Some((*idx, *id, item_responses.get(id)?.rect)))
+                .collect();
+            paint_debug_overlay(
+                ui,
+                &item_rects,
+                dragged_item_rect,
+                gap_rect,
+                response.update.as_ref().map(DragUpdate::target_index),
+            );
+        }
+
+        self.update_springs(ui, constraints, &items, items_rect, &mut response);
+
+        let hovered_item = item_responses
+            .iter()
+            .find(|(_, response)| response.contains_pointer())
+            .map(|(id, _)| *id);
+        if hovered_item != self.hovered_item {
+            // Handles shown on hover only find out in the next frame
+            self.hovered_item = hovered_item;
+            ui.ctx().request_repaint();
+        }
+
+        self.pressed_item = if matches!(
+            self.detection_state,
+            DragDetectionState::PressedWaitingForDelay { .. }
+        ) {
+            ui.input(|i| i.pointer.press_origin()).and_then(|origin| {
+                item_responses
+                    .iter()
+                    .find(|(_, response)| response.rect.contains(origin))
+                    .map(|(id, _)| *id)
+            })
+        } else {
+            None
+        };
+        response.hold_progress = self
+            .pressed_item
+            .and_then(|id| Some((id, self.hold_progress(ui, id)?)));
+        if let Some((_, progress)) = response.hold_progress {
+            ui.ctx().request_repaint();
+            if config.hold_indicator {
+                if let Some(origin) = ui.input(|i| i.pointer.press_origin()) {
+                    paint_hold_indicator(ui, config.floating_item_layer, origin, progress);
+                }
+            }
+        }
+
+        response.item_responses = item_responses;
+        response.history = self.history.clone();
+        response.shown_items = items.iter().map(|(idx, id, _)| (*idx, *id)).collect();
+        response.external_hover_index = external_hover.map(|hover| hover.slot);
+        if let (Some(hover), Some(accept)) = (external_hover, accept_payload) {
+            if ui.input(|i| i.pointer.any_released()) {
+                response.dropped_payload =
+                    accept(ui.ctx(), true).map(|payload| (hover.slot, payload));
+            }
+        }
+        if let (Some(hover), true) = (external_hover, constraints.accept_files) {
+            let files = ui.input(|i| i.raw.dropped_files.clone());
+            if !files.is_empty() {
+                response.external_drop = Some(ExternalDrop {
+                    index: hover.slot,
+                    files,
+                });
+            }
+        }
+
         response
     }
 }
+
+/// The angle in radians between `delta` and the main axis of the list, from 0 (along the axis)
+/// to [std::f32::consts::FRAC_PI_2] (across it)
+fn angle_to_main_axis(delta: Vec2, main_axis_vertical: bool) -> f32 {
+    let (main, cross) = if main_axis_vertical {
+        (delta.y, delta.x)
+    } else {
+        (delta.x, delta.y)
+    };
+    cross.abs().atan2(main.abs())
+}
+
+/// The id of the item with the given index, if it was shown
+fn id_at(items: &[(usize, Id, Pos2)], idx: usize) -> Option<Id> {
+    items
+        .iter()
+        .find(|(item_idx, _, _)| *item_idx == idx)
+        .map(|(_, id, _)| *id)
+}
+
+/// Paints the rects, indices and ids of the items, the boundaries between the zones in which the
+/// dragged item hovers each item, and the slot it would be dropped at, see [crate::Dnd::debug]
+fn paint_debug_overlay(
+    ui: &Ui,
+    items: &[(usize, Id, Rect)],
+    dragged_item_rect: Option<Rect>,
+    gap_rect: Option<Rect>,
+    target: Option<usize>,
+) {
+    let painter = ui.ctx().debug_painter();
+    for (idx, id, rect) in items {
+        let label = format!("{idx} {}", id.short_debug_format());
+        painter.debug_rect(*rect, Color32::LIGHT_BLUE, label);
+    }
+
+    // Without wrapping, the item whose center is closest to the center of the dragged item is
+    // hovered, so the zones meet halfway between the centers of neighbouring items
+    if !ui.layout().main_wrap {
+        let zone_items: Vec<Rect> = items
+            .iter()
+            .map(|(_, _, rect)| *rect)
+            .filter(Rect::is_positive)
+            .collect();
+        for pair in zone_items.windows(2) {
+            let middle = pair[0].center().lerp(pair[1].center(), 0.5);
+            let span = pair[0].union(pair[1]);
+            let boundary = if ui.layout().is_horizontal() {
+                [
+                    Pos2::new(middle.x, span.top()),
+                    Pos2::new(middle.x, span.bottom()),
+                ]
+            } else {
+                [
+                    Pos2::new(span.left(), middle.y),
+                    Pos2::new(span.right(), middle.y),
+                ]
+            };
+            painter.line_segment(boundary, Stroke::new(1.0, Color32::YELLOW));
+        }
+    }
+
+    if let Some(rect) = dragged_item_rect {
+        painter.circle_filled(rect.center(), 3.0, Color32::RED);
+    }
+    if let Some(rect) = gap_rect {
+        let label = target.map_or_else(String::new, |target| format!("drop at {target}"));
+        painter.debug_rect(rect, Color32::GREEN, label);
+    }
+}
+
+/// Covers the screen with an empty area directly below `floating_item_layer`, so widgets on the
+/// layers below it aren't hovered (egui only hovers widgets on the topmost layer under the pointer).
+fn block_interaction(ui: &Ui, floating_item_layer: LayerId) {
+    let screen_rect = ui.ctx().screen_rect();
+    egui::Area::new(Id::new("egui_dnd_interaction_blocker"))
+        .order(floating_item_layer.order)
+        .interactable(true)
+        .constrain(false)
+        .fixed_pos(screen_rect.min)
+        .show(ui.ctx(), |ui| {
+            ui.allocate_rect(screen_rect, Sense::hover());
+        });
+    // Within the same order, the area moved to the top last is painted above the others
+    ui.ctx().move_to_top(floating_item_layer);
+}
+
+/// Paints a ring around `center` that is filled up to `progress`, see [DragDropConfig::hold_indicator]
+fn paint_hold_indicator(ui: &Ui, layer: LayerId, center: Pos2, progress: f32) {
+    let radius = 24.0;
+    let segments = 32;
+    let color = ui.visuals().selection.stroke.color;
+    let painter = ui.ctx().layer_painter(layer);
+    painter.circle_stroke(center, radius, Stroke::new(3.0, color.gamma_multiply(0.3)));
+    let points = (0..=segments)
+        .map(|segment| {
+            let angle = std::f32::consts::TAU * progress * segment as f32 / segments as f32;
+            center + Vec2::angled(angle - std::f32::consts::FRAC_PI_2) * radius
+        })
+        .collect();
+    painter.add(Shape::line(points, Stroke::new(3.0, color)));
+}
+
+/// Paints a copy of the `shapes` of the floating dragged item, moved by `offset`, with the given
+/// opacity
+fn paint_ghost(
+    ui: &Ui,
+    floating_item_layer: LayerId,
+    shapes: std::ops::Range<usize>,
+    offset: Vec2,
+    opacity: f32,
+) {
+    let shapes = ui.ctx().graphics(|graphics| {
+        graphics
+            .get(floating_item_layer)
+            .map(|list| {
+                list.all_entries()
+                    .skip(shapes.start)
+                    .take(shapes.len())
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    });
+    let painter = ui.painter();
+    for ClippedShape { mut shape, .. } in shapes {
+        shape.translate(offset);
+        adjust_colors(&mut shape, &|color| *color = color.gamma_multiply(opacity));
+        painter.add(shape);
+    }
+}
@@ -0,0 +1,166 @@
+use std::hash::Hash;
+
+use egui::{Frame, Id, Label, PointerButton, ScrollArea, Sense, Ui, WidgetText};
+
+use crate::{dnd, DragDropResponse};
+
+/// A horizontal strip of tabs that can be selected, closed and reordered by dragging.
+/// Created with [dnd_tabs].
+pub struct DndTabs<'a> {
+    ui: &'a mut Ui,
+    id_source: Id,
+    closable: bool,
+    select: Option<Id>,
+}
+
+/// The response of [DndTabs::show]
+pub struct TabsResponse<T> {
+    /// The response of the list the tabs are shown in, e.g. to check if a tab is being dragged
+    pub response: DragDropResponse,
+    /// The index of the active tab, after the tabs were reordered or closed.
+    /// None if there are no tabs.
+    pub active: Option<usize>,
+    /// Whether another tab became active this frame, because it was clicked, selected with
+    /// [DndTabs::select] or the active tab was closed
+    pub active_changed: bool,
+    /// The tab that was closed this frame, with the index it was at
+    pub closed: Option<(usize, T)>,
+}
+
+/// Shows a horizontal strip of tabs, which are selected by clicking them, closed with their
+/// close button or a middle click and reordered by dragging them.
+/// Tabs are identified by their hash, like the items of [crate::Dnd::show_vec]. The active tab is
+/// remembered by its id, so it stays active while the tabs are reordered. If there are more tabs
+/// than fit, the strip scrolls horizontally.
+/// Example usage:
+/// ```rust no_run
+/// use eframe::egui;
+/// use egui::CentralPanel;
+/// use egui_dnd::dnd_tabs;
+///
+/// pub fn main() -> eframe::Result<()> {
+///     let mut tabs = vec!["alfred", "bernhard", "christian"];
+///
+///     eframe::run_simple_native("DnD Tabs Example", Default::default(), move |ctx, _frame| {
+///         CentralPanel::default().show(ctx, |ui| {
+///             let response = dnd_tabs(ui, "tabs").show(&mut tabs, |tab| (*tab).into());
+///             ui.separator();
+///             if let Some(active) = response.active {
+///                 ui.heading(tabs[active]);
+///             }
+///         });
+///     })
+/// }
+/// ```
+pub fn dnd_tabs(ui: &mut Ui, id_source: impl Hash) -> DndTabs {
+    DndTabs {
+        ui,
+        id_source: Id::new(id_source),
+        closable: true,
+        select: None,
+    }
+}
+
+impl DndTabs<'_> {
+    /// Whether the tabs have a close button. The default is true.
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    /// Makes the tab with the given id active and scrolls to it, e.g. when it was just opened.
+    /// The id of a tab is `Id::new(tab)`.
+    pub fn select(mut self, id: Id) -> Self {
+        self.select = Some(id);
+        self
+    }
+
+    /// Shows the tabs with the titles returned by `title`, reorders them when a tab is dropped
+    /// and removes them when they are closed.
+    pub fn show<T: Hash>(
+        self,
+        tabs: &mut Vec<T>,
+        mut title: impl FnMut(&T) -> WidgetText,
+    ) -> TabsResponse<T> {
+        let DndTabs {
+            ui,
+            id_source,
+            closable,
+            select,
+        } = self;
+        let active_id = ui.id().with(id_source).with("dnd_tabs_active");
+        let stored = ui.data(|data| data.get_temp::<Id>(active_id));
+        let exists = |id: &Id| tabs.iter().any(|tab| Id::new(tab) == *id);
+        // The tab that was active in the last frame, with the same fallback as below, so the
+        // first tab doesn't count as a change in the first frame
+        let previous = stored.filter(exists).or_else(|| tabs.first().map(Id::new));
+        let mut active = select.or(stored);
+        if active.filter(exists).is_none() {
+            active = tabs.first().map(Id::new);
+        }
+        let mut close = None;
+
+        let response = ScrollArea::horizontal()
+            .id_source(id_source.with("dnd_tabs_scroll"))
+            .auto_shrink([false, true])
+            // Dragging a tab should move it, not scroll the strip
+            .drag_to_scroll(false)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    dnd(ui, id_source).show_vec(tabs, |ui, tab, handle, state| {
+                        let id = Id::new(&*tab);
+                        let selected = active == Some(id);
+                        let frame = Frame::group(ui.style()).fill(if selected {
+                            ui.visuals().selection.bg_fill
+                        } else {
+                            ui.visuals().faint_bg_color
+                        });
+                        frame.show(ui, |ui| {
+                            let response = handle.sense(Sense::click()).ui(ui, |ui| {
+                                ui.add(Label::new(title(tab)).selectable(false));
+                            });
+                            if response.clicked() {
+                                active = Some(id);
+                            }
+                            let close_clicked =
+                                closable && ui.small_button("🗙").on_hover_text("Close").clicked();
+                            if close_clicked
+                                || (closable && response.clicked_by(PointerButton::Middle))
+                            {
+                                close = Some(id);
+                            }
+                            if selected && select.is_some() && !state.dragged {
+                                response.scroll_to_me(None);
+                            }
+                        });
+                    })
+                })
+                .inner
+            })
+            .inner;
+
+        // A closed tab passes its place to the tab after it, or to the one before if it was last
+        let closed = close
+            .and_then(|id| tabs.iter().position(|tab| Id::new(tab) == id))
+            .map(|idx| {
+                let tab = tabs.remove(idx);
+                if active == Some(Id::new(&tab)) {
+                    active = tabs.get(idx.min(tabs.len().saturating_sub(1))).map(Id::new);
+                }
+                (idx, tab)
+            });
+
+        let active_idx = tabs.iter().position(|tab| Some(Id::new(tab)) == active);
+        ui.data_mut(|data| match active {
+            Some(id) => data.insert_temp(active_id, id),
+            None => data.remove::<Id>(active_id),
+        });
+
+        TabsResponse {
+            response,
+            active: active_idx,
+            active_changed: active != previous,
+            closed,
+        }
+    }
+}
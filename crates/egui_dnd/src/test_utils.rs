@@ -0,0 +1,257 @@
+//! Helpers to simulate drags on a headless [Context], so reordering can be tested without a
+//! window, see [DragSimulator], and to show a drag without input, see [DragSnapshot].
+
+use egui::{
+    CentralPanel, Context, Event, Id, Modifiers, PointerButton, Pos2, RawInput, Rect, Ui, Vec2,
+};
+
+use crate::DragDropResponse;
+
+/// Runs frames of a headless [Context] and simulates the pointer input of a drag.
+///
+/// The list is shown by a closure that returns the [DragDropResponse] of the [crate::Dnd], which
+/// is called once per frame. Items are identified by their [crate::DragDropItem::id], which is
+/// `Id::new(item)` for the items of [crate::Dnd::show_vec]. They are pressed at the center of
+/// the rect of their [DragDropResponse::item_response], so the handle has to cover it, e.g.
+/// because it wraps the whole item.
+///
+/// ```rust
+/// use egui::Id;
+/// use egui_dnd::dnd;
+/// use egui_dnd::test_utils::DragSimulator;
+///
+/// let mut items = vec!["a", "b", "c"];
+/// let mut simulator = DragSimulator::new();
+/// simulator.drag(Id::new("a"), Id::new("c"), |ui| {
+///     dnd(ui, "list").show_vec(&mut items, |ui, item, handle, _state| {
+///         handle.ui(ui, |ui| {
+///             ui.label(*item);
+///         });
+///     })
+/// });
+/// assert_eq!(items, vec!["b", "c", "a"]);
+/// ```
+pub struct DragSimulator {
+    ctx: Context,
+    screen_size: Vec2,
+    time: f64,
+    /// How much time passes with each frame, in seconds
+    frame_time: f64,
+    modifiers: Modifiers,
+    response: Option<DragDropResponse>,
+}
+
+impl Default for DragSimulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DragSimulator {
+    /// Creates a simulator with a new [Context] and a screen of 1000 x 1000 points
+    pub fn new() -> Self {
+        Self {
+            ctx: Context::default(),
+            screen_size: Vec2::splat(1000.0),
+            time: 0.0,
+            frame_time: 0.1,
+            modifiers: Modifiers::NONE,
+            response: None,
+        }
+    }
+
+    /// Sets the size of the screen the list is shown on
+    pub fn with_screen_size(mut self, size: Vec2) -> Self {
+        self.screen_size = size;
+        self
+    }
+
+    /// Sets how much time passes with each frame, in seconds. The default is 0.1 seconds.
+    /// With a [crate::DragDropConfig::drag_delay], the item is only picked up once enough
+    /// frames passed.
+    pub fn with_frame_time(mut self, seconds: f64) -> Self {
+        self.frame_time = seconds;
+        self
+    }
+
+    /// Sets the modifier keys held in every frame, e.g. to test [crate::DragUpdate::modifiers]
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    /// The [Context] the frames are run with, e.g. to inspect its memory
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// The response of the last frame
+    pub fn response(&self) -> Option<&DragDropResponse> {
+        self.response.as_ref()
+    }
+
+    /// Runs a frame with the given input events, showing the list in a [CentralPanel]
+    pub fn frame(
+        &mut self,
+        events: Vec<Event>,
+        mut show: impl FnMut(&mut Ui) -> DragDropResponse,
+    ) -> &DragDropResponse {
+        self.time += self.frame_time;
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, self.screen_size)),
+            time: Some(self.time),
+            modifiers: self.modifiers,
+            events,
+            ..Default::default()
+        };
+
+        let mut response = None;
+        let _ = self.ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                response = Some(show(ui));
+            });
+        });
+        self.response
+            .insert(response.expect("the list was not shown"))
+    }
+
+    /// The center of the item with the given id in the last frame. Runs a frame first if there
+    /// was none yet.
+    ///
+    /// # Panics
+    /// If the item wasn't shown
+    pub fn center_of(&mut self, id: Id, show: impl FnMut(&mut Ui) -> DragDropResponse) -> Pos2 {
+        if self.response.is_none() {
+            self.frame(Vec::new(), show);
+        }
+        self.response
+            .as_ref()
+            .and_then(|response| response.item_response(id))
+            .map(|response| response.rect.center())
+            .expect("the item was not shown")
+    }
+
+    /// Presses the pointer on the item with id `source` and moves it onto the item with id
+    /// `target`, without releasing it. Returns the pointer position, to [DragSimulator::release]
+    /// it there.
+    pub fn hold(
+        &mut self,
+        source: Id,
+        target: Id,
+        mut show: impl FnMut(&mut Ui) -> DragDropResponse,
+    ) -> Pos2 {
+        let start = self.center_of(source, &mut show);
+        let end = self.center_of(target, &mut show);
+        self.press(start, &mut show);
+        // Move a bit to get above the click threshold before moving to the target
+        let nudged = start + (end - start).normalized() * 4.0;
+        self.move_to(nudged, &mut show);
+        for _ in 0..4 {
+            self.move_to(end, &mut show);
+        }
+        end
+    }
+
+    /// Drags the item with id `source` onto the item with id `target` and releases it there.
+    /// Returns the response of the frame the item was dropped in.
+    pub fn drag(
+        &mut self,
+        source: Id,
+        target: Id,
+        mut show: impl FnMut(&mut Ui) -> DragDropResponse,
+    ) -> &DragDropResponse {
+        let pos = self.hold(source, target, &mut show);
+        self.release(pos, show)
+    }
+
+    /// Presses the primary pointer button at `pos`
+    pub fn press(
+        &mut self,
+        pos: Pos2,
+        show: impl FnMut(&mut Ui) -> DragDropResponse,
+    ) -> &DragDropResponse {
+        self.frame(
+            vec![Event::PointerMoved(pos), pointer_button(pos, true)],
+            show,
+        )
+    }
+
+    /// Moves the pointer to `pos`
+    pub fn move_to(
+        &mut self,
+        pos: Pos2,
+        show: impl FnMut(&mut Ui) -> DragDropResponse,
+    ) -> &DragDropResponse {
+        self.frame(vec![Event::PointerMoved(pos)], show)
+    }
+
+    /// Releases the primary pointer button at `pos`
+    pub fn release(
+        &mut self,
+        pos: Pos2,
+        show: impl FnMut(&mut Ui) -> DragDropResponse,
+    ) -> &DragDropResponse {
+        self.frame(
+            vec![Event::PointerMoved(pos), pointer_button(pos, false)],
+            show,
+        )
+    }
+}
+
+/// A drag the list is forced into with [crate::Dnd::snapshot_drag], without any input.
+///
+/// This renders the frames of a drag deterministically, e.g. for golden image tests:
+/// - pick up: the item held at its own index with a small offset
+/// - hover: the item held over another slot
+/// - settle: the item dropped with [DragSnapshot::settle], then the list shown without a
+///   snapshot while the item moves into the gap
+///
+/// The item is held like one picked up with the keyboard, so the gap and the held item move to
+/// their place with the animation time of the [crate::Dnd]. Set it to 0 to get the final
+/// positions right away. The snapshot is applied when the item is shown and the gap is placed
+/// at the end of the frame, so render a few frames before taking the image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragSnapshot {
+    /// The id of the held item, see [crate::DragDropItem::id]
+    pub item: Id,
+    /// The slot the gap is shown at, counting only the items that aren't held
+    pub slot: usize,
+    /// Where the held item is drawn, relative to the gap
+    pub offset: Vec2,
+    /// If true, the item is dropped at the slot, like when it's released
+    pub settle: bool,
+}
+
+impl DragSnapshot {
+    /// Holds the item with the given id over the gap at `slot`
+    pub fn new(item: Id, slot: usize) -> Self {
+        Self {
+            item,
+            slot,
+            offset: Vec2::ZERO,
+            settle: false,
+        }
+    }
+
+    /// Draws the held item at `offset` from the gap, like when the pointer doesn't grab it at its
+    /// corner or when it was moved off the list
+    pub fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Drops the item at the slot in this frame. Show the list without a snapshot afterwards, to
+    /// render the item settling into place.
+    pub fn settle(mut self) -> Self {
+        self.settle = true;
+        self
+    }
+}
+
+fn pointer_button(pos: Pos2, pressed: bool) -> Event {
+    Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Modifiers::NONE,
+    }
+}
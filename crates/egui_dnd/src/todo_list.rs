@@ -0,0 +1,188 @@
+use std::hash::Hash;
+
+use egui::{Align, Frame, Id, Label, Layout, Ui, WidgetText};
+
+use crate::utils::shift_vec;
+use crate::{dnd, DragDropResponse, KeyedItem};
+
+/// A list of todos with checkboxes and a "Done" band below them. Created with [dnd_todo_list].
+pub struct TodoList<'a> {
+    ui: &'a mut Ui,
+    id_source: Id,
+    done_text: WidgetText,
+}
+
+/// Something the user did with a todo in [TodoList::show]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoEvent {
+    /// The todo was dropped onto the "Done" band, it's marked as done and wasn't reordered
+    Completed(Id),
+    /// The checkbox of the todo was clicked, with the new done state
+    Toggled(Id, bool),
+    /// The todo was moved within the list, from and to are like in [crate::DragUpdate]
+    Moved {
+        /// The id of the todo
+        id: Id,
+        /// The index the todo was at
+        from: usize,
+        /// The index the todo was inserted before
+        to: usize,
+    },
+}
+
+/// The response of [TodoList::show]
+pub struct TodoListResponse {
+    /// The response of the list the todos are shown in
+    pub response: DragDropResponse,
+    /// The response of the "Done" band
+    pub band: egui::Response,
+    /// What happened to the todos this frame
+    pub events: Vec<TodoEvent>,
+}
+
+/// Shows a list of todos, each with a drag handle and a checkbox, and a "Done" band at the bottom.
+/// Todos are reordered by dragging them within the list. Dropping a todo onto the band completes
+/// it instead: it's checked, stays where it was and a [TodoEvent::Completed] is reported, e.g. to
+/// move it to an archive. The todos are only reordered once a todo is dropped, like with
+/// [crate::CommitStrategy::OnDrop].
+///
+/// Todos are identified by the key returned by `key`, like with [crate::Dnd::show_vec_keyed], so
+/// checking them doesn't change their id. The ids in the events are `Id::new(key(todo))`.
+/// Example usage:
+/// ```rust no_run
+/// use eframe::egui;
+/// use egui::CentralPanel;
+/// use egui_dnd::{dnd_todo_list, TodoEvent};
+///
+/// struct Todo {
+///     title: &'static str,
+///     done: bool,
+/// }
+///
+/// pub fn main() -> eframe::Result<()> {
+///     let mut todos = vec![
+///         Todo { title: "Buy milk", done: false },
+///         Todo { title: "Water plants", done: true },
+///     ];
+///
+///     eframe::run_simple_native("DnD Todo Example", Default::default(), move |ctx, _frame| {
+///         CentralPanel::default().show(ctx, |ui| {
+///             let response = dnd_todo_list(ui, "todos").show(
+///                 &mut todos,
+///                 |todo| todo.title,
+///                 |todo| todo.title.into(),
+///                 |todo| &mut todo.done,
+///             );
+///             for event in response.events {
+///                 if let TodoEvent::Completed(id) = event {
+///                     println!("completed {id:?}");
+///                 }
+///             }
+///         });
+///     })
+/// }
+/// ```
+pub fn dnd_todo_list(ui: &mut Ui, id_source: impl Hash) -> TodoList {
+    TodoList {
+        ui,
+        id_source: Id::new(id_source),
+        done_text: "✔ Done".into(),
+    }
+}
+
+impl TodoList<'_> {
+    /// The text shown in the band todos are dropped onto to complete them.
+    /// The default is "✔ Done".
+    pub fn done_text(mut self, text: impl Into<WidgetText>) -> Self {
+        self.done_text = text.into();
+        self
+    }
+
+    /// Shows the todos with the labels returned by `label` and the checkbox for the flag returned
+    /// by `done`, followed by the "Done" band
+    pub fn show<T, K: Hash>(
+        self,
+        items: &mut [T],
+        key: impl Fn(&T) -> K,
+        mut label: impl FnMut(&T) -> WidgetText,
+        mut done: impl FnMut(&mut T) -> &mut bool,
+    ) -> TodoListResponse {
+        let TodoList {
+            ui,
+            id_source,
+            done_text,
+        } = self;
+        let mut events = Vec::new();
+
+        let keyed_items = items.iter_mut().map(|item| KeyedItem {
+            id: Id::new(key(item)),
+            item,
+        });
+        let response = dnd(ui, id_source).show(keyed_items, |ui, keyed, handle, _state| {
+            ui.horizontal(|ui| {
+                handle.ui(ui, |ui| {
+                    ui.label("☰");
+                });
+                let label = label(keyed.item);
+                let is_done = done(keyed.item);
+                if ui.checkbox(is_done, "").changed() {
+                    events.push(TodoEvent::Toggled(keyed.id, *is_done));
+                }
+                let label = Label::new(label).selectable(false);
+                if *is_done {
+                    ui.add_enabled(false, label);
+                } else {
+                    ui.add(label);
+                }
+            });
+        });
+
+        let dragging = response.is_dragging();
+        let band = Frame::group(ui.style())
+            .fill(if dragging {
+                ui.visuals().selection.bg_fill.gamma_multiply(0.4)
+            } else {
+                ui.visuals().faint_bg_color
+            })
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.with_layout(Layout::top_down(Align::Center), |ui| {
+                    ui.add(Label::new(done_text).selectable(false));
+                });
+            })
+            .response;
+        let pointer = ui.input(|i| i.pointer.hover_pos());
+        if dragging && pointer.is_some_and(|pos| band.rect.contains(pos)) {
+            ui.painter().rect_stroke(
+                band.rect,
+                ui.visuals().widgets.active.rounding,
+                ui.visuals().selection.stroke,
+            );
+        }
+
+        if let Some(update) = response.final_update() {
+            let onto_band = update
+                .pointer_pos
+                .is_some_and(|pos| band.rect.contains(pos));
+            if onto_band {
+                if let Some(item) = items.get_mut(update.from) {
+                    *done(item) = true;
+                }
+                events.push(TodoEvent::Completed(update.id));
+            } else if update.target_index() != update.from {
+                shift_vec(update.from, update.to, items);
+                events.push(TodoEvent::Moved {
+                    id: update.id,
+                    from: update.from,
+                    to: update.to,
+                });
+            }
+        }
+
+        TodoListResponse {
+            response,
+            band,
+            events,
+        }
+    }
+}
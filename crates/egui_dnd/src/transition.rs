@@ -0,0 +1,175 @@
+use std::collections::{HashMap, HashSet};
+
+use egui::{Id, Rect, Ui, Vec2};
+
+/// Tracks which items were shown in the last frame, so we can animate items that are
+/// added to or removed from the list from outside of the drag and drop.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ItemTransitions {
+    /// Ids and sizes of the items shown in the last frame, in order.
+    /// None if the list hasn't been shown yet, so we don't animate the initial items.
+    previous_items: Option<Vec<(Id, Vec2)>>,
+    previous_ids: HashSet<Id>,
+    current_items: Vec<(Id, Vec2)>,
+    /// Ids of the items that will be shown this frame, if known upfront.
+    /// This is needed to know where to put the gap of a removed item.
+    upcoming_items: Option<HashSet<Id>>,
+    /// Items that have been added, with the time they were added and their last known size.
+    entering: HashMap<Id, (f64, Vec2)>,
+    exiting: Vec<ExitingItem>,
+    /// If false, we only keep track of the items but don't start any new animations.
+    animate: bool,
+    duration: f32,
+}
+
+#[derive(Debug, Clone)]
+struct ExitingItem {
+    id: Id,
+    size: Vec2,
+    started_at: f64,
+    /// The gap is shown before this item, or at the end of the list if None
+    before: Option<Id>,
+    shown: bool,
+}
+
+impl ItemTransitions {
+    pub fn set_upcoming_items(&mut self, ids: impl Iterator<Item = Id>) {
+        self.upcoming_items = Some(ids.collect());
+    }
+
+    pub fn begin_frame(&mut self, ui: &Ui, enabled: bool, animate: bool, duration: f32) {
+        if !enabled {
+            *self = Self::default();
+            return;
+        }
+        self.animate = animate;
+        self.duration = duration;
+
+        let now = ui.input(|i| i.time);
+        if let Some(previous) = &self.previous_items {
+            self.previous_ids = previous.iter().map(|(id, _)| *id).collect();
+        }
+        if let (Some(previous), Some(upcoming)) = (&self.previous_items, self.upcoming_items.take())
+        {
+            if animate {
+                for (i, (id, size)) in previous.iter().enumerate() {
+                    if !upcoming.contains(id) && !self.exiting.iter().any(|e| e.id == *id) {
+                        let before = previous[i + 1..]
+                            .iter()
+                            .map(|(id, _)| *id)
+                            .find(|id| upcoming.contains(id));
+                        self.exiting.push(ExitingItem {
+                            id: *id,
+                            size: *size,
+                            started_at: now,
+                            before,
+                            shown: false,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn progress(&self, ui: &Ui, started_at: f64) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        let elapsed = ui.input(|i| i.time) - started_at;
+        let progress = (elapsed as f32 / self.duration).clamp(0.0, 1.0);
+        if progress < 1.0 {
+            ui.ctx().request_repaint();
+        }
+        simple_easing::cubic_in_out(progress)
+    }
+
+    /// Shows the collapsing gaps of removed items that were in front of `before`.
+    pub fn show_exiting(&mut self, ui: &mut Ui, before: Option<Id>) {
+        let mut exiting = std::mem::take(&mut self.exiting);
+        for item in exiting.iter_mut().filter(|item| item.before == before) {
+            let remaining = 1.0 - self.progress(ui, item.started_at);
+            ui.add_space(main_axis_space(ui, item.size) * remaining);
+            item.shown = remaining > 0.0;
+        }
+        self.exiting = exiting;
+    }
+
+    /// Returns the progress of the enter animation and the last known size, if the item is entering.
+    pub fn enter_progress(&mut self, ui: &Ui, id: Id) -> Option<(f32, Vec2)> {
+        if self.animate
+            && self.previous_items.is_some()
+            && !self.previous_ids.contains(&id)
+            && !self.entering.contains_key(&id)
+        {
+            self.entering.insert(id, (ui.input(|i| i.time), Vec2::ZERO));
+            // The item might have been removed and added again
+            self.exiting.retain(|item| item.id != id);
+        }
+
+        let (started_at, size) = *self.entering.get(&id)?;
+        let progress = self.progress(ui, started_at);
+        if progress >= 1.0 {
+            self.entering.remove(&id);
+            None
+        } else {
+            Some((progress, size))
+        }
+    }
+
+    pub fn record_item(&mut self, id: Id, size: Vec2) {
+        self.current_items.push((id, size));
+        if let Some((_, last_size)) = self.entering.get_mut(&id) {
+            *last_size = size;
+        }
+    }
+
+    pub fn end_frame(&mut self, ui: &mut Ui, enabled: bool) {
+        if !enabled {
+            return;
+        }
+        self.show_exiting(ui, None);
+        self.exiting.retain(|item| item.shown);
+        for item in &mut self.exiting {
+            item.shown = false;
+        }
+        self.previous_items = Some(std::mem::take(&mut self.current_items));
+    }
+}
+
+/// Shows an entering item, clipped to the part of it that is already visible.
+/// Returns the allocated rect and the full rect of the item.
+pub(crate) fn show_entering(
+    ui: &mut Ui,
+    (progress, last_size): (f32, Vec2),
+    content: impl FnOnce(&mut Ui) -> Rect,
+) -> (Rect, Rect) {
+    let mut child = ui.child_ui(ui.available_rect_before_wrap(), *ui.layout());
+    let visible_size = scale_main_axis(ui, last_size, progress);
+    child.set_clip_rect(
+        Rect::from_min_size(child.next_widget_position(), visible_size).intersect(ui.clip_rect()),
+    );
+    let rect = content(&mut child);
+
+    let min = ui.cursor().min;
+    ui.add_space(main_axis_space(ui, rect.size()) * progress);
+    let allocated = Rect::from_min_size(min, scale_main_axis(ui, rect.size(), progress));
+    (allocated, rect)
+}
+
+/// The space an item takes up along the main axis, including the item spacing
+fn main_axis_space(ui: &Ui, size: Vec2) -> f32 {
+    let spacing = ui.spacing().item_spacing;
+    if ui.layout().main_dir().is_horizontal() {
+        size.x + spacing.x
+    } else {
+        size.y + spacing.y
+    }
+}
+
+fn scale_main_axis(ui: &Ui, size: Vec2, factor: f32) -> Vec2 {
+    if ui.layout().main_dir().is_horizontal() {
+        Vec2::new(size.x * factor, size.y)
+    } else {
+        Vec2::new(size.x, size.y * factor)
+    }
+}
@@ -0,0 +1,152 @@
+//! Helpers for showing a tree as a flat list, where the children of open nodes are shown indented
+//! after their parent. Used by [crate::FileList] and [crate::LayerPanel].
+
+use std::collections::HashSet;
+
+use egui::Id;
+
+use crate::{DragDropItem, DragUpdate, DropPosition};
+
+/// A node of a tree shown with the helpers of this module
+pub(crate) trait TreeNode: Sized {
+    /// The id of the node, it has to be unique among the children of `parent`
+    fn id(&self, parent: Id) -> Id;
+    /// The children of the node, or `None` if it can't have any
+    fn children(&self) -> Option<&Vec<Self>>;
+    /// The children of the node, or `None` if it can't have any
+    fn children_mut(&mut self) -> Option<&mut Vec<Self>>;
+}
+
+/// A visible node of the tree
+#[derive(Debug, Clone)]
+pub(crate) struct TreeRow {
+    pub(crate) id: Id,
+    /// The indices of the node and its parents, starting at the root
+    pub(crate) path: Vec<usize>,
+    /// Whether the node is open, `None` if it can't have children
+    pub(crate) open: Option<bool>,
+}
+
+impl TreeRow {
+    /// How many parents the node has
+    pub(crate) fn depth(&self) -> usize {
+        self.path.len() - 1
+    }
+}
+
+impl DragDropItem for TreeRow {
+    fn id(&self) -> Id {
+        self.id
+    }
+}
+
+/// The visible nodes, with the children of the open nodes after their parent
+pub(crate) fn flatten<N: TreeNode>(nodes: &[N], root: Id, open: &HashSet<Id>) -> Vec<TreeRow> {
+    let mut rows = Vec::new();
+    flatten_into(nodes, root, &mut Vec::new(), open, &mut rows);
+    rows
+}
+
+fn flatten_into<N: TreeNode>(
+    nodes: &[N],
+    parent: Id,
+    path: &mut Vec<usize>,
+    open: &HashSet<Id>,
+    rows: &mut Vec<TreeRow>,
+) {
+    for (idx, node) in nodes.iter().enumerate() {
+        let id = node.id(parent);
+        path.push(idx);
+        let is_open = open.contains(&id);
+        rows.push(TreeRow {
+            id,
+            path: path.clone(),
+            open: node.children().map(|_| is_open),
+        });
+        if let (Some(children), true) = (node.children(), is_open) {
+            flatten_into(children, id, path, open, rows);
+        }
+        path.pop();
+    }
+}
+
+/// The path the dragged row of `update` is inserted at. Rows dropped onto a node, see
+/// [crate::Dnd::drop_onto], are added at the end of its children.
+pub(crate) fn drop_target(
+    rows: &[TreeRow],
+    roots: usize,
+    update: &DragUpdate,
+) -> Option<Vec<usize>> {
+    let path_of = |id: Id| {
+        rows.iter()
+            .find(|row| row.id == id)
+            .map(|row| row.path.clone())
+    };
+    match (update.onto, update.position) {
+        (Some(idx), _) => rows.get(idx).map(|row| {
+            let mut path = row.path.clone();
+            path.push(usize::MAX);
+            path
+        }),
+        (None, DropPosition::Before(id)) => path_of(id),
+        (None, DropPosition::After(id)) => path_of(id).map(|mut path| {
+            *path.last_mut().unwrap() += 1;
+            path
+        }),
+        (None, DropPosition::End) => Some(vec![roots]),
+    }
+}
+
+/// The node at `path`
+pub(crate) fn node_at<'n, N: TreeNode>(nodes: &'n [N], path: &[usize]) -> Option<&'n N> {
+    match path.split_first() {
+        None => None,
+        Some((idx, [])) => nodes.get(*idx),
+        Some((idx, rest)) => node_at(nodes.get(*idx)?.children()?, rest),
+    }
+}
+
+/// The children of the node at `path`, or the roots if the path is empty
+pub(crate) fn children_mut<'n, N: TreeNode>(
+    nodes: &'n mut Vec<N>,
+    path: &[usize],
+) -> Option<&'n mut Vec<N>> {
+    match path.split_first() {
+        None => Some(nodes),
+        Some((idx, rest)) => children_mut(nodes.get_mut(*idx)?.children_mut()?, rest),
+    }
+}
+
+/// Whether `target` is inside of the node at `source`, so the node can't be moved there
+pub(crate) fn is_inside(target: &[usize], source: &[usize]) -> bool {
+    target.len() > source.len() && target.starts_with(source)
+}
+
+/// Removes the node at `source`. The nodes behind it move up by one, so `target` is adjusted to
+/// still point to the same place.
+pub(crate) fn remove<N: TreeNode>(
+    nodes: &mut Vec<N>,
+    source: &[usize],
+    target: &mut [usize],
+) -> Option<N> {
+    let (idx, parent) = source.split_last()?;
+    let siblings = children_mut(nodes, parent).filter(|siblings| *idx < siblings.len())?;
+    let node = siblings.remove(*idx);
+    let depth = parent.len();
+    if target.len() > depth && target.starts_with(parent) && target[depth] > *idx {
+        target[depth] -= 1;
+    }
+    Some(node)
+}
+
+/// Inserts the node at `path`, clamped to the end of its siblings
+pub(crate) fn insert<N: TreeNode>(nodes: &mut Vec<N>, path: &[usize], node: N) -> bool {
+    let Some((idx, parent)) = path.split_last() else {
+        return false;
+    };
+    let Some(siblings) = children_mut(nodes, parent) else {
+        return false;
+    };
+    siblings.insert((*idx).min(siblings.len()), node);
+    true
+}
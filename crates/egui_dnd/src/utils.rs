@@ -1,3 +1,11 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use egui::{Context, Id};
+
+use crate::state::DragDropUi;
+use crate::ListScopes;
+
 /// Move an item in a slice according to the drag and drop logic.
 ///
 /// Rotates the section of the slice between `source_idx` and `target_idx` such that the item
@@ -41,3 +49,249 @@ pub fn shift_vec<T>(source_idx: usize, target_idx: usize, vec: &mut [T]) {
         );
     }
 }
+
+/// Moves a contiguous range of items, like [shift_vec] moves a single item, e.g. a group of
+/// items or a subtree that is dragged together.
+///
+/// The items in `source` keep their order and are inserted in front of the item that was at
+/// `target_idx` before the move, or at the end if `target_idx` is the length of the slice.
+/// Nothing is moved if `target_idx` is inside of the range.
+///
+/// # Example
+///
+/// ```rust
+/// use egui_dnd::utils::shift_range;
+///
+/// let mut v = vec![1, 2, 3, 4, 5];
+/// shift_range(0..2, 4, &mut v);
+/// assert_eq!(v, [3, 4, 1, 2, 5]);
+/// shift_range(2..4, 0, &mut v);
+/// assert_eq!(v, [1, 2, 3, 4, 5]);
+/// ```
+///
+/// # Panics
+/// Panics if the range isn't within the slice or `target_idx > len()`, like [shift_vec]
+pub fn shift_range<T>(source: Range<usize>, target_idx: usize, vec: &mut [T]) {
+    if source.start > source.end || source.end > vec.len() || target_idx > vec.len() {
+        panic!(
+            "Failed to move items {:?} to index {}. Slice has {} elements",
+            source,
+            target_idx,
+            vec.len()
+        );
+    }
+    let len = source.len();
+    if target_idx > source.end {
+        vec[source.start..target_idx].rotate_left(len);
+    } else if target_idx < source.start {
+        vec[target_idx..source.end].rotate_right(len);
+    }
+}
+
+/// Applies several moves in one pass, e.g. when all selected items are dragged together.
+///
+/// Each move is a `(source_idx, target_idx)` pair like the arguments of [shift_vec], and all
+/// indices refer to the slice before any move, so they don't have to be adjusted for each other.
+/// The moved items are inserted in front of the item that was at their `target_idx`, or at the
+/// end if it's the length of the slice. If that item is moved as well, they are inserted where
+/// it was. Items moved to the same place end up in the order of the moves.
+///
+/// # Example
+///
+/// ```rust
+/// use egui_dnd::utils::shift_many;
+///
+/// // Move the selected items 1 and 4 in front of item 3
+/// let mut v = vec![0, 1, 2, 3, 4, 5];
+/// shift_many(&[(1, 3), (4, 3)], &mut v);
+/// assert_eq!(v, [0, 2, 1, 4, 3, 5]);
+/// ```
+///
+/// # Panics
+/// Panics if an item is moved twice or an index is out of bounds, like [shift_vec]
+pub fn shift_many<T>(moves: &[(usize, usize)], vec: &mut [T]) {
+    let len = vec.len();
+    let mut moved = vec![false; len];
+    for (source_idx, target_idx) in moves {
+        if *source_idx >= len || *target_idx > len || moved[*source_idx] {
+            panic!(
+                "Failed to move item from index {} to index {}. Slice has {} elements",
+                source_idx, target_idx, len
+            );
+        }
+        moved[*source_idx] = true;
+    }
+
+    // The index each item had before the moves, in the new order
+    let mut order = Vec::with_capacity(len);
+    for idx in 0..=len {
+        order.extend(
+            moves
+                .iter()
+                .filter(|(_, target_idx)| *target_idx == idx)
+                .map(|(source_idx, _)| *source_idx),
+        );
+        if idx < len && !moved[idx] {
+            order.push(idx);
+        }
+    }
+
+    // Swaps each item into place. Items that were swapped away already are found by following
+    // the order to where they went.
+    for idx in 0..len {
+        let mut source_idx = order[idx];
+        while source_idx < idx {
+            source_idx = order[source_idx];
+        }
+        vec.swap(idx, source_idx);
+    }
+}
+
+/// Moves an item whose position is stored as an order value, like an `Order(u32)` component in
+/// an ECS like bevy or hecs, where the items can't be sorted in a [Vec].
+/// If the entities are kept in a `Vec<Entity>` instead, use [crate::DragDropResponse::update].
+///
+/// `orders` are the order values of the items in the order they were shown, i.e. sorted by
+/// order value. After the move, they are rewritten to be dense, starting at 0, so gaps or
+/// duplicates left by items that were added or removed are fixed as well.
+///
+/// # Example
+///
+/// ```rust
+/// use egui_dnd::utils::shift_order_values;
+///
+/// struct Order(u32);
+///
+/// // The order values of the shown items, sorted. Item 3 was removed at some point.
+/// let mut orders = vec![Order(0), Order(1), Order(2), Order(4)];
+/// shift_order_values(0, 2, orders.iter_mut().map(|order| &mut order.0));
+/// let values: Vec<u32> = orders.iter().map(|order| order.0).collect();
+/// assert_eq!(values, [1, 0, 2, 3]);
+/// ```
+///
+/// # Panics
+/// Panics if `source_idx >= len()` or `target_idx > len()`, like [shift_vec]
+pub fn shift_order_values<'a>(
+    source_idx: usize,
+    target_idx: usize,
+    orders: impl IntoIterator<Item = &'a mut u32>,
+) {
+    let mut orders: Vec<&mut u32> = orders.into_iter().collect();
+    shift_vec(source_idx, target_idx, &mut orders);
+    for (order, value) in orders.into_iter().zip(0..) {
+        *order = value;
+    }
+}
+
+/// A single move of [diff_order]: the item is removed at `from` and inserted again at `to`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    /// The id of the moved item
+    pub id: Id,
+    /// The index of the item before the move
+    pub from: usize,
+    /// The index the item ends up at, counted after it was removed
+    pub to: usize,
+}
+
+/// Finds the fewest moves that turn the order `old` into `new`, e.g. to sync the result of a
+/// drag to an API that only accepts "move item X to position Y" calls. Items that are part of
+/// the longest run of items whose relative order didn't change stay where they are, all others
+/// are moved. The moves have to be applied in order, each one to the order left by the previous.
+///
+/// The ids are expected to be unique. Ids that are only in one of the orders are ignored, so the
+/// indices only count the ids that are in both.
+///
+/// # Example
+///
+/// ```rust
+/// use egui::Id;
+/// use egui_dnd::utils::{diff_order, Move};
+///
+/// let [a, b, c, d] = ["a", "b", "c", "d"].map(Id::new);
+/// let moves = diff_order(&[a, b, c, d], &[b, c, d, a]);
+/// assert_eq!(moves, [Move { id: a, from: 0, to: 3 }]);
+///
+/// let mut order = vec![a, b, c, d];
+/// for step in diff_order(&order, &[d, b, a, c]) {
+///     let id = order.remove(step.from);
+///     order.insert(step.to, id);
+/// }
+/// assert_eq!(order, [d, b, a, c]);
+/// ```
+pub fn diff_order(old: &[Id], new: &[Id]) -> Vec<Move> {
+    let in_old: HashSet<Id> = old.iter().copied().collect();
+    let new: Vec<Id> = new
+        .iter()
+        .copied()
+        .filter(|id| in_old.contains(id))
+        .collect();
+    let in_new: HashSet<Id> = new.iter().copied().collect();
+    let mut current: Vec<Id> = old
+        .iter()
+        .copied()
+        .filter(|id| in_new.contains(id))
+        .collect();
+    let indices: HashMap<Id, usize> = current
+        .iter()
+        .enumerate()
+        .map(|(idx, id)| (*id, idx))
+        .collect();
+    let positions: Vec<usize> = new.iter().map(|id| indices[id]).collect();
+    let keep = longest_increasing(&positions);
+
+    let mut moves = Vec::new();
+    for (idx, id) in new.iter().enumerate() {
+        if keep[idx] {
+            continue;
+        }
+        let Some(from) = current.iter().position(|item| item == id) else {
+            continue;
+        };
+        current.remove(from);
+        // Each moved item goes right behind the item it follows in the new order
+        let to = idx
+            .checked_sub(1)
+            .and_then(|previous| current.iter().position(|item| *item == new[previous]))
+            .map_or(0, |previous| previous + 1);
+        current.insert(to, *id);
+        if from != to {
+            moves.push(Move { id: *id, from, to });
+        }
+    }
+    moves
+}
+
+/// Marks the values that are part of the longest strictly increasing subsequence of `values`
+fn longest_increasing(values: &[usize]) -> Vec<bool> {
+    // The index of the smallest value that ends an increasing subsequence of each length
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![None; values.len()];
+    for (idx, value) in values.iter().enumerate() {
+        let len = tails.partition_point(|tail| values[*tail] < *value);
+        previous[idx] = len.checked_sub(1).map(|prev| tails[prev]);
+        if len == tails.len() {
+            tails.push(idx);
+        } else {
+            tails[len] = idx;
+        }
+    }
+    let mut keep = vec![false; values.len()];
+    let mut next = tails.last().copied();
+    while let Some(idx) = next {
+        keep[idx] = true;
+        next = previous[idx];
+    }
+    keep
+}
+
+/// Removes the state of all drag and drop lists from egui's memory.
+/// Use [crate::Dnd::forget] to remove the state of a single list.
+/// This is useful if you create many short lived lists with unique ids, whose state would
+/// otherwise stay in memory forever. Any drag in progress is cancelled.
+pub fn clear_dnd_state(ctx: &Context) {
+    ctx.data_mut(|data| {
+        data.remove_by_type::<DragDropUi>();
+        data.remove_by_type::<ListScopes>();
+    });
+}
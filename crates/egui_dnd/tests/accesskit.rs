@@ -0,0 +1,121 @@
+//! Tests for the AccessKit nodes and announcements of a list.
+
+mod common;
+
+use egui::{Align, Event, Key, Layout, Vec2};
+
+use common::Harness;
+
+#[test]
+fn accesskit_announcements() {
+    use egui::accesskit::{NodeId, Role};
+
+    fn announcement(harness: &Harness) -> Option<String> {
+        let update = harness.output.platform_output.accesskit_update.as_ref()?;
+        update
+            .nodes
+            .iter()
+            .map(|(_, node)| node)
+            .find(|node| node.role() == Role::Status)
+            .and_then(|node| node.name().map(str::to_owned))
+    }
+
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.ctx.enable_accesskit();
+    harness.focus(1);
+    harness.press(&[Key::Space]);
+    harness.frame(vec![]);
+    assert_eq!(
+        announcement(&harness).as_deref(),
+        Some("Grabbed item 2 of 5")
+    );
+    harness.press(&[Key::ArrowDown]);
+    harness.frame(vec![]);
+    assert_eq!(
+        announcement(&harness).as_deref(),
+        Some("Moved to position 3 of 5")
+    );
+    harness.press(&[Key::Enter]);
+    assert_eq!(
+        announcement(&harness).as_deref(),
+        Some("Dropped item at position 3 of 5")
+    );
+    assert_eq!(harness.order(), vec![0, 2, 1, 3, 4]);
+
+    // The list items can be moved with actions
+    harness.frame(vec![]);
+    let update = harness
+        .output
+        .platform_output
+        .accesskit_update
+        .as_ref()
+        .unwrap();
+    let (item, _) = update
+        .nodes
+        .iter()
+        .find(|(_, node)| node.role() == Role::ListItem && node.position_in_set() == Some(3))
+        .expect("item node not found");
+    let item: NodeId = *item;
+    harness.frame(vec![Event::AccessKitActionRequest(
+        egui::accesskit::ActionRequest {
+            action: egui::accesskit::Action::CustomAction,
+            target: item,
+            data: Some(egui::accesskit::ActionData::CustomAction(0)),
+        },
+    )]);
+    harness.press(&[]);
+    assert_eq!(harness.order(), vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn accesskit_actions() {
+    use egui::accesskit::{Action, ActionData, ActionRequest, Node, NodeId, Role};
+
+    fn item_node(harness: &Harness, position: usize) -> (NodeId, Node) {
+        let update = harness.output.platform_output.accesskit_update.as_ref();
+        update
+            .and_then(|update| {
+                update.nodes.iter().find(|(_, node)| {
+                    node.role() == Role::ListItem && node.position_in_set() == Some(position)
+                })
+            })
+            .cloned()
+            .expect("item node not found")
+    }
+
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.ctx.enable_accesskit();
+    harness.frame(vec![]);
+    harness.frame(vec![]);
+
+    let (item, node) = item_node(&harness, 2);
+    assert_eq!(node.numeric_value(), Some(2.0));
+    assert_eq!(node.max_numeric_value(), Some(5.0));
+    harness.frame(vec![Event::AccessKitActionRequest(ActionRequest {
+        action: Action::CustomAction,
+        target: item,
+        data: Some(ActionData::CustomAction(3)),
+    })]);
+    harness.press(&[]);
+    assert_eq!(harness.order(), vec![0, 2, 3, 4, 1]);
+
+    // Setting the value moves the item to that position
+    harness.frame(vec![]);
+    let (item, _) = item_node(&harness, 5);
+    harness.frame(vec![Event::AccessKitActionRequest(ActionRequest {
+        action: Action::SetValue,
+        target: item,
+        data: Some(ActionData::NumericValue(2.0)),
+    })]);
+    harness.press(&[]);
+    assert_eq!(harness.order(), vec![0, 1, 2, 3, 4]);
+
+    // The grabbed item describes how to move it
+    harness.focus(0);
+    harness.press(&[Key::Space]);
+    harness.frame(vec![]);
+    let (_, node) = item_node(&harness, 1);
+    assert!(node
+        .description()
+        .is_some_and(|description| description.starts_with("Grabbed")));
+}
@@ -0,0 +1,439 @@
+//! The harness shared by the integration tests.
+//!
+//! It runs egui headless and simulates pointer and keyboard input, so the tests don't depend on
+//! any platform integration.
+
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::hash::{Hash, Hasher};
+
+use egui::{
+    Align, CentralPanel, Context, DroppedFile, Event, HoveredFile, Id, Key, Layout, Modifiers,
+    PointerButton, Pos2, RawInput, Rect, ScrollArea, Sense, Vec2,
+};
+
+use egui_dnd::{
+    dnd, test_utils::DragSnapshot, CancellationReason, CommitStrategy, DragDropConfig,
+    DragDropResponse, DragMode, DragUpdate, DropDecision, ItemState,
+};
+
+pub struct TestItem {
+    pub id: usize,
+    pub size: Vec2,
+}
+
+// Only the id should identify the item, the size is just used for layout
+impl Hash for TestItem {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+pub struct Harness {
+    pub ctx: Context,
+    pub time: f64,
+    pub layout: Layout,
+    /// If set, the items are shown with [egui_dnd::Dnd::show_vec_sized]
+    pub fixed_size: Option<Vec2>,
+    pub items: Vec<TestItem>,
+    pub rects: Vec<(usize, Rect)>,
+    /// The [ItemState] each item was shown with in the last frame
+    pub states: Vec<(usize, ItemState)>,
+    /// The clip rect of the ui each item was shown in in the last frame
+    pub clip_rects: Vec<(usize, Rect)>,
+    pub config: DragDropConfig,
+    pub animation_time: f32,
+    pub output: egui::FullOutput,
+    /// Passed to [egui_dnd::Dnd::begin_drag] in the next frame
+    pub begin_drag: Option<(usize, DragMode)>,
+    /// Passed to [egui_dnd::Dnd::snapshot_drag] in every frame
+    pub snapshot: Option<DragSnapshot>,
+    /// Passed to [egui_dnd::Dnd::cancel_drag] in the next frame
+    pub cancel_drag: Option<CancellationReason>,
+    /// Whether the window has focus
+    pub focused: bool,
+    /// If set, the list is shown in a vertical [ScrollArea] with this max height
+    pub scroll_height: Option<f32>,
+    /// The modifier keys held in every frame
+    pub modifiers: Modifiers,
+    /// Lifecycle callbacks of [egui_dnd::Dnd] that were called, e.g. `start 1`
+    pub callbacks: RefCell<Vec<String>>,
+    /// Passed to [egui_dnd::Dnd::allow_drop]
+    pub allow_drop: Option<fn(usize, usize) -> bool>,
+    /// Passed to [egui_dnd::Dnd::dead_zone]
+    pub dead_zone: Option<fn(usize, usize) -> bool>,
+    /// Passed to [egui_dnd::Dnd::locked]
+    pub locked: Option<fn(usize) -> bool>,
+    /// Passed to [egui_dnd::Dnd::draggable]
+    pub draggable: Option<fn(usize) -> bool>,
+    /// Passed to [egui_dnd::Dnd::enabled]
+    pub enabled: bool,
+    /// Uses [egui_dnd::Dnd::show_vec_keyed] instead of relying on the [Hash] impl of [TestItem]
+    pub keyed: bool,
+    /// Draws the items with [egui_dnd::Handle::fill_item], with a widget sensing drags in the
+    /// top left corner of each item
+    pub fill_item: bool,
+    /// Passed to [egui_dnd::Handle::enabled] with the id of the item
+    pub handle_enabled: Option<fn(usize) -> bool>,
+    /// Passed to [egui_dnd::Dnd::commit_strategy]
+    pub commit_strategy: CommitStrategy,
+    /// Passed to [egui_dnd::Dnd::validate_drop]
+    pub validate_drop: Option<fn(&DragUpdate) -> DropDecision>,
+    /// Calls [egui_dnd::Dnd::accept_payload] with `u32` payloads
+    pub accept_payload: bool,
+    /// Calls [egui_dnd::Dnd::accept_files]
+    pub accept_files: bool,
+    /// Files dragged over the window in every frame
+    pub hovered_files: Vec<HoveredFile>,
+    /// Files dropped on the window in the next frame
+    pub dropped_files: Vec<DroppedFile>,
+    /// Passed to [egui_dnd::Dnd::persist_order]
+    pub persist_order: Option<&'static str>,
+    /// Passed to [egui_dnd::Dnd::with_history]
+    pub history: Option<usize>,
+    /// Passed to [egui_dnd::Dnd::filtered]
+    pub filtered: bool,
+    /// Passed to [egui_dnd::Dnd::debug]
+    pub debug: bool,
+    /// The size of the window
+    pub screen_size: Vec2,
+    /// Passed to [ScrollArea::vertical_scroll_offset] in the next frame
+    pub scroll_offset: Option<f32>,
+    /// If set, replaces the [egui::style::Spacing::item_spacing] of the list
+    pub item_spacing: Option<Vec2>,
+}
+
+impl Harness {
+    pub fn new(layout: Layout, sizes: &[Vec2]) -> Self {
+        Self {
+            ctx: Context::default(),
+            time: 0.0,
+            layout,
+            fixed_size: None,
+            items: sizes
+                .iter()
+                .enumerate()
+                .map(|(id, size)| TestItem { id, size: *size })
+                .collect(),
+            rects: Vec::new(),
+            states: Vec::new(),
+            clip_rects: Vec::new(),
+            config: DragDropConfig {
+                keyboard_reordering: true,
+                ..DragDropConfig::mouse()
+            },
+            animation_time: 0.0,
+            output: Default::default(),
+            begin_drag: None,
+            snapshot: None,
+            cancel_drag: None,
+            focused: true,
+            scroll_height: None,
+            modifiers: Modifiers::NONE,
+            callbacks: RefCell::default(),
+            allow_drop: None,
+            dead_zone: None,
+            locked: None,
+            draggable: None,
+            enabled: true,
+            keyed: false,
+            fill_item: false,
+            handle_enabled: None,
+            commit_strategy: CommitStrategy::Live,
+            validate_drop: None,
+            accept_payload: false,
+            accept_files: false,
+            hovered_files: Vec::new(),
+            dropped_files: Vec::new(),
+            persist_order: None,
+            history: None,
+            filtered: false,
+            debug: false,
+            screen_size: Vec2::splat(1000.0),
+            scroll_offset: None,
+            item_spacing: None,
+        }
+    }
+
+    pub fn sized(layout: Layout, count: usize, size: Vec2) -> Self {
+        let mut harness = Self::new(layout, &vec![size; count]);
+        harness.fixed_size = Some(size);
+        harness
+    }
+
+    pub fn order(&self) -> Vec<usize> {
+        self.items.iter().map(|item| item.id).collect()
+    }
+
+    pub fn center_of(&self, id: usize) -> Pos2 {
+        self.rects
+            .iter()
+            .find(|(item_id, _)| *item_id == id)
+            .map(|(_, rect)| rect.center())
+            .expect("item was not shown")
+    }
+
+    pub fn frame(&mut self, events: Vec<Event>) -> DragDropResponse {
+        self.time += 0.1;
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, self.screen_size)),
+            time: Some(self.time),
+            events,
+            modifiers: self.modifiers,
+            focused: self.focused,
+            hovered_files: self.hovered_files.clone(),
+            dropped_files: std::mem::take(&mut self.dropped_files),
+            ..Default::default()
+        };
+
+        let Self {
+            ctx,
+            layout,
+            fixed_size,
+            items,
+            rects,
+            states,
+            clip_rects,
+            config,
+            animation_time,
+            output,
+            begin_drag,
+            snapshot,
+            cancel_drag,
+            scroll_height,
+            callbacks,
+            allow_drop,
+            dead_zone,
+            locked,
+            draggable,
+            enabled,
+            keyed,
+            fill_item,
+            handle_enabled,
+            commit_strategy,
+            validate_drop,
+            accept_payload,
+            accept_files,
+            persist_order,
+            history,
+            filtered,
+            debug,
+            scroll_offset,
+            item_spacing,
+            ..
+        } = self;
+        let callbacks = &*callbacks;
+        let begin_drag = begin_drag.take();
+        let cancel_drag = cancel_drag.take();
+        let scroll_offset = scroll_offset.take();
+        rects.clear();
+        states.clear();
+        clip_rects.clear();
+
+        let mut response = None;
+        *output = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let mut list_ui = |ui: &mut egui::Ui| {
+                    if let Some(item_spacing) = *item_spacing {
+                        ui.spacing_mut().item_spacing = item_spacing;
+                    }
+                    let log = |event: String| callbacks.borrow_mut().push(event);
+                    let mut dnd = dnd(ui, "layout_test")
+                        .with_animation_time(*animation_time)
+                        .with_mouse_config(config.clone())
+                        .enabled(*enabled)
+                        .commit_strategy(*commit_strategy)
+                        .filtered(*filtered)
+                        .debug(*debug)
+                        .on_drag_start(move |_, index| log(format!("start {index}")))
+                        .on_hover_index_changed(move |index| log(format!("hover {index}")))
+                        .on_drop(move |update| log(format!("drop {}", update.target_index())))
+                        .on_cancel(move |reason| log(format!("cancel {reason:?}")));
+                    if let Some((id, mode)) = begin_drag {
+                        dnd = dnd.begin_drag(Id::new(id), mode);
+                    }
+                    if let Some(snapshot) = *snapshot {
+                        dnd = dnd.snapshot_drag(snapshot);
+                    }
+                    if let Some(reason) = cancel_drag.clone() {
+                        dnd = dnd.cancel_drag(reason);
+                    }
+                    if let Some(allow_drop) = *allow_drop {
+                        dnd = dnd.allow_drop(allow_drop);
+                    }
+                    if let Some(dead_zone) = *dead_zone {
+                        dnd = dnd.dead_zone(dead_zone);
+                    }
+                    if let Some(locked) = *locked {
+                        dnd = dnd.locked(locked);
+                    }
+                    if let Some(draggable) = *draggable {
+                        dnd = dnd.draggable(draggable);
+                    }
+                    if let Some(validate_drop) = *validate_drop {
+                        dnd = dnd.validate_drop(validate_drop);
+                    }
+                    if *accept_payload {
+                        dnd = dnd.accept_payload::<u32>();
+                    }
+                    if *accept_files {
+                        dnd = dnd.accept_files();
+                    }
+                    if let Some(capacity) = *history {
+                        dnd = dnd.with_history(capacity);
+                    }
+                    if let Some(storage_key) = *persist_order {
+                        dnd = dnd.persist_order(storage_key);
+                    }
+                    let mut item_ui = |ui: &mut egui::Ui,
+                                       item: &mut TestItem,
+                                       handle: egui_dnd::Handle,
+                                       state| {
+                        let handle = match handle_enabled {
+                            Some(enabled) => handle.enabled(enabled(item.id)),
+                            None => handle,
+                        };
+                        let response = if *fill_item {
+                            handle.fill_item(ui, |ui| {
+                                let (rect, _) = ui.allocate_exact_size(item.size, Sense::hover());
+                                let corner = Rect::from_min_size(rect.min, rect.size() / 4.0);
+                                ui.interact(corner, Id::new(("widget", item.id)), Sense::drag());
+                            })
+                        } else {
+                            handle.ui(ui, |ui| {
+                                ui.allocate_exact_size(item.size, Sense::hover());
+                            })
+                        };
+                        rects.push((item.id, response.rect));
+                        states.push((item.id, state));
+                        clip_rects.push((item.id, ui.clip_rect()));
+                    };
+                    response = Some(if let Some(size) = fixed_size {
+                        dnd.show_vec_sized(items, *size, &mut item_ui)
+                    } else if *keyed {
+                        dnd.show_vec_keyed(items, |item| item.id, &mut item_ui)
+                    } else {
+                        dnd.show_vec(items, &mut item_ui)
+                    });
+                };
+                if let Some(height) = scroll_height {
+                    let mut scroll_area = ScrollArea::vertical().max_height(*height);
+                    if let Some(offset) = scroll_offset {
+                        scroll_area = scroll_area.vertical_scroll_offset(offset);
+                    }
+                    scroll_area.show(ui, |ui| ui.with_layout(*layout, &mut list_ui));
+                } else {
+                    ui.with_layout(*layout, list_ui);
+                }
+            });
+        });
+        response.expect("dnd was not shown")
+    }
+
+    /// Grabs the item with id `source` at its center and drops it at the center of the item with id `target`.
+    /// Returns the response of the frame the item was dropped in.
+    pub fn drag(&mut self, source: usize, target: usize) -> DragDropResponse {
+        let target = self.hold(source, target);
+        let response = self.frame(vec![button(target, false)]);
+        self.frame(vec![]);
+        response
+    }
+
+    /// Like [Harness::drag], but keeps holding the item over the target.
+    /// Returns the pointer position.
+    pub fn hold(&mut self, source: usize, target: usize) -> Pos2 {
+        self.frame(vec![]);
+        let start = self.center_of(source);
+        let target = self.center_of(target);
+
+        self.frame(vec![Event::PointerMoved(start), button(start, true)]);
+        // Move a bit to get above the click threshold
+        let nudged = start + (target - start).normalized() * 4.0;
+        self.frame(vec![Event::PointerMoved(nudged)]);
+        for _ in 0..4 {
+            self.frame(vec![Event::PointerMoved(target)]);
+        }
+        target
+    }
+}
+
+impl Harness {
+    /// Moves the keyboard focus to the handle of the item at `index` by pressing tab
+    pub fn focus(&mut self, index: usize) {
+        self.frame(vec![]);
+        for _ in 0..=index {
+            self.frame(vec![key(Key::Tab)]);
+        }
+    }
+
+    pub fn press(&mut self, keys: &[Key]) -> DragDropResponse {
+        let mut response = self.frame(vec![]);
+        for k in keys {
+            response = self.frame(vec![key(*k)]);
+        }
+        response
+    }
+}
+
+pub fn key(key: Key) -> Event {
+    Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: Modifiers::NONE,
+    }
+}
+
+pub fn button(pos: Pos2, pressed: bool) -> Event {
+    Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Modifiers::NONE,
+    }
+}
+
+pub fn layouts() -> [(&'static str, Layout); 2] {
+    [
+        ("top_down", Layout::top_down(Align::Min)),
+        ("left_to_right", Layout::left_to_right(Align::Min)),
+    ]
+}
+
+/// Layouts with a reversed main direction, where the first item is at the bottom or the right
+pub fn reversed_layouts() -> [(&'static str, Layout); 2] {
+    [
+        ("bottom_up", Layout::bottom_up(Align::Min)),
+        ("right_to_left", Layout::right_to_left(Align::Min)),
+    ]
+}
+
+pub fn item_sizes(layout: Layout, lengths: &[f32]) -> Vec<Vec2> {
+    lengths
+        .iter()
+        .map(|length| {
+            if layout.main_dir().is_horizontal() {
+                Vec2::new(*length, 30.0)
+            } else {
+                Vec2::new(120.0, *length)
+            }
+        })
+        .collect()
+}
+
+/// Checks that `result` is `original` with only `source` moved, and returns the new index of `source`.
+pub fn moved_index(original: &[usize], result: &[usize], source: usize) -> Option<usize> {
+    let without_source = |order: &[usize]| {
+        order
+            .iter()
+            .copied()
+            .filter(|id| *id != source)
+            .collect::<Vec<_>>()
+    };
+    if without_source(original) != without_source(result) || original.len() != result.len() {
+        return None;
+    }
+    result.iter().position(|id| *id == source)
+}
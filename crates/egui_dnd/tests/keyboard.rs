@@ -0,0 +1,106 @@
+//! Tests for reordering and cutting and pasting items with the keyboard.
+
+mod common;
+
+use egui::{Align, Event, Key, Layout, Vec2};
+
+use egui_dnd::{CancellationReason, DragDropConfig};
+
+use common::{layouts, reversed_layouts, Harness};
+
+#[test]
+fn keyboard_reorder_follows_reversed_layouts() {
+    let forward_keys = [Key::ArrowUp, Key::ArrowLeft];
+    for ((name, layout), forward) in reversed_layouts().into_iter().zip(forward_keys) {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.focus(1);
+        let response = harness.press(&[Key::Space, forward, forward, Key::Enter]);
+        assert!(response.is_drag_finished(), "{name}: drag didn't finish");
+        assert_eq!(harness.order(), vec![0, 2, 3, 1, 4], "{name}");
+    }
+}
+
+#[test]
+fn keyboard_reorder() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.focus(1);
+        let response = harness.press(&[Key::Space, Key::ArrowDown, Key::ArrowRight, Key::Enter]);
+        assert!(response.is_drag_finished(), "{name}: drag didn't finish");
+        assert_eq!(harness.order(), vec![0, 2, 3, 1, 4], "{name}");
+
+        // The focus stays on the moved item, so we can move it again
+        harness.press(&[
+            Key::Enter,
+            Key::ArrowUp,
+            Key::ArrowLeft,
+            Key::ArrowUp,
+            Key::Space,
+        ]);
+        assert_eq!(harness.order(), vec![1, 0, 2, 3, 4], "{name}");
+
+        // Moving past the end of the list stops at the last slot
+        harness.press(&[Key::Space]);
+        harness.press(&[Key::ArrowDown; 8]);
+        let response = harness.press(&[Key::Enter]);
+        assert_eq!(harness.order(), vec![0, 2, 3, 4, 1], "{name}");
+        let update = response.final_update().expect("no final update");
+        assert_eq!((update.from, update.to), (4, 5), "{name}");
+    }
+}
+
+#[test]
+fn keyboard_reordering_is_opt_in() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.config = DragDropConfig::mouse();
+    harness.focus(1);
+    let response = harness.press(&[Key::Space, Key::ArrowDown, Key::Enter]);
+    assert!(!response.is_dragging());
+    assert!(!response.is_drag_finished());
+    assert_eq!(harness.order(), vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn keyboard_escape_puts_item_back() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 4]);
+        harness.focus(2);
+        harness.press(&[Key::Enter, Key::ArrowUp, Key::ArrowUp]);
+        // The list is updated while the item is moved
+        assert_eq!(harness.order(), vec![2, 0, 1, 3], "{name}");
+        let response = harness.press(&[Key::Escape]);
+        assert!(!response.is_drag_finished(), "{name}");
+        assert_eq!(
+            response.cancellation_reason(),
+            Some(CancellationReason::CancelKey),
+            "{name}"
+        );
+        assert_eq!(harness.order(), vec![0, 1, 2, 3], "{name}");
+    }
+}
+
+#[test]
+fn cut_and_paste() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.focus(1);
+        harness.frame(vec![Event::Cut]);
+        harness.frame(vec![]);
+        let cut: Vec<_> = harness
+            .states
+            .iter()
+            .filter(|(_, state)| state.cut)
+            .map(|(id, _)| *id)
+            .collect();
+        assert_eq!(cut, vec![1], "{name}");
+
+        harness.press(&[Key::Tab, Key::Tab]);
+        let response = harness.frame(vec![Event::Paste(String::new())]);
+        assert!(response.is_drag_finished(), "{name}");
+        let update = response.final_update().expect("no final update");
+        assert_eq!((update.from, update.to), (1, 3), "{name}");
+        assert_eq!(harness.order(), vec![0, 2, 1, 3, 4], "{name}");
+        harness.frame(vec![]);
+        assert!(harness.states.iter().all(|(_, state)| !state.cut), "{name}");
+    }
+}
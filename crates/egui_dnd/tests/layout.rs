@@ -0,0 +1,485 @@
+//! Layout test suite for the drop target calculation.
+//!
+//! Run it with `cargo test -p egui_dnd --test layout`. If you are reporting a layout related
+//! bug, please include the output of this suite.
+
+mod common;
+
+use std::cell::RefCell;
+
+use egui::{Align, Color32, Event, Id, Key, Layout, Pos2, Sense, Vec2};
+use proptest::prelude::*;
+
+use egui_dnd::{dnd, test_utils::DragSimulator, CancellationReason, DragDropConfig};
+
+use common::{button, item_sizes, key, layouts, moved_index, reversed_layouts, Harness, TestItem};
+
+#[test]
+fn golden_uniform_items() {
+    // Equally sized items end up in the same place along either axis
+    let cases = [
+        (0, 3, [1, 2, 3, 0, 4]),
+        (4, 1, [0, 4, 1, 2, 3]),
+        (1, 2, [0, 2, 1, 3, 4]),
+        (2, 1, [0, 2, 1, 3, 4]),
+    ];
+    for (name, layout) in layouts() {
+        for (source, target, expected) in cases {
+            let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+            let response = harness.drag(source, target);
+            assert!(response.is_drag_finished(), "{name}: drag didn't finish");
+            assert_eq!(
+                harness.order(),
+                expected,
+                "{name}: dragging {source} onto {target}"
+            );
+        }
+    }
+}
+
+#[test]
+fn golden_mixed_sizes() {
+    let lengths = [10.0, 50.0, 22.0, 70.0, 15.0, 40.0];
+    let cases = [
+        (0, 3, [1, 2, 0, 3, 4, 5]),
+        (5, 2, [0, 1, 2, 5, 3, 4]),
+        (3, 0, [0, 3, 1, 2, 4, 5]),
+        (1, 5, [0, 2, 3, 4, 1, 5]),
+    ];
+    for (name, layout) in layouts() {
+        for (source, target, expected) in cases {
+            let mut harness = Harness::new(layout, &item_sizes(layout, &lengths));
+            harness.drag(source, target);
+            assert_eq!(
+                harness.order(),
+                expected,
+                "{name}: dragging {source} onto {target}"
+            );
+        }
+    }
+}
+
+#[test]
+fn golden_wrapped_items() {
+    let layout = Layout::left_to_right(Align::Min).with_main_wrap(true);
+    let cases = [
+        (0, 3, [1, 2, 3, 0, 4, 5, 6, 7]),
+        (7, 2, [0, 1, 7, 2, 3, 4, 5, 6]),
+        (1, 6, [0, 2, 3, 4, 5, 6, 1, 7]),
+    ];
+    for (source, target, expected) in cases {
+        // 3 items per row
+        let mut harness = Harness::sized(layout, 8, Vec2::new(300.0, 30.0));
+        let response = harness.drag(source, target);
+        assert!(response.is_drag_finished());
+        assert_eq!(harness.order(), expected, "dragging {source} onto {target}");
+    }
+}
+
+#[test]
+fn wrapped_items_own_the_spacing_around_them() {
+    let layout = Layout::left_to_right(Align::Min).with_main_wrap(true);
+    for (offset, expected) in [(-10.0, [1, 0, 2]), (10.0, [1, 2, 0])] {
+        let mut harness = Harness::new(layout, &[Vec2::new(60.0, 30.0); 3]);
+        harness.item_spacing = Some(Vec2::splat(40.0));
+        harness.frame(vec![]);
+        let rect_of = |harness: &Harness, id: usize| {
+            harness
+                .rects
+                .iter()
+                .find(|(item, _)| *item == id)
+                .unwrap()
+                .1
+        };
+        // The point in the spacing between item 1 and 2, closer to one of them
+        let between = (rect_of(&harness, 1).max.x + rect_of(&harness, 2).min.x) / 2.0 + offset;
+        let start = harness.center_of(0);
+        let nudge = Vec2::new(4.0, 0.0);
+        harness.frame(vec![Event::PointerMoved(start), button(start, true)]);
+        harness.frame(vec![Event::PointerMoved(start + nudge)]);
+        // Jump there without passing over the items, so no item was hovered before. The center
+        // of the held item is behind the pointer by the nudge.
+        let pos = Pos2::new(between, start.y) + nudge;
+        for _ in 0..4 {
+            harness.frame(vec![Event::PointerMoved(pos)]);
+        }
+        harness.frame(vec![button(pos, false)]);
+        harness.frame(vec![]);
+        assert_eq!(harness.order(), expected, "offset {offset}");
+    }
+}
+
+#[test]
+fn reversed_layouts_match_forward_layouts() {
+    for ((name, reversed), (_, forward)) in reversed_layouts().into_iter().zip(layouts()) {
+        for (source, target) in [(0, 3), (4, 1)] {
+            let mut forward = Harness::new(forward, &[Vec2::splat(30.0); 5]);
+            forward.drag(source, target);
+            let mut reversed = Harness::new(reversed, &[Vec2::splat(30.0); 5]);
+            let response = reversed.drag(source, target);
+            assert!(response.is_drag_finished(), "{name}: drag didn't finish");
+            assert_eq!(
+                reversed.order(),
+                forward.order(),
+                "{name}: dragging {source} onto {target}"
+            );
+        }
+    }
+}
+
+#[test]
+fn enter_exit_animations() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 4]);
+        harness.config.enter_exit_animations = true;
+        harness.animation_time = 0.35;
+        harness.frame(vec![]);
+        let removed_pos = harness.center_of(1);
+        let next_pos = harness.center_of(2);
+
+        harness.items.remove(1);
+        harness.frame(vec![]);
+        // The gap of the removed item is still there
+        assert_eq!(harness.center_of(2), next_pos, "{name}");
+        for _ in 0..4 {
+            harness.frame(vec![]);
+        }
+        assert_eq!(harness.center_of(2), removed_pos, "{name}");
+
+        harness.items.insert(
+            1,
+            TestItem {
+                id: 1,
+                size: Vec2::splat(30.0),
+            },
+        );
+        harness.frame(vec![]);
+        // The new item starts collapsed
+        assert_eq!(harness.center_of(2), removed_pos, "{name}");
+        for _ in 0..4 {
+            harness.frame(vec![]);
+        }
+        assert_eq!(harness.center_of(2), next_pos, "{name}");
+    }
+}
+
+#[test]
+fn list_changes_during_drag() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.hold(1, 3);
+        // An item is added in front and another one removed, e.g. by a sync
+        harness.items.insert(
+            0,
+            TestItem {
+                id: 5,
+                size: Vec2::splat(30.0),
+            },
+        );
+        harness.items.retain(|item| item.id != 4);
+        harness.frame(vec![]);
+        // The item is put back in front of the item that was behind it
+        harness.frame(vec![key(Key::Escape)]);
+        assert_eq!(harness.order(), vec![5, 0, 1, 2, 3], "{name}");
+
+        // Removing the hovered item doesn't produce an update past the end of the list
+        let pos = harness.hold(1, 3);
+        harness.items.retain(|item| item.id < 3);
+        let response = harness.frame(vec![button(pos, false)]);
+        assert!(response.is_drag_finished(), "{name}");
+        let mut order = harness.order();
+        order.sort();
+        assert_eq!(order, vec![0, 1, 2], "{name}");
+    }
+}
+
+#[test]
+fn drag_continues_while_filtered_out() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.filtered = true;
+    let pos = harness.hold(1, 3);
+
+    // The filter hides the dragged item
+    let hidden_at = harness.items.iter().position(|item| item.id == 1).unwrap();
+    let hidden = harness.items.remove(hidden_at);
+    let response = harness.frame(vec![]);
+    assert!(response.is_dragging());
+    assert!(response.update.is_none());
+    assert_eq!(response.cancellation_reason(), None);
+
+    // Once it's shown again, the drag goes on and it can be dropped
+    harness.items.insert(hidden_at, hidden);
+    harness.frame(vec![]);
+    let response = harness.frame(vec![button(pos, false)]);
+    assert!(response.is_drag_finished());
+    assert_eq!(
+        response.final_update().map(|update| update.id),
+        Some(Id::new(1))
+    );
+
+    // Dropping it while it's hidden cancels the drag
+    let pos = harness.hold(1, 3);
+    harness.items.retain(|item| item.id != 1);
+    harness.frame(vec![]);
+    let response = harness.frame(vec![button(pos, false)]);
+    assert_eq!(
+        response.cancellation_reason(),
+        Some(CancellationReason::ListChanged)
+    );
+}
+
+#[test]
+fn window_resized_during_drag() {
+    let mut harness = Harness::new(Layout::top_down(Align::Center), &[Vec2::splat(30.0); 5]);
+    let pos = harness.hold(0, 2);
+    assert_eq!(harness.center_of(4).x, 500.0);
+
+    // The items jump to their new positions instead of slowly animating there
+    harness.animation_time = 10.0;
+    harness.screen_size = Vec2::splat(600.0);
+    let response = harness.frame(vec![]);
+    assert!(response.is_dragging());
+    assert_eq!(harness.center_of(4).x, 300.0);
+
+    let response = harness.frame(vec![button(pos, false)]);
+    assert!(response.is_drag_finished());
+}
+
+#[test]
+fn zoom_changed_during_drag() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    let pos = harness.hold(0, 2);
+    let order = harness.order();
+
+    harness.ctx.set_pixels_per_point(2.0);
+    let response = harness.frame(vec![]);
+    assert!(response.is_dragging());
+    assert_eq!(response.cancellation_reason(), None);
+
+    let response = harness.frame(vec![button(pos, false)]);
+    assert!(response.is_drag_finished());
+    assert_eq!(harness.order(), order);
+}
+
+#[test]
+fn zero_size_items() {
+    for (name, layout) in layouts() {
+        let sizes = item_sizes(layout, &[30.0, 30.0, 0.0, 30.0, 30.0]);
+        let mut harness = Harness::new(layout, &sizes);
+        let pos = harness.hold(0, 3);
+        let order = harness.order();
+
+        // Holding the item still doesn't move the gap over the hidden item and back
+        for _ in 0..5 {
+            harness.frame(vec![Event::PointerMoved(pos)]);
+            assert_eq!(harness.order(), order, "{name}");
+        }
+        let response = harness.frame(vec![button(pos, false)]);
+        assert!(response.is_drag_finished(), "{name}");
+        assert_eq!(harness.order(), order, "{name}");
+    }
+}
+
+#[test]
+fn allow_drop_snaps_to_allowed_slot() {
+    let layout = Layout::top_down(Align::Min);
+    let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+    harness.allow_drop = Some(|_, to| to != 3);
+    let response = harness.drag(0, 3);
+    assert!(response.is_drag_finished());
+    assert_eq!(harness.order(), vec![1, 2, 0, 3, 4]);
+
+    harness.allow_drop = Some(|_, _| false);
+    let response = harness.drag(1, 4);
+    assert!(!response.is_drag_finished());
+    assert_eq!(
+        response.cancellation_reason(),
+        Some(CancellationReason::InvalidDrop)
+    );
+    assert_eq!(harness.order(), vec![1, 2, 0, 3, 4]);
+
+    harness.allow_drop = Some(|_, to| to != 1);
+    harness.focus(0);
+    harness.press(&[Key::Space, Key::ArrowDown, Key::Enter]);
+    assert_eq!(harness.order(), vec![2, 0, 1, 3, 4]);
+}
+
+#[test]
+fn dead_zone_keeps_items_together() {
+    let layout = Layout::top_down(Align::Min);
+    let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+    harness.dead_zone = Some(|before, after| (before, after) == (0, 1));
+    // The gap between the first two items never opens, the item snaps to the next gap
+    let response = harness.drag(4, 1);
+    assert!(response.is_drag_finished());
+    assert_eq!(harness.order(), vec![0, 1, 4, 2, 3]);
+
+    // The items themselves can still be moved
+    harness.drag(1, 0);
+    assert_eq!(harness.order(), vec![1, 0, 4, 2, 3]);
+}
+
+#[test]
+fn z_index_paints_items_above_their_neighbors() {
+    let colors = [Color32::RED, Color32::GREEN, Color32::BLUE];
+    let painted = RefCell::new(Vec::new());
+    let mut show = |ui: &mut egui::Ui| {
+        let response = dnd(ui, "z_index").show_custom(|ui, iter| {
+            for (idx, color) in colors.iter().enumerate() {
+                let z_index = if idx == 0 { 1 } else { 0 };
+                iter.next(ui, Id::new(idx), idx, true, |ui, item| {
+                    item.z_index(z_index).ui(ui, |ui, handle, _state| {
+                        handle.ui(ui, |ui| {
+                            let (rect, _) =
+                                ui.allocate_exact_size(Vec2::splat(30.0), Sense::hover());
+                            ui.painter().rect_filled(rect, 0.0, *color);
+                        });
+                    })
+                });
+            }
+        });
+        // The shapes are handed to the backend at the end of the frame, so look at them now
+        *painted.borrow_mut() = ui.ctx().graphics(|graphics| {
+            graphics
+                .get(ui.layer_id())
+                .into_iter()
+                .flat_map(|list| list.all_entries())
+                .filter_map(|clipped| match &clipped.shape {
+                    egui::Shape::Rect(rect) if colors.contains(&rect.fill) => Some(rect.fill),
+                    _ => None,
+                })
+                .collect()
+        });
+        response
+    };
+    let mut simulator = DragSimulator::new();
+    simulator.frame(vec![], &mut show);
+
+    assert_eq!(
+        *painted.borrow(),
+        vec![Color32::GREEN, Color32::BLUE, Color32::RED]
+    );
+}
+
+#[test]
+fn slot_snapping_pulls_the_dragged_item_toward_its_slot() {
+    // Returns how far the held item is from the gap it would be dropped in
+    let distance_to_slot = |slot_snapping| {
+        let layout = Layout::top_down(Align::Min);
+        let mut harness = Harness::new(layout, &[Vec2::new(120.0, 30.0); 4]);
+        harness.config = DragDropConfig {
+            slot_snapping,
+            animations: false,
+            ..DragDropConfig::mouse()
+        };
+        harness.frame(vec![]);
+        let rect_of = |harness: &Harness, id: usize| {
+            harness
+                .rects
+                .iter()
+                .find(|(item, _)| *item == id)
+                .unwrap()
+                .1
+        };
+        let gap = rect_of(&harness, 2).min;
+        let start = harness.center_of(0);
+        harness.frame(vec![Event::PointerMoved(start), button(start, true)]);
+        harness.frame(vec![Event::PointerMoved(start + Vec2::new(0.0, 4.0))]);
+        let pos = harness.center_of(2) + Vec2::new(16.0, -4.0);
+        for _ in 0..4 {
+            harness.frame(vec![Event::PointerMoved(pos)]);
+        }
+        assert_eq!(harness.frame(vec![]).update.unwrap().target_index(), 2);
+        rect_of(&harness, 0).min - gap
+    };
+
+    let free = distance_to_slot(None);
+    assert_ne!(free, Vec2::ZERO);
+    let snapped = distance_to_slot(Some(0.5));
+    assert!(
+        (snapped - free * 0.5).length() < 0.01,
+        "{snapped:?} {free:?}"
+    );
+}
+
+#[test]
+fn floating_item_not_clipped() {
+    let layout = Layout::top_down(Align::Min);
+    let mut harness = Harness::new(layout, &[Vec2::new(120.0, 30.0); 20]);
+    harness.scroll_height = Some(60.0);
+    harness.config.auto_scroll = None;
+    let pos = harness.hold(0, 1);
+
+    // Far below the ScrollArea, the item is still fully visible
+    let below = Pos2::new(pos.x, 500.0);
+    harness.frame(vec![Event::PointerMoved(below)]);
+    let rect = harness.rects.iter().find(|(id, _)| *id == 0).unwrap().1;
+    let clip_rect = harness
+        .clip_rects
+        .iter()
+        .find(|(id, _)| *id == 0)
+        .unwrap()
+        .1;
+    assert!(
+        clip_rect.contains_rect(rect),
+        "{rect:?} is clipped by {clip_rect:?}"
+    );
+
+    harness.frame(vec![button(below, false)]);
+}
+
+fn list() -> impl Strategy<Value = (Vec<f32>, usize, usize)> {
+    prop::collection::vec(10.0f32..80.0, 2..8).prop_flat_map(|lengths| {
+        let len = lengths.len();
+        (Just(lengths), 0..len, 0..len)
+    })
+}
+
+proptest! {
+    #[test]
+    fn dropped_item_lands_next_to_target(
+        layout_idx in 0..2usize,
+        (lengths, source, target) in list(),
+    ) {
+        let (name, layout) = layouts()[layout_idx];
+        let mut harness = Harness::new(layout, &item_sizes(layout, &lengths));
+        let original = harness.order();
+        harness.drag(source, target);
+
+        let new_index = moved_index(&original, &harness.order(), source);
+        prop_assert!(new_index.is_some(), "{}: order of other items changed: {:?}", name, harness.order());
+        let new_index = new_index.unwrap();
+        // The item either takes the targets place or stops right before it
+        let allowed = if target > source {
+            target - 1..=target
+        } else if target < source {
+            target..=target + 1
+        } else {
+            source..=source
+        };
+        prop_assert!(allowed.contains(&new_index), "{}: {} ended up at {}", name, source, new_index);
+    }
+
+    #[test]
+    fn result_is_always_a_single_move(
+        layout_idx in 0..2usize,
+        (lengths, source, _) in list(),
+        offset in (-300.0f32..300.0, -300.0f32..300.0),
+    ) {
+        let (_, layout) = layouts()[layout_idx];
+        let mut harness = Harness::new(layout, &item_sizes(layout, &lengths));
+        harness.frame(vec![]);
+        let original = harness.order();
+
+        let start = harness.center_of(source);
+        let end = start + Vec2::from(offset);
+        harness.frame(vec![Event::PointerMoved(start), button(start, true)]);
+        harness.frame(vec![Event::PointerMoved(start + Vec2::splat(4.0))]);
+        for _ in 0..4 {
+            harness.frame(vec![Event::PointerMoved(end)]);
+        }
+        harness.frame(vec![button(end, false)]);
+
+        prop_assert!(moved_index(&original, &harness.order(), source).is_some());
+    }
+}
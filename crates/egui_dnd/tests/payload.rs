@@ -0,0 +1,132 @@
+//! Tests for dragging payloads and files in and out of a list.
+
+mod common;
+
+use egui::{Align, DragAndDrop, DroppedFile, Event, HoveredFile, Id, Key, Layout, Pos2, Vec2};
+
+use egui_dnd::{DndEvent, DndPayload, DragDropResponse};
+
+use common::{button, key, layouts, Harness};
+
+#[test]
+fn publishes_payload() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.hold(1, 3);
+    let payload = DragAndDrop::payload::<DndPayload>(&harness.ctx).expect("no payload");
+    assert!(payload.is_from("layout_test"));
+    assert_eq!(payload.item, Id::new(1));
+    assert_eq!(payload.index, 3);
+
+    // Cancelling the drag clears it
+    harness.frame(vec![key(Key::Escape)]);
+    assert!(DragAndDrop::payload::<DndPayload>(&harness.ctx).is_none());
+}
+
+#[test]
+fn accept_payload() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.accept_payload = true;
+        harness.frame(vec![]);
+
+        // Another widget starts dragging a payload outside of the list
+        let outside = Pos2::new(900.0, 900.0);
+        harness.frame(vec![Event::PointerMoved(outside), button(outside, true)]);
+        DragAndDrop::set_payload(&harness.ctx, 7_u32);
+        let response = harness.frame(vec![]);
+        assert_eq!(response.external_hover_index(), None, "{name}");
+
+        let target = harness.center_of(2);
+        let before = harness.center_of(3);
+        for _ in 0..3 {
+            harness.frame(vec![Event::PointerMoved(target)]);
+        }
+        let response = harness.frame(vec![]);
+        assert_eq!(response.external_hover_index(), Some(2), "{name}");
+        // A gap is opened for it
+        assert_ne!(harness.center_of(3), before, "{name}");
+        assert!(!response.is_dragging(), "{name}");
+
+        let response = harness.frame(vec![button(target, false)]);
+        let (index, payload) = response.dropped_payload::<u32>().expect("no payload");
+        assert_eq!((index, *payload), (2, 7), "{name}");
+        assert!(
+            DragAndDrop::payload::<u32>(&harness.ctx).is_none(),
+            "{name}"
+        );
+        assert_eq!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+
+        // Other payloads are ignored
+        harness.frame(vec![Event::PointerMoved(outside), button(outside, true)]);
+        DragAndDrop::set_payload(&harness.ctx, "text");
+        for _ in 0..3 {
+            harness.frame(vec![Event::PointerMoved(target)]);
+        }
+        assert_eq!(harness.frame(vec![]).external_hover_index(), None, "{name}");
+        harness.frame(vec![button(target, false)]);
+    }
+}
+
+#[test]
+fn file_drop() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.accept_files = true;
+        harness.frame(vec![]);
+        let target = harness.center_of(3);
+        let before = harness.center_of(4);
+
+        harness.hovered_files = vec![HoveredFile::default()];
+        for _ in 0..3 {
+            harness.frame(vec![Event::PointerMoved(target)]);
+        }
+        let response = harness.frame(vec![]);
+        assert_eq!(response.external_hover_index(), Some(3), "{name}");
+        assert_ne!(harness.center_of(4), before, "{name}");
+
+        harness.hovered_files.clear();
+        harness.dropped_files = vec![DroppedFile {
+            name: "song.mp3".to_owned(),
+            ..Default::default()
+        }];
+        let response = harness.frame(vec![]);
+        let drop = response.external_drop().expect("no external drop");
+        assert_eq!(drop.index, 3, "{name}");
+        assert_eq!(drop.files[0].name, "song.mp3", "{name}");
+
+        // The gap closes again
+        let response = harness.frame(vec![]);
+        assert_eq!(response.external_hover_index(), None, "{name}");
+        assert!(response.external_drop().is_none(), "{name}");
+        assert_eq!(harness.center_of(4), before, "{name}");
+    }
+}
+
+#[test]
+fn dragged_out_of_window() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    let is_dragged_out = |response: &DragDropResponse| {
+        response
+            .events()
+            .iter()
+            .any(|event| matches!(event, DndEvent::DraggedOutOfWindow { .. }))
+    };
+    let pos = harness.hold(1, 3);
+
+    let response = harness.frame(vec![Event::PointerGone]);
+    assert!(matches!(
+        response.events(),
+        [DndEvent::DraggedOutOfWindow { pointer_pos, .. }] if *pointer_pos == pos
+    ));
+    // It's only reported once, and the item is still dragged
+    let response = harness.frame(vec![]);
+    assert!(!is_dragged_out(&response));
+    assert!(response.is_dragging());
+
+    // Moving back in and out again reports it again
+    harness.frame(vec![Event::PointerMoved(pos)]);
+    let outside = Pos2::new(1200.0, pos.y);
+    let response = harness.frame(vec![Event::PointerMoved(outside)]);
+    assert!(is_dragged_out(&response));
+    harness.frame(vec![Event::PointerMoved(pos), button(pos, false)]);
+}
@@ -0,0 +1,839 @@
+//! Tests for dragging with the mouse or touch, and how that interacts with other widgets.
+
+mod common;
+
+use std::cell::RefCell;
+use std::time::Duration;
+
+use egui::{
+    Align, Color32, Event, Id, Key, LayerId, Layout, Modifiers, Order, PointerButton, Pos2, Rect,
+    ScrollArea, Sense, Vec2,
+};
+
+use egui_dnd::{
+    dnd, test_utils::DragSimulator, AutoScroll, CancellationReason, DndPayload, DragDropConfig,
+    DragMode, FocusLossBehavior,
+};
+
+use common::{button, key, layouts, moved_index, Harness};
+
+#[test]
+fn click_without_move_does_not_reorder() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 3]);
+        harness.frame(vec![]);
+        let pos = harness.center_of(1);
+        harness.frame(vec![Event::PointerMoved(pos), button(pos, true)]);
+        let response = harness.frame(vec![button(pos, false)]);
+        assert!(!response.is_drag_finished(), "{name}");
+        assert_eq!(harness.order(), vec![0, 1, 2], "{name}");
+    }
+}
+
+#[test]
+fn move_within_click_tolerance_is_a_click() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 3]);
+        harness.config.click_tolerance = 10.0;
+        harness.config.click_tolerance_timeout = Duration::from_secs(5);
+        harness.frame(vec![]);
+        let pos = harness.center_of(1);
+        harness.frame(vec![Event::PointerMoved(pos), button(pos, true)]);
+        let moved = pos + Vec2::new(4.0, 0.0);
+        let response = harness.frame(vec![Event::PointerMoved(moved)]);
+        assert!(!response.is_dragging(), "{name}");
+        let response = harness.frame(vec![button(moved, false)]);
+        assert!(!response.is_drag_finished(), "{name}");
+        assert!(
+            response.item_response(Id::new(1)).unwrap().clicked(),
+            "{name}"
+        );
+        assert_eq!(harness.order(), vec![0, 1, 2], "{name}");
+    }
+}
+
+#[test]
+fn drag_angle_drags_across_and_scrolls_along_the_list() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 3]);
+        harness.config = DragDropConfig {
+            drag_angle: Some(std::f32::consts::FRAC_PI_4),
+            ..DragDropConfig::touch_scroll()
+        };
+        let (along, across) = if layout.main_dir().is_vertical() {
+            (Vec2::Y, Vec2::X)
+        } else {
+            (Vec2::X, Vec2::Y)
+        };
+
+        // Swiping across the list starts the drag before the drag delay is over
+        harness.frame(vec![]);
+        let start = harness.center_of(0);
+        harness.frame(vec![Event::PointerMoved(start), button(start, true)]);
+        let pos = start + across * 10.0;
+        harness.frame(vec![Event::PointerMoved(pos)]);
+        let response = harness.frame(vec![Event::PointerMoved(pos)]);
+        assert!(response.is_dragging(), "{name}");
+        harness.frame(vec![button(pos, false)]);
+        harness.frame(vec![]);
+
+        // Swiping along the list is left to the ScrollArea
+        let start = harness.center_of(1);
+        harness.frame(vec![Event::PointerMoved(start), button(start, true)]);
+        let pos = start + along * 10.0;
+        for _ in 0..5 {
+            let response = harness.frame(vec![Event::PointerMoved(pos)]);
+            assert!(!response.is_dragging(), "{name}");
+        }
+        harness.frame(vec![button(pos, false)]);
+        assert_eq!(harness.order(), vec![0, 1, 2], "{name}");
+    }
+}
+
+#[test]
+fn hold_progress_reports_the_pressed_item() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 3]);
+        harness.config = DragDropConfig {
+            drag_delay: Duration::from_secs(60),
+            hold_indicator: true,
+            ..DragDropConfig::touch_scroll()
+        };
+        harness.frame(vec![]);
+        let pos = harness.center_of(1);
+        harness.frame(vec![Event::PointerMoved(pos), button(pos, true)]);
+        let response = harness.frame(vec![]);
+        let progress = response.hold_progress().map(|(_, progress)| progress);
+        assert!(
+            progress.is_some_and(|progress| (0.0..1.0).contains(&progress)),
+            "{name}"
+        );
+        harness.frame(vec![]);
+        let held: Vec<_> = harness
+            .states
+            .iter()
+            .filter(|(_, state)| state.hold_progress.is_some())
+            .map(|(id, _)| *id)
+            .collect();
+        assert_eq!(held, vec![1], "{name}");
+
+        let response = harness.frame(vec![button(pos, false)]);
+        assert!(!response.is_dragging(), "{name}");
+        let response = harness.frame(vec![]);
+        assert_eq!(response.hold_progress(), None, "{name}");
+    }
+}
+
+#[test]
+fn cancel_pointer_drag() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        let pos = harness.hold(0, 3);
+        assert_ne!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+        harness.frame(vec![key(Key::Escape)]);
+        assert_eq!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+        let response = harness.frame(vec![button(pos, false)]);
+        assert!(!response.is_drag_finished(), "{name}");
+        assert_eq!(
+            response.cancellation_reason(),
+            Some(CancellationReason::CancelKey),
+            "{name}"
+        );
+
+        harness.config.cancel_on_other_button = true;
+        let pos = harness.hold(4, 1);
+        let secondary = |pressed| Event::PointerButton {
+            pos,
+            button: PointerButton::Secondary,
+            pressed,
+            modifiers: Modifiers::NONE,
+        };
+        harness.frame(vec![secondary(true)]);
+        assert_eq!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+        let response = harness.frame(vec![button(pos, false), secondary(false)]);
+        assert_eq!(
+            response.cancellation_reason(),
+            Some(CancellationReason::OtherButtonPressed),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn middle_button_drag() {
+    let middle = |pos, pressed| Event::PointerButton {
+        pos,
+        button: PointerButton::Middle,
+        pressed,
+        modifiers: Modifiers::NONE,
+    };
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.config.drag_button = PointerButton::Middle;
+
+        // The primary button doesn't drag anymore
+        harness.drag(0, 3);
+        assert_eq!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+
+        let start = harness.center_of(0);
+        let target = harness.center_of(3);
+        harness.frame(vec![Event::PointerMoved(start), middle(start, true)]);
+        harness.frame(vec![Event::PointerMoved(
+            start + (target - start).normalized() * 4.0,
+        )]);
+        for _ in 0..4 {
+            harness.frame(vec![Event::PointerMoved(target)]);
+        }
+        let response = harness.frame(vec![middle(target, false)]);
+        assert!(response.is_drag_finished(), "{name}");
+        let mut dragged = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        dragged.drag(0, 3);
+        assert_eq!(harness.order(), dragged.order(), "{name}");
+    }
+}
+
+#[test]
+fn modifiers_at_drop() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    let pos = harness.hold(0, 3);
+    let response = harness.frame(vec![]);
+    assert!(!response.modifiers().alt);
+    harness.modifiers = Modifiers::ALT;
+    let response = harness.frame(vec![button(pos, false)]);
+    assert!(response.is_drag_finished());
+    assert!(response.modifiers().alt);
+    assert!(response.final_update().unwrap().modifiers.alt);
+}
+
+#[test]
+fn sticky_drag() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.config.sticky_drag = true;
+        harness.frame(vec![]);
+        let start = harness.center_of(1);
+        let target = harness.center_of(3);
+
+        // Click to pick the item up
+        harness.frame(vec![Event::PointerMoved(start), button(start, true)]);
+        let response = harness.frame(vec![button(start, false)]);
+        assert!(response.is_dragging(), "{name}");
+        for _ in 0..4 {
+            let response = harness.frame(vec![Event::PointerMoved(target)]);
+            assert!(response.is_dragging(), "{name}");
+        }
+        // Click again to drop it
+        let response = harness.frame(vec![button(target, true)]);
+        assert!(response.is_dragging(), "{name}");
+        let response = harness.frame(vec![button(target, false)]);
+        assert!(response.is_drag_finished(), "{name}");
+
+        let mut dragged = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        dragged.drag(1, 3);
+        assert_eq!(harness.order(), dragged.order(), "{name}");
+    }
+}
+
+#[test]
+fn item_context_menu_coexists_with_dragging() {
+    let items = RefCell::new(vec!["a", "b", "c"]);
+    let opened = RefCell::new(Vec::new());
+    let mut show = |ui: &mut egui::Ui| {
+        dnd(ui, "context_menu")
+            .on_item_context_menu(|ui, index| {
+                opened.borrow_mut().push(index);
+                ui.label("Rename");
+            })
+            .show_vec(&mut items.borrow_mut(), |ui, item, handle, _state| {
+                handle.ui(ui, |ui| {
+                    ui.label(*item);
+                });
+            })
+    };
+    let mut simulator = DragSimulator::new();
+
+    // Right clicking the handle opens the menu of its item without starting a drag
+    let pos = simulator.center_of(Id::new("b"), &mut show);
+    for pressed in [true, false] {
+        let event = Event::PointerButton {
+            pos,
+            button: PointerButton::Secondary,
+            pressed,
+            modifiers: Modifiers::NONE,
+        };
+        let response = simulator.frame(vec![Event::PointerMoved(pos), event], &mut show);
+        assert!(!response.is_dragging());
+    }
+    simulator.frame(vec![], &mut show);
+    assert!(opened.take().contains(&1));
+
+    // Dragging with the primary button doesn't open it
+    simulator.frame(vec![key(Key::Escape)], &mut show);
+    opened.take();
+    simulator.drag(Id::new("a"), Id::new("c"), &mut show);
+    simulator.frame(vec![], &mut show);
+    assert!(opened.take().is_empty());
+    assert_eq!(*items.borrow(), vec!["b", "c", "a"]);
+}
+
+#[test]
+fn block_interaction_stops_hover_outside_the_dragged_item() {
+    let hovered = RefCell::new(None);
+    let outside_rect = RefCell::new(Rect::NOTHING);
+    let mut items = vec!["a", "b", "c"];
+    let mut show = |ui: &mut egui::Ui| {
+        let response = dnd(ui, "block_interaction")
+            .with_mouse_config(DragDropConfig {
+                block_interaction: true,
+                ..DragDropConfig::mouse()
+            })
+            .show_vec(&mut items, |ui, item, handle, _state| {
+                handle.ui(ui, |ui| {
+                    ui.label(*item);
+                });
+            });
+        let outside = ui.allocate_response(Vec2::new(100.0, 20.0), Sense::hover());
+        *outside_rect.borrow_mut() = outside.rect;
+        *hovered.borrow_mut() = Some(outside.hovered());
+        response
+    };
+    let mut simulator = DragSimulator::new();
+
+    let start = simulator.center_of(Id::new("a"), &mut show);
+    let outside = outside_rect.borrow().center();
+    simulator.move_to(outside, &mut show);
+    assert_eq!(hovered.take(), Some(true));
+
+    // While dragging, the widget under the pointer isn't hovered
+    simulator.press(start, &mut show);
+    simulator.move_to(start + Vec2::new(0.0, 4.0), &mut show);
+    for _ in 0..3 {
+        let response = simulator.move_to(outside, &mut show);
+        assert!(response.is_dragging());
+    }
+    assert_eq!(hovered.take(), Some(false));
+
+    // After the drop, it reacts to the pointer again
+    simulator.release(outside, &mut show);
+    simulator.frame(vec![], &mut show);
+    simulator.move_to(outside, &mut show);
+    assert_eq!(hovered.take(), Some(true));
+}
+
+#[test]
+fn block_interaction_stays_below_the_dragged_item() {
+    for order in [Order::Middle, Order::Foreground, Order::Tooltip] {
+        let floating_item_layer = LayerId::new(order, Id::new("draggable_item"));
+        let mut items = vec!["a", "b", "c"];
+        let mut show = |ui: &mut egui::Ui| {
+            dnd(ui, "block_interaction_order")
+                .with_mouse_config(DragDropConfig {
+                    block_interaction: true,
+                    floating_item_layer,
+                    ..DragDropConfig::mouse()
+                })
+                .show_vec(&mut items, |ui, item, handle, _state| {
+                    handle.ui(ui, |ui| {
+                        ui.label(*item);
+                    });
+                })
+        };
+        let mut simulator = DragSimulator::new();
+
+        let start = simulator.center_of(Id::new("a"), &mut show);
+        simulator.press(start, &mut show);
+        for step in 1..4 {
+            let response = simulator.move_to(start + Vec2::new(0.0, 4.0 * step as f32), &mut show);
+            assert!(response.is_dragging(), "{order:?}");
+        }
+
+        let layers: Vec<LayerId> = simulator.ctx().memory(|mem| mem.layer_ids().collect());
+        let position = |id| layers.iter().position(|layer| layer.id == id).unwrap();
+        let blocker = position(Id::new("egui_dnd_interaction_blocker"));
+        assert_eq!(layers[blocker].order, order);
+        assert!(blocker < position(floating_item_layer.id), "{order:?}");
+    }
+}
+
+#[test]
+fn block_interaction_hides_the_pointer_from_drop_zones() {
+    for block_interaction in [false, true] {
+        let dropped = RefCell::new(None);
+        let zone_rect = RefCell::new(Rect::NOTHING);
+        let mut items = vec!["a", "b", "c"];
+        let mut show = |ui: &mut egui::Ui| {
+            let response = dnd(ui, "block_interaction_drop_zone")
+                .with_mouse_config(DragDropConfig {
+                    block_interaction,
+                    ..DragDropConfig::mouse()
+                })
+                .show_vec(&mut items, |ui, item, handle, _state| {
+                    handle.ui(ui, |ui| {
+                        ui.label(*item);
+                    });
+                });
+            let zone = ui.allocate_response(Vec2::new(100.0, 40.0), Sense::hover());
+            *zone_rect.borrow_mut() = zone.rect;
+            if let Some(payload) = zone.dnd_release_payload::<DndPayload>() {
+                *dropped.borrow_mut() = Some(payload.item);
+            }
+            response
+        };
+        let mut simulator = DragSimulator::new();
+
+        let start = simulator.center_of(Id::new("b"), &mut show);
+        let zone = zone_rect.borrow().center();
+        simulator.press(start, &mut show);
+        simulator.move_to(start + Vec2::new(0.0, 4.0), &mut show);
+        for _ in 0..3 {
+            simulator.move_to(zone, &mut show);
+        }
+        simulator.release(zone, &mut show);
+        // The zone only takes the payload if it's hovered, which the blocker prevents
+        let expected = (!block_interaction).then_some(Id::new("b"));
+        assert_eq!(dropped.take(), expected, "{block_interaction}");
+    }
+}
+
+#[test]
+fn ghost_only_copies_the_dragged_item() {
+    let ghost = |color: Color32| color.gamma_multiply(0.5);
+    let painted = RefCell::new(Vec::new());
+    let mut items = vec![Color32::RED, Color32::GREEN, Color32::BLUE];
+    let mut show = |ui: &mut egui::Ui| {
+        // Something else painted on the layer the dragged item is painted on
+        let floating_item_layer = DragDropConfig::mouse().floating_item_layer;
+        ui.ctx().layer_painter(floating_item_layer).rect_filled(
+            Rect::from_min_size(Pos2::ZERO, Vec2::splat(10.0)),
+            0.0,
+            Color32::YELLOW,
+        );
+        let response = dnd(ui, "ghost")
+            .with_mouse_config(DragDropConfig {
+                ghost_opacity: Some(0.5),
+                ..DragDropConfig::mouse()
+            })
+            .show_vec(&mut items, |ui, color, handle, _state| {
+                handle.ui(ui, |ui| {
+                    let (rect, _) = ui.allocate_exact_size(Vec2::splat(30.0), Sense::hover());
+                    ui.painter().rect_filled(rect, 0.0, *color);
+                });
+            });
+        let ghosts = [Color32::RED, Color32::GREEN, Color32::BLUE, Color32::YELLOW].map(ghost);
+        *painted.borrow_mut() = ui.ctx().graphics(|graphics| {
+            graphics
+                .get(ui.layer_id())
+                .into_iter()
+                .flat_map(|list| list.all_entries())
+                .filter_map(|clipped| match &clipped.shape {
+                    egui::Shape::Rect(rect) if ghosts.contains(&rect.fill) => Some(rect.fill),
+                    _ => None,
+                })
+                .collect()
+        });
+        response
+    };
+    let mut simulator = DragSimulator::new();
+
+    simulator.hold(Id::new(Color32::RED), Id::new(Color32::BLUE), &mut show);
+    assert_eq!(*painted.borrow(), vec![ghost(Color32::RED)]);
+}
+
+#[test]
+fn spring_open_expands_held_groups() {
+    let mut items = vec!["a", "group", "b"];
+    let log = RefCell::new(Vec::new());
+    let mut show = |ui: &mut egui::Ui| {
+        dnd(ui, "springs")
+            .drop_onto(|index| index == 1)
+            .spring_open(
+                Duration::from_millis(250),
+                |index| index == 1,
+                |_, index| log.borrow_mut().push(format!("open {index}")),
+            )
+            .on_spring_close(|_, index| log.borrow_mut().push(format!("close {index}")))
+            .show_vec(&mut items, |ui, item, handle, _state| {
+                handle.ui(ui, |ui| {
+                    ui.label(*item);
+                });
+            })
+    };
+    let mut simulator = DragSimulator::new();
+
+    // Holding an item onto the group opens it once the delay passed
+    let group = simulator.hold(Id::new("a"), Id::new("group"), &mut show);
+    assert_eq!(log.take(), vec!["open 1"]);
+
+    // Leaving the list closes it again
+    let outside = Pos2::new(900.0, 900.0);
+    simulator.move_to(outside, &mut show);
+    simulator.release(outside, &mut show);
+    assert_eq!(log.take(), vec!["close 1"]);
+
+    // Dropping the item keeps the group open
+    simulator.hold(Id::new("a"), Id::new("group"), &mut show);
+    simulator.release(group, &mut show);
+    simulator.frame(vec![], &mut show);
+    assert_eq!(log.take(), vec!["open 1"]);
+}
+
+#[test]
+fn locked_items() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.locked = Some(|index| index == 2);
+
+        // Locked items can't be dragged
+        harness.drag(2, 0);
+        assert_eq!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+
+        // Other items can't be moved past them
+        harness.drag(0, 4);
+        assert_eq!(harness.order(), vec![1, 0, 2, 3, 4], "{name}");
+        harness.drag(4, 0);
+        assert_eq!(harness.order(), vec![1, 0, 2, 4, 3], "{name}");
+    }
+}
+
+#[test]
+fn non_draggable_items() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.draggable = Some(|index| index != 2);
+
+        // The item can't be picked up
+        harness.drag(2, 0);
+        assert_eq!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+        assert!(harness.callbacks.take().is_empty(), "{name}");
+
+        // But other items can be moved past it
+        harness.drag(0, 4);
+        let order = harness.order();
+        let moved_to = moved_index(&[0, 1, 2, 3, 4], &order, 0);
+        assert!(
+            moved_to.is_some_and(|index| index >= 2),
+            "{name}: {order:?}"
+        );
+        assert_eq!(order.iter().position(|id| *id == 2), Some(1), "{name}");
+    }
+}
+
+#[test]
+fn disabled() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.frame(vec![]);
+        let rects = harness.rects.clone();
+
+        // The items look the same but can't be dragged
+        harness.enabled = false;
+        harness.frame(vec![]);
+        assert_eq!(harness.rects, rects, "{name}");
+
+        harness.drag(0, 3);
+        assert_eq!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+
+        // Disabling while dragging puts the item back
+        harness.enabled = true;
+        let pos = harness.hold(0, 3);
+        harness.enabled = false;
+        harness.frame(vec![]);
+        harness.frame(vec![button(pos, false)]);
+        assert_eq!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+        assert_eq!(
+            harness.callbacks.take().last().map(String::as_str),
+            Some("cancel Disabled"),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn fill_item_yields_to_widgets() {
+    for (name, layout) in layouts() {
+        let mut plain = Harness::new(layout, &[Vec2::splat(40.0); 5]);
+        plain.drag(1, 3);
+        let mut harness = Harness::new(layout, &[Vec2::splat(40.0); 5]);
+        harness.fill_item = true;
+
+        // Pressing anywhere but the widget drags the item
+        harness.drag(1, 3);
+        assert_eq!(harness.order(), plain.order(), "{name}");
+
+        // Pressing the widget drags the widget instead of the item
+        let order = harness.order();
+        let corner =
+            harness.rects.iter().find(|(id, _)| *id == 0).unwrap().1.min + Vec2::splat(2.0);
+        let target = harness.center_of(4);
+        harness.frame(vec![Event::PointerMoved(corner), button(corner, true)]);
+        for _ in 0..4 {
+            harness.frame(vec![Event::PointerMoved(target)]);
+        }
+        assert!(
+            harness
+                .ctx
+                .memory(|mem| mem.is_being_dragged(Id::new(("widget", 0)))),
+            "{name}"
+        );
+        let response = harness.frame(vec![button(target, false)]);
+        harness.frame(vec![]);
+        assert_eq!(harness.order(), order, "{name}");
+        assert_eq!(
+            response.cancellation_reason(),
+            Some(CancellationReason::WidgetPressed),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn fill_item_in_overflowing_scroll_area() {
+    let mut plain = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(40.0); 8]);
+    plain.scroll_height = Some(150.0);
+    plain.drag(0, 2);
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(40.0); 8]);
+    harness.scroll_height = Some(150.0);
+    harness.fill_item = true;
+
+    // The ScrollArea senses drags to scroll, which doesn't make the item yield to it
+    let response = harness.drag(0, 2);
+    assert_eq!(response.cancellation_reason(), None);
+    assert_eq!(harness.order(), plain.order());
+    assert_ne!(harness.order(), (0..8).collect::<Vec<_>>());
+}
+
+#[test]
+fn fill_item_in_movable_window() {
+    let mut items = vec!["a", "b", "c"];
+    let mut show = |ui: &mut egui::Ui| {
+        let ctx = ui.ctx().clone();
+        egui::Window::new("list")
+            .show(&ctx, |ui| {
+                dnd(ui, "fill_item_window").show_vec(&mut items, |ui, item, handle, _state| {
+                    handle.fill_item(ui, |ui| {
+                        ui.label(*item);
+                    });
+                })
+            })
+            .and_then(|response| response.inner)
+            .expect("the window is closed")
+    };
+    let mut simulator = DragSimulator::new();
+
+    // Pressing the item moves the item, not the window
+    let response = simulator.drag(Id::new("a"), Id::new("c"), &mut show);
+    assert_eq!(response.cancellation_reason(), None);
+    simulator.frame(vec![], &mut show);
+    assert_eq!(items, vec!["b", "c", "a"]);
+}
+
+#[test]
+fn disabled_handle() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.handle_enabled = Some(|id| id != 2);
+
+        harness.drag(2, 0);
+        assert_eq!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+        assert!(harness.callbacks.take().is_empty(), "{name}");
+
+        // Other items can still be moved past it
+        harness.drag(3, 0);
+        assert_eq!(
+            harness.order().iter().position(|id| *id == 2),
+            Some(3),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn focus_loss_behavior() {
+    let mut dragged = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    dragged.drag(1, 3);
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+
+    // By default, the drag goes on once the window is focused again
+    assert_eq!(harness.config.focus_loss, FocusLossBehavior::Hold);
+    let pos = harness.hold(1, 3);
+    harness.focused = false;
+    assert!(harness.frame(vec![]).is_dragging());
+    harness.focused = true;
+    let response = harness.frame(vec![button(pos, false)]);
+    assert!(response.is_drag_finished());
+    assert_eq!(harness.order(), dragged.order());
+
+    // The item is dropped where it was hovering when the focus was lost
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.config.focus_loss = FocusLossBehavior::Drop;
+    harness.hold(1, 3);
+    harness.focused = false;
+    assert!(harness.frame(vec![]).is_drag_finished());
+    assert_eq!(harness.order(), dragged.order());
+
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.config.pointer_left_window = FocusLossBehavior::Drop;
+    harness.hold(1, 3);
+    assert!(harness.frame(vec![Event::PointerGone]).is_drag_finished());
+    assert_eq!(harness.order(), dragged.order());
+
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.config.pointer_left_window = FocusLossBehavior::Cancel;
+    let pos = harness.hold(1, 3);
+    harness.frame(vec![Event::PointerGone]);
+    let response = harness.frame(vec![Event::PointerMoved(pos), button(pos, false)]);
+    assert_eq!(
+        response.cancellation_reason(),
+        Some(CancellationReason::WindowUnfocused)
+    );
+    assert_eq!(harness.order(), vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn double_tap_and_hold() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.config.double_tap_and_hold = Some(Duration::from_secs(5));
+
+        // A plain press and drag doesn't move the item
+        let response = harness.drag(0, 3);
+        assert_eq!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+        assert_eq!(
+            response.cancellation_reason(),
+            Some(CancellationReason::NotDoubleTapped),
+            "{name}"
+        );
+
+        // Tap first, then press again and drag
+        let start = harness.center_of(0);
+        harness.frame(vec![Event::PointerMoved(start), button(start, true)]);
+        harness.frame(vec![button(start, false)]);
+        let response = harness.drag(0, 3);
+        assert!(response.is_drag_finished(), "{name}");
+        let mut dragged = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        dragged.drag(0, 3);
+        assert_eq!(harness.order(), dragged.order(), "{name}");
+    }
+}
+
+#[test]
+fn wheel_scroll_while_dragging() {
+    // The wheel is forwarded to the ScrollArea of the list even if it's blocked
+    for block_interaction in [false, true] {
+        let layout = Layout::top_down(Align::Min);
+        let mut harness = Harness::new(layout, &[Vec2::new(120.0, 30.0); 20]);
+        harness.scroll_height = Some(150.0);
+        harness.config.block_interaction = block_interaction;
+        let pos = harness.hold(0, 2);
+        let mut dragged = Harness::new(layout, &[Vec2::new(120.0, 30.0); 20]);
+        dragged.scroll_height = Some(150.0);
+        dragged.drag(0, 2);
+
+        // Scroll down by a few items while holding the item in place
+        for _ in 0..3 {
+            harness.frame(vec![
+                Event::PointerMoved(pos),
+                Event::Scroll(Vec2::new(0.0, -50.0)),
+            ]);
+        }
+        for _ in 0..10 {
+            harness.frame(vec![Event::PointerMoved(pos)]);
+        }
+        let response = harness.frame(vec![button(pos, false)]);
+        assert!(response.is_drag_finished(), "{block_interaction}");
+
+        let original = (0..20).collect::<Vec<_>>();
+        let without_scroll = moved_index(&original, &dragged.order(), 0).unwrap();
+        let with_scroll = moved_index(&original, &harness.order(), 0).unwrap();
+        assert!(
+            with_scroll > without_scroll,
+            "the item should land further down after scrolling, {with_scroll} <= {without_scroll}, {block_interaction}"
+        );
+    }
+}
+
+#[test]
+fn wheel_scroll_outside_the_list_while_dragging() {
+    let offset = RefCell::new(0.0);
+    let area_rect = RefCell::new(Rect::NOTHING);
+    let mut items = vec!["a", "b", "c"];
+    let mut show = |ui: &mut egui::Ui| {
+        let response = dnd(ui, "wheel_outside").show_vec(&mut items, |ui, item, handle, _state| {
+            handle.ui(ui, |ui| {
+                ui.label(*item);
+            });
+        });
+        let output = ScrollArea::vertical()
+            .id_source("other")
+            .max_height(100.0)
+            .show(ui, |ui| {
+                for line in 0..50 {
+                    ui.label(format!("line {line}"));
+                }
+            });
+        *offset.borrow_mut() = output.state.offset.y;
+        *area_rect.borrow_mut() = output.inner_rect;
+        response
+    };
+    let mut simulator = DragSimulator::new();
+
+    let start = simulator.center_of(Id::new("a"), &mut show);
+    let area = area_rect.borrow().center();
+    simulator.press(start, &mut show);
+    simulator.move_to(start + Vec2::new(0.0, 4.0), &mut show);
+    simulator.move_to(area, &mut show);
+    let scroll = vec![
+        Event::PointerMoved(area),
+        Event::Scroll(Vec2::new(0.0, -50.0)),
+    ];
+    assert!(simulator.frame(scroll, &mut show).is_dragging());
+    for _ in 0..10 {
+        simulator.move_to(area, &mut show);
+    }
+
+    // The list isn't in a ScrollArea, so the wheel scrolls the area under the pointer
+    assert!(*offset.borrow() > 0.0);
+}
+
+#[test]
+fn programmatic_scroll_while_dragging() {
+    let layout = Layout::top_down(Align::Min);
+    let mut harness = Harness::new(layout, &[Vec2::new(120.0, 30.0); 20]);
+    harness.scroll_height = Some(150.0);
+    harness.begin_drag = Some((2, DragMode::Keyboard));
+    harness.frame(vec![]);
+    harness.frame(vec![]);
+    let gap = harness.center_of(3).y - harness.center_of(2).y;
+
+    // The app scrolls the list, the dragged item stays in the gap
+    harness.scroll_offset = Some(30.0);
+    harness.frame(vec![]);
+    assert_eq!(harness.center_of(3).y - harness.center_of(2).y, gap);
+
+    let response = harness.press(&[Key::ArrowDown, Key::Enter]);
+    assert!(response.is_drag_finished());
+    assert_eq!(&harness.order()[..4], &[0, 1, 3, 2]);
+}
+
+#[test]
+fn auto_scroll_near_edge() {
+    let layout = Layout::top_down(Align::Min);
+    let original = (0..20).collect::<Vec<_>>();
+    let mut landed = Vec::new();
+    for auto_scroll in [None, Some(AutoScroll::default())] {
+        let mut harness = Harness::new(layout, &[Vec2::new(120.0, 30.0); 20]);
+        harness.scroll_height = Some(150.0);
+        harness.config.auto_scroll = auto_scroll;
+        // Item 4 is cut off by the bottom edge of the ScrollArea
+        let pos = harness.hold(0, 4);
+        for _ in 0..10 {
+            harness.frame(vec![Event::PointerMoved(pos)]);
+        }
+        harness.frame(vec![button(pos, false)]);
+        landed.push(moved_index(&original, &harness.order(), 0).unwrap());
+    }
+    assert!(
+        landed[1] > landed[0],
+        "the list should scroll while holding the item near the edge, {landed:?}"
+    );
+}
@@ -0,0 +1,705 @@
+//! Tests for the response, the events and the state a list keeps between frames.
+
+mod common;
+
+use std::cell::RefCell;
+
+use egui::{Align, CentralPanel, Context, Event, Id, Key, Layout, Pos2, RawInput, Rect, Vec2};
+
+use egui_dnd::{
+    dnd,
+    test_utils::{DragSimulator, DragSnapshot},
+    utils::clear_dnd_state,
+    CancellationReason, CommitStrategy, Dnd, DndEvent, DndSortable, DragDropResponse, DragMode,
+    DropDecision, DropPosition, FocusLossBehavior,
+};
+
+use common::{button, key, layouts, Harness, TestItem};
+
+#[test]
+fn feedback_events_fire_once() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.frame(vec![]);
+        let start = harness.center_of(0);
+        let target = harness.center_of(3);
+
+        let mut responses =
+            vec![harness.frame(vec![Event::PointerMoved(start), button(start, true)])];
+        responses.push(harness.frame(vec![Event::PointerMoved(
+            start + (target - start).normalized() * 4.0,
+        )]));
+        for _ in 0..4 {
+            responses.push(harness.frame(vec![Event::PointerMoved(target)]));
+        }
+        responses.push(harness.frame(vec![button(target, false)]));
+        responses.push(harness.frame(vec![]));
+
+        let count = |f: fn(&DragDropResponse) -> bool| responses.iter().filter(|r| f(r)).count();
+        assert_eq!(count(DragDropResponse::is_drag_started), 1, "{name}");
+        assert_eq!(count(DragDropResponse::has_slot_changed), 1, "{name}");
+        assert_eq!(count(DragDropResponse::is_drag_finished), 1, "{name}");
+    }
+}
+
+#[test]
+fn hovering_index() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        assert_eq!(harness.frame(vec![]).hovering_index(), None, "{name}");
+        let pos = harness.hold(0, 3);
+        let response = harness.frame(vec![Event::PointerMoved(pos)]);
+        assert_eq!(response.dragged_item_id(), Some(Id::new(0)), "{name}");
+        let hovering_index = response.hovering_index();
+
+        harness.frame(vec![button(pos, false)]);
+        assert_eq!(
+            hovering_index,
+            harness.order().iter().position(|id| *id == 0),
+            "{name}"
+        );
+        assert_eq!(harness.frame(vec![]).hovering_index(), None, "{name}");
+    }
+}
+
+#[test]
+fn events() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.frame(vec![]);
+    let start = harness.center_of(0);
+    let target = harness.center_of(3);
+
+    let mut events = Vec::new();
+    let mut frame = |harness: &mut Harness, event_list| {
+        events.extend(harness.frame(event_list).events().iter().cloned());
+    };
+    frame(
+        &mut harness,
+        vec![Event::PointerMoved(start), button(start, true)],
+    );
+    frame(
+        &mut harness,
+        vec![Event::PointerMoved(start + Vec2::new(0.0, 4.0))],
+    );
+    for _ in 0..4 {
+        frame(&mut harness, vec![Event::PointerMoved(target)]);
+    }
+    frame(&mut harness, vec![button(target, false)]);
+    frame(&mut harness, vec![]);
+
+    let id = Id::new(0);
+    assert!(
+        matches!(
+            events.as_slice(),
+            [
+                DndEvent::DragStarted { id: started, index: 0 },
+                DndEvent::TargetChanged { id: moved, from: 0, to: 3 },
+                DndEvent::Dropped { id: dropped, update },
+            ] if *started == id && *moved == id && *dropped == id && update.target_index() == 3
+        ),
+        "{events:?}"
+    );
+}
+
+#[test]
+fn merge_reports_items_dropped_onto_each_other() {
+    let items = RefCell::new(vec!["a", "b", "c"]);
+    let merged = RefCell::new(Vec::new());
+    let mut show = |ui: &mut egui::Ui| {
+        dnd(ui, "merge")
+            .merge(|_, index| index != 1)
+            .on_merge(|source, target| merged.borrow_mut().push((source, target)))
+            .show_vec(&mut items.borrow_mut(), |ui, item, handle, _state| {
+                handle.ui(ui, |ui| {
+                    ui.label(*item);
+                });
+            })
+    };
+    let mut simulator = DragSimulator::new();
+
+    // Dropping an item onto another one merges them without reordering the list
+    let response = simulator.drag(Id::new("a"), Id::new("c"), &mut show);
+    assert!(matches!(
+        response.events(),
+        [
+            DndEvent::Dropped { .. },
+            DndEvent::Merge {
+                source: 0,
+                target: 2,
+                ..
+            }
+        ]
+    ));
+    assert_eq!(merged.take(), vec![(0, 2)]);
+    assert_eq!(*items.borrow(), vec!["a", "b", "c"]);
+
+    // Items that can't be merged with are moved around as usual
+    simulator.frame(vec![], &mut show);
+    simulator.drag(Id::new("a"), Id::new("b"), &mut show);
+    simulator.frame(vec![], &mut show);
+    assert!(merged.take().is_empty());
+    assert_eq!(*items.borrow(), vec!["b", "a", "c"]);
+}
+
+#[test]
+fn keyed() {
+    for (name, layout) in layouts() {
+        let mut hashed = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        hashed.drag(1, 3);
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.keyed = true;
+        harness.drag(1, 3);
+        assert_eq!(harness.order(), hashed.order(), "{name}");
+
+        // Changing the dragged item doesn't change its id, so the drag continues
+        hashed.drag(2, 0);
+        let pos = harness.hold(2, 0);
+        let dragged = harness.items.iter_mut().find(|item| item.id == 2).unwrap();
+        dragged.size.x += 0.5;
+        harness.frame(vec![]);
+        harness.frame(vec![button(pos, false)]);
+        assert_eq!(harness.order(), hashed.order(), "{name}");
+    }
+}
+
+#[test]
+fn item_state() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.frame(vec![]);
+    let start = harness.center_of(0);
+    let pos = harness.hold(0, 2);
+    harness.frame(vec![]);
+
+    let state = |harness: &Harness, id| &harness.states.iter().find(|(i, _)| *i == id).unwrap().1;
+    let dragged = state(&harness, 0);
+    assert_eq!(dragged.target_index, 2);
+    // The item is picked up once the pointer moved past the click threshold
+    assert!((dragged.drag_delta - (pos - start)).length() <= 4.0);
+    assert!(!dragged.is_displaced);
+    for (id, target_index, is_displaced) in [(1, 0, true), (2, 1, true), (3, 3, false)] {
+        let state = state(&harness, id);
+        assert_eq!(state.target_index, target_index, "{id}");
+        assert_eq!(state.is_displaced, is_displaced, "{id}");
+        assert_eq!(state.drag_delta, Vec2::ZERO, "{id}");
+    }
+
+    harness.frame(vec![button(pos, false)]);
+    harness.frame(vec![]);
+    for (id, state) in &harness.states {
+        assert!(!state.is_displaced, "{id}");
+        assert_eq!(state.target_index, state.index, "{id}");
+    }
+
+    harness.focus(1);
+    harness.frame(vec![]);
+    let selected: Vec<_> = harness
+        .states
+        .iter()
+        .filter(|(_, state)| state.selected)
+        .map(|(id, _)| *id)
+        .collect();
+    assert_eq!(selected, vec![harness.order()[1]]);
+}
+
+#[test]
+fn forget_state() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 3]);
+
+    // Forgetting the state drops the drag in progress
+    let pos = harness.hold(0, 2);
+    Dnd::forget(&harness.ctx, "layout_test");
+    let response = harness.frame(vec![button(pos, false)]);
+    assert!(!response.is_drag_finished());
+
+    let pos = harness.hold(1, 0);
+    clear_dnd_state(&harness.ctx);
+    let response = harness.frame(vec![button(pos, false)]);
+    assert!(!response.is_drag_finished());
+
+    // The list is registered again when its state is recreated, so it can be forgotten again
+    let pos = harness.hold(2, 0);
+    Dnd::forget(&harness.ctx, "layout_test");
+    let response = harness.frame(vec![button(pos, false)]);
+    assert!(!response.is_drag_finished());
+}
+
+#[test]
+fn lists_scoped_by_ui() {
+    let ctx = Context::default();
+    let mut lists = [vec![0_usize, 1, 2], vec![0, 1, 2]];
+    let mut run = |begin_drag: bool, global: bool| {
+        let mut dragging = Vec::new();
+        let _ = ctx.run(RawInput::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                for (side, items) in lists.iter_mut().enumerate() {
+                    ui.push_id(side, |ui| {
+                        let mut dnd = if global {
+                            Dnd::global(ui, "shared")
+                        } else {
+                            dnd(ui, "shared")
+                        };
+                        if begin_drag && side == 0 {
+                            dnd = dnd.begin_drag(Id::new(0_usize), DragMode::Keyboard);
+                        }
+                        let response = dnd.show_vec(items, |ui, item, handle, _state| {
+                            handle.ui(ui, |ui| {
+                                ui.label(item.to_string());
+                            });
+                        });
+                        dragging.push(response.is_dragging());
+                    });
+                }
+            });
+        });
+        dragging
+    };
+
+    // Lists with the same id source in different uis don't share a drag
+    run(true, false);
+    assert_eq!(run(false, false), vec![true, false]);
+
+    // Unless they opt out of the scoping
+    run(true, true);
+    assert_eq!(run(false, true), vec![true, true]);
+}
+
+#[test]
+fn persist_order() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 4]);
+    harness.persist_order = Some("order");
+    harness.drag(0, 2);
+    let sorted = harness.order();
+    assert_ne!(sorted, vec![0, 1, 2, 3]);
+
+    // Like after a restart, the list starts out in the original order, with an item added
+    harness.items.sort_by_key(|item| item.id);
+    harness.items.insert(
+        0,
+        TestItem {
+            id: 4,
+            size: Vec2::splat(30.0),
+        },
+    );
+    Dnd::forget(&harness.ctx, "layout_test");
+    harness.frame(vec![]);
+    let mut expected = sorted;
+    expected.push(4);
+    assert_eq!(harness.order(), expected);
+
+    // The order is only restored once, so the app can still reorder the items itself
+    harness.items.reverse();
+    expected.reverse();
+    harness.frame(vec![]);
+    assert_eq!(harness.order(), expected);
+}
+
+#[test]
+fn undo_redo() {
+    for commit_strategy in [CommitStrategy::Live, CommitStrategy::OnDrop] {
+        let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+        harness.commit_strategy = commit_strategy;
+        harness.history = Some(2);
+        let response = harness.frame(vec![]);
+        assert!(!response.can_undo(), "{commit_strategy:?}");
+
+        harness.drag(0, 3);
+        let first = harness.order();
+        harness.drag(4, 1);
+        let second = harness.order();
+        harness.drag(2, 0);
+        let response = harness.frame(vec![]);
+        assert!(
+            response.can_undo() && !response.can_redo(),
+            "{commit_strategy:?}"
+        );
+
+        // Only the last two moves are remembered
+        assert!(response.undo(&mut harness.items), "{commit_strategy:?}");
+        assert_eq!(harness.order(), second, "{commit_strategy:?}");
+        assert!(response.undo(&mut harness.items), "{commit_strategy:?}");
+        assert_eq!(harness.order(), first, "{commit_strategy:?}");
+        assert!(!response.undo(&mut harness.items), "{commit_strategy:?}");
+
+        assert!(response.redo(&mut harness.items), "{commit_strategy:?}");
+        assert_eq!(harness.order(), second, "{commit_strategy:?}");
+
+        // A new move forgets the undone ones
+        harness.drag(1, 2);
+        let response = harness.frame(vec![]);
+        assert!(!response.can_redo(), "{commit_strategy:?}");
+    }
+}
+
+#[test]
+fn duplicate_ids() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 3]);
+    let response = harness.frame(vec![]);
+    assert!(response.duplicate_ids().is_empty());
+
+    harness.items[2].id = 0;
+    let response = harness.frame(vec![]);
+    // Duplicates are only detected in debug builds
+    let expected = usize::from(cfg!(debug_assertions));
+    assert_eq!(response.duplicate_ids().len(), expected);
+}
+
+#[test]
+fn drop_position() {
+    // The id of a shown item only depends on the id of the TestItem
+    let item_id = |id: usize| {
+        Id::new(TestItem {
+            id,
+            size: Vec2::ZERO,
+        })
+    };
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        for (source, target) in [(1, 3), (3, 0), (0, 4)] {
+            let response = harness.drag(source, target);
+            let update = response.final_update().expect("no final update");
+            let order = harness.order();
+            let index = update.target_index();
+            assert_eq!(update.id, item_id(source), "{name}");
+            assert_eq!(order[index], source, "{name}");
+            let expected = match order.get(index + 1) {
+                Some(next) => DropPosition::Before(item_id(*next)),
+                None => DropPosition::After(item_id(order[index - 1])),
+            };
+            assert_eq!(update.position, expected, "{name}");
+            assert!(update.pointer_pos.is_some(), "{name}");
+        }
+    }
+}
+
+#[test]
+fn commit_on_drop() {
+    for (name, layout) in layouts() {
+        let mut live = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        live.drag(0, 3);
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.commit_strategy = CommitStrategy::OnDrop;
+
+        // The items are untouched until the item is dropped
+        let pos = harness.hold(0, 3);
+        assert_eq!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+        harness.frame(vec![button(pos, false)]);
+        assert_eq!(harness.order(), live.order(), "{name}");
+
+        // Cancelling leaves them untouched as well
+        let order = harness.order();
+        let pos = harness.hold(1, 4);
+        harness.frame(vec![key(Key::Escape)]);
+        harness.frame(vec![button(pos, false)]);
+        assert_eq!(harness.order(), order, "{name}");
+    }
+}
+
+#[test]
+fn apply_to_custom_model() {
+    /// Records the moves like an undo command stack, without knowing how many items there are
+    struct Moves(Vec<(usize, usize)>);
+
+    impl DndSortable for Moves {
+        fn move_item(&mut self, from: usize, to: usize) {
+            self.0.push((from, to));
+        }
+    }
+
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.commit_strategy = CommitStrategy::OnDrop;
+    let pos = harness.hold(0, 3);
+    let response = harness.frame(vec![button(pos, false)]);
+    let update = response.final_update().expect("no final update");
+    let mut moves = Moves(Vec::new());
+    response.apply(&mut moves);
+    assert_eq!(moves.0, vec![(update.from, update.to)]);
+
+    // Every DndContainer can be passed to apply as well
+    let mut items = vec![0, 1, 2, 3, 4];
+    response.apply(&mut items);
+    assert_eq!(items, harness.order());
+}
+
+#[test]
+fn slot_delta() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.focus(1);
+        assert_eq!(harness.press(&[Key::Space]).slot_delta(), 0, "{name}");
+        assert_eq!(harness.press(&[Key::ArrowDown]).slot_delta(), 1, "{name}");
+        // Nothing moved in this frame
+        assert_eq!(harness.frame(vec![]).slot_delta(), 0, "{name}");
+        assert_eq!(harness.press(&[Key::ArrowUp]).slot_delta(), -1, "{name}");
+        harness.press(&[Key::Escape]);
+
+        // With the pointer, the item can cross several slots at once
+        harness.frame(vec![]);
+        let start = harness.center_of(0);
+        let target = harness.center_of(3);
+        harness.frame(vec![Event::PointerMoved(start), button(start, true)]);
+        let nudged = start + (target - start).normalized() * 4.0;
+        harness.frame(vec![Event::PointerMoved(nudged)]);
+        harness.frame(vec![Event::PointerMoved(nudged)]);
+        let delta: isize = (0..4)
+            .map(|_| {
+                harness
+                    .frame(vec![Event::PointerMoved(target)])
+                    .slot_delta()
+            })
+            .sum();
+        assert_eq!(delta, 3, "{name}");
+    }
+}
+
+#[test]
+fn current_order() {
+    for (name, layout) in layouts() {
+        let mut live = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        live.hold(0, 3);
+        let response = live.frame(vec![]);
+        // The live list already matches what is shown
+        assert_eq!(response.current_order(), vec![0, 1, 2, 3, 4], "{name}");
+
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.commit_strategy = CommitStrategy::OnDrop;
+        assert_eq!(harness.frame(vec![]).current_order(), vec![0, 1, 2, 3, 4]);
+        harness.hold(0, 3);
+        let response = harness.frame(vec![]);
+        let preview: Vec<usize> = response
+            .current_order()
+            .into_iter()
+            .map(|idx| harness.items[idx].id)
+            .collect();
+        assert_eq!(preview, live.order(), "{name}");
+        assert_eq!(harness.order(), vec![0, 1, 2, 3, 4], "{name}");
+    }
+}
+
+#[test]
+fn cancellation_reasons() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    let release = |harness: &mut Harness, pos| {
+        let response = harness.frame(vec![button(pos, false)]);
+        harness.frame(vec![]);
+        response
+    };
+
+    let message = CancellationReason::Custom("No permission".to_owned());
+    let pos = harness.hold(0, 3);
+    harness.cancel_drag = Some(message.clone());
+    harness.frame(vec![]);
+    let response = release(&mut harness, pos);
+    assert_eq!(response.cancellation_reason(), Some(message));
+    assert_eq!(harness.order(), vec![0, 1, 2, 3, 4]);
+
+    harness.config.focus_loss = FocusLossBehavior::Cancel;
+    let pos = harness.hold(1, 3);
+    harness.focused = false;
+    harness.frame(vec![]);
+    harness.focused = true;
+    let response = release(&mut harness, pos);
+    assert_eq!(
+        response.cancellation_reason(),
+        Some(CancellationReason::WindowUnfocused)
+    );
+    assert_eq!(harness.order(), vec![0, 1, 2, 3, 4]);
+
+    // Removing the dragged item cancels the drag without moving any other item
+    let pos = harness.hold(2, 4);
+    harness.items.retain(|item| item.id != 2);
+    let order = harness.order();
+    let response = harness.frame(vec![]);
+    assert!(!response.is_dragging());
+    assert!(response.update.is_none());
+    assert!(matches!(
+        response.events(),
+        [DndEvent::Cancelled {
+            reason: CancellationReason::ListChanged,
+            ..
+        }]
+    ));
+    release(&mut harness, pos);
+    assert_eq!(harness.order(), order);
+}
+
+#[test]
+fn vetoed_drop() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.validate_drop = Some(|update| {
+        if update.target_index() == 0 {
+            DropDecision::Veto
+        } else {
+            DropDecision::Accept
+        }
+    });
+
+    // The released item keeps following the pointer
+    let pos = harness.hold(2, 0);
+    let response = harness.frame(vec![button(pos, false)]);
+    assert!(response.is_drop_vetoed());
+    assert!(response.is_dragging());
+    assert!(!response.is_drag_finished());
+    assert!(matches!(
+        response.events(),
+        [DndEvent::DropVetoed { update, .. }] if update.target_index() == 0
+    ));
+    for _ in 0..4 {
+        harness.frame(vec![]);
+    }
+    assert!(harness.frame(vec![]).is_dragging());
+    assert_eq!(harness.order(), vec![2, 0, 1, 3, 4]);
+
+    // The next click drops it where the veto doesn't apply
+    let target = harness.center_of(3);
+    for _ in 0..4 {
+        harness.frame(vec![Event::PointerMoved(target)]);
+    }
+    harness.frame(vec![button(target, true)]);
+    let response = harness.frame(vec![button(target, false)]);
+    assert!(response.is_drag_finished());
+    assert_eq!(harness.order(), vec![0, 1, 3, 2, 4]);
+    // The vetoed drop isn't reported as a drop
+    let callbacks = harness.callbacks.take();
+    assert_eq!(
+        callbacks
+            .iter()
+            .filter(|event| event.starts_with("drop"))
+            .collect::<Vec<_>>(),
+        vec!["drop 3"]
+    );
+
+    // Keyboard drags continue
+    harness.focus(3);
+    let response = harness.press(&[
+        Key::Space,
+        Key::ArrowUp,
+        Key::ArrowUp,
+        Key::ArrowUp,
+        Key::Enter,
+    ]);
+    assert!(response.is_drop_vetoed());
+    assert!(response.is_dragging());
+    let response = harness.press(&[Key::ArrowDown, Key::Enter]);
+    assert!(response.is_drag_finished());
+    assert_eq!(harness.order(), vec![0, 2, 1, 3, 4]);
+}
+
+#[test]
+fn item_responses() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 3]);
+    let response = harness.frame(vec![]);
+    assert_eq!(response.item_responses().len(), 3);
+    for id in 0..3 {
+        let item_response = response
+            .item_response(Id::new(id))
+            .expect("no item response");
+        assert_eq!(item_response.rect.center(), harness.center_of(id));
+    }
+
+    // Clicks on the row are reported without starting a drag
+    let pos = harness.center_of(1);
+    harness.frame(vec![Event::PointerMoved(pos), button(pos, true)]);
+    let response = harness.frame(vec![button(pos, false)]);
+    assert!(!response.is_dragging());
+    assert!(response.item_response(Id::new(1)).unwrap().clicked());
+    assert!(!response.item_response(Id::new(0)).unwrap().clicked());
+
+    // Tabbing through the list still only focuses the handles
+    harness.focus(0);
+    let response = harness.press(&[Key::Space]);
+    assert!(response.is_dragging());
+}
+
+#[test]
+fn lifecycle_callbacks() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.drag(1, 3);
+    assert_eq!(
+        harness.callbacks.take(),
+        vec!["start 1", "hover 3", "drop 3"]
+    );
+
+    let pos = harness.hold(0, 2);
+    harness.frame(vec![key(Key::Escape)]);
+    harness.frame(vec![button(pos, false)]);
+    assert_eq!(
+        harness.callbacks.take(),
+        vec!["start 0", "hover 2", "cancel CancelKey"]
+    );
+
+    // Clicking somewhere else doesn't call any callback
+    harness.frame(vec![button(Pos2::new(500.0, 500.0), true)]);
+    harness.frame(vec![button(Pos2::new(500.0, 500.0), false)]);
+    assert!(harness.callbacks.take().is_empty());
+}
+
+#[test]
+fn begin_drag() {
+    for (name, layout) in layouts() {
+        let mut harness = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        harness.frame(vec![]);
+        let start = harness.center_of(1);
+        let target = harness.center_of(3);
+
+        harness.begin_drag = Some((1, DragMode::Pointer));
+        harness.frame(vec![Event::PointerMoved(start)]);
+        // The item follows the pointer without a button being held
+        for _ in 0..4 {
+            let response = harness.frame(vec![Event::PointerMoved(target)]);
+            assert!(response.is_dragging(), "{name}");
+        }
+        harness.frame(vec![button(target, true)]);
+        let response = harness.frame(vec![button(target, false)]);
+        assert!(response.is_drag_finished(), "{name}");
+        // The item lands where a regular drag would have dropped it
+        let mut dragged = Harness::new(layout, &[Vec2::splat(30.0); 5]);
+        dragged.drag(1, 3);
+        let mut expected = dragged.order();
+        assert_eq!(harness.order(), expected, "{name}");
+
+        harness.begin_drag = Some((0, DragMode::Keyboard));
+        harness.frame(vec![]);
+        let response = harness.press(&[Key::ArrowDown, Key::Enter]);
+        assert!(response.is_drag_finished(), "{name}");
+        expected.swap(0, 1);
+        assert_eq!(harness.order(), expected, "{name}");
+    }
+}
+
+#[test]
+fn snapshot_drag() {
+    let layout = Layout::top_down(Align::Min);
+    let mut harness = Harness::new(layout, &[Vec2::new(120.0, 30.0); 4]);
+    harness.frame(vec![]);
+    let start = harness.rects.clone();
+    let rect_of =
+        |rects: &[(usize, Rect)], id: usize| rects.iter().find(|(item, _)| *item == id).unwrap().1;
+
+    // Hover: item 0 is held at an offset from the gap after item 2, without any input
+    let offset = Vec2::new(15.0, -5.0);
+    harness.snapshot = Some(DragSnapshot::new(Id::new(0_usize), 2).with_offset(offset));
+    for _ in 0..2 {
+        harness.frame(vec![]);
+    }
+    let response = harness.frame(vec![]);
+    assert!(response.is_dragging());
+    assert_eq!(rect_of(&harness.rects, 1), rect_of(&start, 0));
+    assert_eq!(rect_of(&harness.rects, 2), rect_of(&start, 1));
+    assert_eq!(rect_of(&harness.rects, 3), rect_of(&start, 3));
+    assert_eq!(
+        rect_of(&harness.rects, 0).min,
+        rect_of(&start, 2).min + offset
+    );
+    // The state is the same in every frame
+    let rects = harness.rects.clone();
+    harness.frame(vec![]);
+    assert_eq!(harness.rects, rects);
+
+    // Settle: the item is dropped at the slot
+    harness.snapshot = Some(DragSnapshot::new(Id::new(0_usize), 2).settle());
+    let response = harness.frame(vec![]);
+    assert!(response.is_drag_finished());
+    harness.snapshot = None;
+    harness.frame(vec![]);
+    assert_eq!(harness.order(), vec![1, 2, 0, 3]);
+}
@@ -0,0 +1,105 @@
+//! Tests for the helpers in [egui_dnd::utils].
+
+use egui::Id;
+use proptest::prelude::*;
+
+use egui_dnd::utils::{diff_order, shift_many, shift_range, shift_vec};
+
+proptest! {
+    #[test]
+    fn diff_order_finds_fewest_moves(
+        new in Just((0..12usize).collect::<Vec<_>>()).prop_shuffle(),
+    ) {
+        let old: Vec<Id> = (0..new.len()).map(Id::new).collect();
+        let new: Vec<Id> = new.into_iter().map(Id::new).collect();
+        let moves = diff_order(&old, &new);
+
+        let mut order = old.clone();
+        for step in &moves {
+            prop_assert_eq!(order[step.from], step.id);
+            let id = order.remove(step.from);
+            order.insert(step.to, id);
+        }
+        prop_assert_eq!(&order, &new);
+
+        // Every item outside of the longest run that kept its relative order has to move once
+        let positions: Vec<usize> = new.iter().map(|id| old.iter().position(|item| item == id).unwrap()).collect();
+        let mut longest = vec![1; positions.len()];
+        for idx in 0..positions.len() {
+            for before in 0..idx {
+                if positions[before] < positions[idx] {
+                    longest[idx] = longest[idx].max(longest[before] + 1);
+                }
+            }
+        }
+        let kept = longest.into_iter().max().unwrap_or(0);
+        prop_assert_eq!(moves.len(), new.len() - kept);
+    }
+
+    #[test]
+    fn shift_range_matches_moving_items_one_by_one(
+        (len, start, end, target) in (1..12usize).prop_flat_map(|len| {
+            (Just(len), 0..len).prop_flat_map(|(len, start)| {
+                (Just(len), Just(start), start + 1..=len, 0..=len)
+            })
+        }),
+    ) {
+        let mut shifted: Vec<usize> = (0..len).collect();
+        shift_range(start..end, target, &mut shifted);
+
+        let mut expected: Vec<usize> = (0..len).collect();
+        if target < start || target > end {
+            let moved: Vec<usize> = expected.drain(start..end).collect();
+            let insert_at = if target > end { target - moved.len() } else { target };
+            for (offset, item) in moved.into_iter().enumerate() {
+                expected.insert(insert_at + offset, item);
+            }
+        }
+        prop_assert_eq!(shifted, expected);
+    }
+
+    #[test]
+    fn shift_many_matches_moving_items_one_by_one(
+        (len, sources, targets) in (1..12usize).prop_flat_map(|len| {
+            (
+                Just(len),
+                Just((0..len).collect::<Vec<_>>()).prop_shuffle(),
+                prop::collection::vec(0..=len, len),
+            )
+        }),
+        count in 0..12usize,
+    ) {
+        let moves: Vec<(usize, usize)> = sources
+            .into_iter()
+            .zip(targets)
+            .take(count)
+            .collect();
+        let mut shifted: Vec<usize> = (0..len).collect();
+        shift_many(&moves, &mut shifted);
+
+        // Take the moved items out, then insert each one in front of the item that was at its
+        // target, behind the items that were moved there before
+        let is_moved = |idx: &usize| moves.iter().any(|(source, _)| source == idx);
+        let mut expected: Vec<usize> = (0..len).filter(|idx| !is_moved(idx)).collect();
+        for (source, target) in &moves {
+            let at = expected
+                .iter()
+                .position(|item| match moves.iter().find(|(moved, _)| moved == item) {
+                    Some((_, moved_target)) => moved_target > target,
+                    None => item >= target,
+                })
+                .unwrap_or(expected.len());
+            expected.insert(at, *source);
+        }
+        prop_assert_eq!(shifted, expected);
+
+        // A single move is the same as shift_vec
+        if let Some((source, target)) = moves.first() {
+            let mut single: Vec<usize> = (0..len).collect();
+            shift_many(&[(*source, *target)], &mut single);
+            let mut expected: Vec<usize> = (0..len).collect();
+            shift_vec(*source, *target, &mut expected);
+            prop_assert_eq!(single, expected);
+        }
+    }
+}
@@ -0,0 +1,725 @@
+//! Tests for the prebuilt widgets, the debug overlay and the inspector.
+
+mod common;
+
+use std::cell::RefCell;
+use std::time::Duration;
+
+use egui::{
+    Align, CentralPanel, Context, Event, Id, Key, Layout, Modifiers, PointerButton, Pos2, RawInput,
+    Rect, Sense, Vec2,
+};
+
+use egui_dnd::{
+    dnd, dnd_calendar, dnd_command_list, dnd_file_list, dnd_form_builder, dnd_gallery,
+    dnd_layer_panel, dnd_playlist, dnd_sidebar, dnd_tabs, dnd_todo_list, test_utils::DragSimulator,
+    CalendarMove, Dnd, FileEntry, Layer, RankedList, TodoEvent,
+};
+
+use common::{button, key, Harness};
+
+#[test]
+fn drag_simulator() {
+    let mut items = vec![0_usize, 1, 2, 3];
+    let mut simulator = DragSimulator::new();
+    let response = simulator.drag(Id::new(3_usize), Id::new(0_usize), |ui| {
+        dnd(ui, "simulated").show_vec(&mut items, |ui, _item, handle, _state| {
+            handle.ui(ui, |ui| {
+                ui.allocate_exact_size(Vec2::new(120.0, 30.0), Sense::hover());
+            });
+        })
+    });
+    assert!(response.is_drag_finished());
+    assert_eq!(
+        response.final_update().map(|update| update.id),
+        Some(Id::new(3_usize))
+    );
+    assert_eq!(items, vec![3, 0, 1, 2]);
+}
+
+#[test]
+fn dnd_tabs_select_reorder_close() {
+    let mut tabs = vec!["alfred", "bernhard", "christian"];
+    let last = RefCell::new(None);
+    let show = |ui: &mut egui::Ui, tabs: &mut Vec<&'static str>, select: Option<&str>| {
+        let mut strip = dnd_tabs(ui, "tabs");
+        if let Some(tab) = select {
+            strip = strip.select(Id::new(tab));
+        }
+        let response = strip.show(tabs, |tab| (*tab).into());
+        last.replace(Some((
+            response.active,
+            response.active_changed,
+            response.closed,
+        )));
+        response.response
+    };
+    let mut simulator = DragSimulator::new();
+
+    // The first tab is active from the start, that's not a change
+    simulator.frame(vec![], |ui| show(ui, &mut tabs, None));
+    assert_eq!(last.take(), Some((Some(0), false, None)));
+    simulator.frame(vec![], |ui| show(ui, &mut tabs, Some("christian")));
+    assert_eq!(last.take(), Some((Some(2), true, None)));
+
+    // The active tab stays active while it's moved
+    let response = simulator.drag(Id::new("christian"), Id::new("alfred"), |ui| {
+        show(ui, &mut tabs, None)
+    });
+    assert!(response.is_drag_finished());
+    assert_eq!(tabs, vec!["christian", "alfred", "bernhard"]);
+    assert_eq!(last.take(), Some((Some(0), false, None)));
+
+    // Closing the active tab activates the one after it
+    let pos = simulator.center_of(Id::new("christian"), |ui| show(ui, &mut tabs, None));
+    for pressed in [true, false] {
+        let event = Event::PointerButton {
+            pos,
+            button: PointerButton::Middle,
+            pressed,
+            modifiers: Modifiers::NONE,
+        };
+        simulator.frame(vec![Event::PointerMoved(pos), event], |ui| {
+            show(ui, &mut tabs, None)
+        });
+    }
+    assert_eq!(tabs, vec!["alfred", "bernhard"]);
+    assert_eq!(last.take(), Some((Some(0), true, Some((0, "christian")))));
+}
+
+#[test]
+fn dnd_tabs_reorder_in_overflowing_strip() {
+    let mut tabs = vec![
+        "one", "two", "three", "four", "five", "six", "seven", "eight",
+    ];
+    let show = |ui: &mut egui::Ui, tabs: &mut Vec<&'static str>| {
+        dnd_tabs(ui, "tabs")
+            .show(tabs, |tab| (*tab).into())
+            .response
+    };
+    let mut simulator = DragSimulator::new().with_screen_size(Vec2::new(200.0, 100.0));
+
+    // The tabs don't fit, so the strip can be scrolled
+    let last = simulator.center_of(Id::new("eight"), |ui| show(ui, &mut tabs));
+    assert!(last.x > 200.0, "the strip doesn't overflow: {last:?}");
+
+    // Dragging a tab moves it instead of scrolling the strip
+    let first = simulator.center_of(Id::new("one"), |ui| show(ui, &mut tabs));
+    let response = simulator.drag(Id::new("two"), Id::new("one"), |ui| show(ui, &mut tabs));
+    assert!(response.is_drag_finished());
+    assert_eq!(&tabs[..3], ["two", "one", "three"]);
+    // Let the tab settle at its new place
+    for _ in 0..10 {
+        simulator.frame(vec![], |ui| show(ui, &mut tabs));
+    }
+    let moved = simulator.center_of(Id::new("two"), |ui| show(ui, &mut tabs));
+    // The tabs are about as wide, the strip would have moved by the length of the drag
+    assert!(
+        (moved.x - first.x).abs() < 5.0,
+        "the strip scrolled: {first:?} {moved:?}"
+    );
+}
+
+#[test]
+fn dnd_command_list_reorders_with_handle() {
+    let mut steps = vec![("copy", true), ("paste", false), ("save", true)];
+    let mut show = |ui: &mut egui::Ui| {
+        dnd_command_list(ui, "commands").show_toggleable(
+            &mut steps,
+            |step| step.0,
+            |step| step.0.into(),
+            |step| &mut step.1,
+        )
+    };
+    let mut simulator = DragSimulator::new();
+    simulator.frame(vec![], &mut show);
+    let row = |simulator: &DragSimulator, key: &str| {
+        simulator
+            .response()
+            .and_then(|response| response.item_response(Id::new(key)))
+            .unwrap()
+            .rect
+    };
+
+    // Only the handle in front of the row picks it up
+    let start = row(&simulator, "copy").left_center() + Vec2::new(4.0, 0.0);
+    let end = row(&simulator, "save").center();
+    simulator.press(start, &mut show);
+    simulator.move_to(start + Vec2::new(0.0, 4.0), &mut show);
+    for _ in 0..4 {
+        simulator.move_to(end, &mut show);
+    }
+    let response = simulator.release(end, &mut show);
+    assert!(response.is_drag_finished());
+    assert_eq!(
+        steps,
+        vec![("paste", false), ("save", true), ("copy", true)]
+    );
+}
+
+#[test]
+fn dnd_playlist_queues_onto_now_playing() {
+    let mut tracks = vec!["a", "b", "c", "d", "e"];
+    let mut playing = Some(1);
+    let mut queued = false;
+    let mut simulator = DragSimulator::new();
+    let mut drag_onto_playing =
+        |tracks: &mut Vec<&str>, playing: &mut Option<usize>, track: &str| {
+            let mut show = |ui: &mut egui::Ui| {
+                let response = dnd_playlist(ui, "playlist")
+                    .now_playing(*playing)
+                    .show(tracks, |track| (*track).into());
+                *playing = response.now_playing;
+                queued = response.queued_next;
+                response.response
+            };
+            simulator.frame(vec![], &mut show);
+            let rect_of = |simulator: &DragSimulator, track: &str| {
+                simulator
+                    .response()
+                    .and_then(|response| response.item_response(Id::new(track)))
+                    .unwrap()
+                    .rect
+            };
+            // Press the handle in front of the track
+            let start = rect_of(&simulator, track).left_center() + Vec2::new(4.0, 0.0);
+            let end = rect_of(&simulator, "b").center();
+            simulator.press(start, &mut show);
+            simulator.move_to(start + (end - start).normalized() * 4.0, &mut show);
+            for _ in 0..4 {
+                simulator.move_to(end, &mut show);
+            }
+            simulator.release(end, &mut show);
+            queued
+        };
+
+    // The playing track stays in place, so a track from further down lands right behind it
+    assert!(drag_onto_playing(&mut tracks, &mut playing, "e"));
+    assert_eq!(tracks, vec!["a", "b", "e", "c", "d"]);
+    assert_eq!(playing, Some(1));
+
+    // A track that was played already is queued again
+    assert!(drag_onto_playing(&mut tracks, &mut playing, "a"));
+    assert_eq!(tracks, vec!["b", "a", "e", "c", "d"]);
+    assert_eq!(playing, Some(0));
+}
+
+#[test]
+fn ranked_list_renumbers_while_dragging() {
+    let mut items = vec!["a", "b", "c"];
+    let ranks = RefCell::new(Vec::new());
+    let mut show = |ui: &mut egui::Ui| {
+        ranks.borrow_mut().clear();
+        RankedList::new(ui, "ranking").show(&mut items, |ui, item, rank| {
+            ui.allocate_exact_size(Vec2::new(120.0, 30.0), Sense::hover());
+            ranks.borrow_mut().push((*item, rank));
+        })
+    };
+    let mut simulator = DragSimulator::new();
+    simulator.frame(vec![], &mut show);
+    assert_eq!(*ranks.borrow(), vec![("a", 1), ("b", 2), ("c", 3)]);
+
+    // While "a" is held over "c", the ranks already show the order after the drop
+    let rect_of = |simulator: &DragSimulator, item: &str| {
+        simulator
+            .response()
+            .and_then(|response| response.item_response(Id::new(item)))
+            .unwrap()
+            .rect
+    };
+    let start = rect_of(&simulator, "a").left_center() + Vec2::new(4.0, 0.0);
+    let end = rect_of(&simulator, "c").center();
+    simulator.press(start, &mut show);
+    simulator.move_to(start + Vec2::new(0.0, 4.0), &mut show);
+    for _ in 0..4 {
+        simulator.move_to(end, &mut show);
+    }
+    let mut held = ranks.borrow().clone();
+    held.sort();
+    assert_eq!(held, vec![("a", 3), ("b", 1), ("c", 2)]);
+    let response = simulator.release(end, &mut show);
+    assert!(response.is_drag_finished());
+    simulator.frame(vec![], &mut show);
+    assert_eq!(*ranks.borrow(), vec![("b", 1), ("c", 2), ("a", 3)]);
+}
+
+#[test]
+fn dnd_file_list_moves_copies_and_opens_folders() {
+    let files = RefCell::new(vec![
+        FileEntry::folder("docs", vec![FileEntry::file("a.txt")]),
+        FileEntry::file("b.txt"),
+        FileEntry::file("c.txt"),
+    ]);
+    let mut show = |ui: &mut egui::Ui| {
+        dnd_file_list(ui, "files")
+            .spring_delay(Duration::from_millis(150))
+            .show(&mut files.borrow_mut())
+            .response
+    };
+    let rows = |simulator: &DragSimulator| {
+        let mut rects: Vec<Rect> = simulator
+            .response()
+            .unwrap()
+            .item_responses()
+            .values()
+            .map(|response| response.rect)
+            .collect();
+        rects.sort_by(|a, b| a.min.y.total_cmp(&b.min.y));
+        rects
+    };
+    let mut simulator = DragSimulator::new();
+    simulator.frame(vec![], &mut show);
+
+    // Dropping a file onto the closed folder moves it into the folder
+    let start = rows(&simulator)[1].center();
+    let folder = rows(&simulator)[0].center();
+    simulator.press(start, &mut show);
+    simulator.move_to(start + (folder - start).normalized() * 4.0, &mut show);
+    for _ in 0..4 {
+        simulator.move_to(folder, &mut show);
+    }
+    simulator.release(folder, &mut show);
+    assert_eq!(
+        *files.borrow(),
+        vec![
+            FileEntry::folder(
+                "docs",
+                vec![FileEntry::file("a.txt"), FileEntry::file("b.txt")]
+            ),
+            FileEntry::file("c.txt"),
+        ]
+    );
+
+    // Holding a file over the folder opens it, dropping it with ctrl held copies it
+    simulator.frame(vec![], &mut show);
+    assert_eq!(rows(&simulator).len(), 2);
+    simulator.set_modifiers(Modifiers::COMMAND);
+    let start = rows(&simulator)[1].center();
+    simulator.press(start, &mut show);
+    simulator.move_to(start + (folder - start).normalized() * 4.0, &mut show);
+    for _ in 0..4 {
+        simulator.move_to(folder, &mut show);
+    }
+    assert_eq!(rows(&simulator).len(), 4);
+    let response = simulator.release(folder, &mut show);
+    assert!(response.is_drag_finished());
+    assert_eq!(
+        *files.borrow(),
+        vec![
+            FileEntry::folder(
+                "docs",
+                vec![
+                    FileEntry::file("a.txt"),
+                    FileEntry::file("b.txt"),
+                    FileEntry::file("c.txt"),
+                ]
+            ),
+            FileEntry::file("c.txt"),
+        ]
+    );
+}
+
+#[test]
+fn dnd_form_builder_spawns_fields_from_palette() {
+    let fields = RefCell::new(Vec::<String>::new());
+    let rects = RefCell::new((Vec::new(), Rect::NOTHING));
+    let spawned = RefCell::new(Vec::new());
+    let mut show = |ui: &mut egui::Ui| {
+        let mut fields = fields.borrow_mut();
+        let count = fields.len();
+        let response = dnd_form_builder(ui, "form").show(
+            &["Text", "Checkbox"],
+            |kind| (*kind).into(),
+            &mut *fields,
+            |kind| format!("{kind} {}", count + 1),
+            |ui, field| {
+                ui.label(field.as_str());
+            },
+        );
+        *rects.borrow_mut() = (
+            response.palette.iter().map(|entry| entry.rect).collect(),
+            response.canvas.rect,
+        );
+        spawned.borrow_mut().extend(response.spawned);
+        response.response
+    };
+    let mut simulator = DragSimulator::new();
+    simulator.frame(vec![], &mut show);
+    let mut drag_to = |simulator: &mut DragSimulator, entry: usize, target: Pos2| {
+        let start = rects.borrow().0[entry].center();
+        simulator.press(start, &mut show);
+        simulator.move_to(start + (target - start).normalized() * 4.0, &mut show);
+        for _ in 0..4 {
+            simulator.move_to(target, &mut show);
+        }
+        simulator.release(target, &mut show);
+        simulator.frame(vec![], &mut show);
+    };
+
+    // The first field is dropped onto the placeholder of the empty form
+    let canvas = rects.borrow().1.center();
+    drag_to(&mut simulator, 0, canvas);
+    assert_eq!(*fields.borrow(), vec!["Text 1"]);
+    assert_eq!(*spawned.borrow(), vec![0]);
+
+    // Dropping onto the upper half of a field inserts the new field before it
+    let field = simulator
+        .response()
+        .unwrap()
+        .item_responses()
+        .values()
+        .next()
+        .unwrap()
+        .rect;
+    drag_to(&mut simulator, 1, field.center() - Vec2::new(0.0, 2.0));
+    assert_eq!(*fields.borrow(), vec!["Checkbox 2", "Text 1"]);
+    assert_eq!(*spawned.borrow(), vec![0, 0]);
+    // The palette entries stay in the palette
+    assert_eq!(rects.borrow().0.len(), 2);
+}
+
+#[test]
+fn dnd_gallery_moves_selection_together() {
+    let photos = RefCell::new((0..6).collect::<Vec<usize>>());
+    let selected = RefCell::new(Vec::new());
+    let mut show = |ui: &mut egui::Ui| {
+        let response = dnd_gallery(ui, "gallery")
+            .thumbnail_size(Vec2::splat(40.0))
+            .show(&mut photos.borrow_mut(), |ui, photo| {
+                ui.label(photo.to_string());
+            });
+        *selected.borrow_mut() = response.selected;
+        response.response
+    };
+    let mut click = |simulator: &mut DragSimulator, photo: usize, modifiers: Modifiers| {
+        let pos = simulator.center_of(Id::new(photo), &mut show);
+        simulator.set_modifiers(modifiers);
+        simulator.press(pos, &mut show);
+        simulator.release(pos, &mut show);
+        simulator.set_modifiers(Modifiers::NONE);
+        simulator.frame(vec![], &mut show);
+    };
+    let mut simulator = DragSimulator::new();
+
+    // A click selects a single photo, ctrl + click adds to the selection
+    click(&mut simulator, 4, Modifiers::NONE);
+    assert_eq!(*selected.borrow(), vec![4]);
+    click(&mut simulator, 1, Modifiers::NONE);
+    click(&mut simulator, 3, Modifiers::COMMAND);
+    assert_eq!(*selected.borrow(), vec![1, 3]);
+
+    // Dragging a selected photo moves the whole selection, keeping its order
+    let response = simulator.drag(Id::new(3), Id::new(0), &mut show);
+    assert!(response.is_drag_finished());
+    assert_eq!(*photos.borrow(), vec![1, 3, 0, 2, 4, 5]);
+    assert_eq!(*selected.borrow(), vec![0, 1]);
+
+    // An unselected photo is moved on its own
+    simulator.drag(Id::new(5), Id::new(2), &mut show);
+    assert_eq!(*photos.borrow(), vec![1, 3, 0, 5, 2, 4]);
+}
+
+#[test]
+fn dnd_todo_list_completes_on_done_band() {
+    let todos = RefCell::new(vec![("milk", false), ("plants", false), ("taxes", false)]);
+    let band = RefCell::new(Rect::NOTHING);
+    let events = RefCell::new(Vec::new());
+    let mut show = |ui: &mut egui::Ui| {
+        let response = dnd_todo_list(ui, "todos").show(
+            &mut todos.borrow_mut(),
+            |todo| todo.0,
+            |todo| todo.0.into(),
+            |todo| &mut todo.1,
+        );
+        *band.borrow_mut() = response.band.rect;
+        events.borrow_mut().extend(response.events);
+        response.response
+    };
+    let mut drag_to = |simulator: &mut DragSimulator, todo: &str, target: Pos2| {
+        // The handle is at the left of the row
+        let start = simulator
+            .response()
+            .and_then(|response| response.item_response(Id::new(todo)))
+            .unwrap()
+            .rect
+            .left_center()
+            + Vec2::new(4.0, 0.0);
+        simulator.press(start, &mut show);
+        simulator.move_to(start + (target - start).normalized() * 4.0, &mut show);
+        for _ in 0..4 {
+            simulator.move_to(target, &mut show);
+        }
+        simulator.release(target, &mut show);
+    };
+    let mut simulator = DragSimulator::new();
+    simulator.frame(vec![], &mut show);
+
+    // Dropping a todo onto the band completes it without moving it
+    let target = band.borrow().center();
+    drag_to(&mut simulator, "milk", target);
+    assert_eq!(
+        *events.borrow(),
+        vec![TodoEvent::Completed(Id::new("milk"))]
+    );
+    assert_eq!(
+        *todos.borrow(),
+        vec![("milk", true), ("plants", false), ("taxes", false)]
+    );
+
+    // Dropping it in the list moves it
+    events.borrow_mut().clear();
+    let target = simulator
+        .response()
+        .and_then(|response| response.item_response(Id::new("milk")))
+        .unwrap()
+        .rect
+        .center();
+    drag_to(&mut simulator, "taxes", target);
+    assert_eq!(
+        *events.borrow(),
+        vec![TodoEvent::Moved {
+            id: Id::new("taxes"),
+            from: 2,
+            to: 0
+        }]
+    );
+    assert_eq!(
+        *todos.borrow(),
+        vec![("taxes", false), ("milk", true), ("plants", false)]
+    );
+}
+
+#[test]
+fn dnd_layer_panel_moves_selection_into_group() {
+    let layers = RefCell::new(vec![
+        Layer::group(1, "group", vec![Layer::new(2, "inside")]),
+        Layer::new(3, "sky"),
+        Layer::new(4, "sun"),
+    ]);
+    let selected = RefCell::new(Vec::new());
+    let mut show = |ui: &mut egui::Ui| {
+        let response = dnd_layer_panel(ui, "layers").show(&mut layers.borrow_mut());
+        *selected.borrow_mut() = response.selected;
+        response.response
+    };
+    // The eye button is at the left of each row, the name at the right
+    let row = |simulator: &DragSimulator, layer: usize| {
+        simulator
+            .response()
+            .and_then(|response| response.item_response(Id::new(layer)))
+            .unwrap()
+            .rect
+    };
+    let mut simulator = DragSimulator::new();
+    simulator.frame(vec![], &mut show);
+
+    // Clicking the eye toggles the visibility without picking the layer up
+    let eye = row(&simulator, 3).left_center() + Vec2::new(6.0, 0.0);
+    simulator.press(eye, &mut show);
+    assert!(!simulator.release(eye, &mut show).is_drag_finished());
+    assert!(!layers.borrow()[1].visible);
+
+    // Select both layers and drop them onto the group
+    let sky = row(&simulator, 3).right_center() - Vec2::new(4.0, 0.0);
+    simulator.press(sky, &mut show);
+    simulator.release(sky, &mut show);
+    let sun = row(&simulator, 4).right_center() - Vec2::new(4.0, 0.0);
+    simulator.set_modifiers(Modifiers::COMMAND);
+    simulator.press(sun, &mut show);
+    simulator.release(sun, &mut show);
+    simulator.set_modifiers(Modifiers::NONE);
+    assert_eq!(*selected.borrow(), vec![Id::new(3), Id::new(4)]);
+
+    let group = row(&simulator, 1).center();
+    simulator.press(sun, &mut show);
+    simulator.move_to(sun + (group - sun).normalized() * 4.0, &mut show);
+    for _ in 0..4 {
+        simulator.move_to(group, &mut show);
+    }
+    simulator.release(group, &mut show);
+    let mut sky = Layer::new(3, "sky");
+    sky.visible = false;
+    assert_eq!(
+        *layers.borrow(),
+        vec![Layer::group(
+            1,
+            "group",
+            vec![Layer::new(2, "inside"), sky, Layer::new(4, "sun")]
+        )]
+    );
+    assert_eq!(*selected.borrow(), vec![Id::new(3), Id::new(4)]);
+}
+
+#[test]
+fn dnd_calendar_moves_cards_between_days() {
+    let days = RefCell::new(vec![vec!["a", "b"], vec!["c"], vec![]]);
+    let rects = RefCell::new(std::collections::HashMap::new());
+    let columns = RefCell::new(Vec::new());
+    let moved = RefCell::new(None);
+    let mut show = |ui: &mut egui::Ui| {
+        let mut response = dnd_calendar(ui, "week").show(
+            &mut days.borrow_mut(),
+            |day| format!("Day {day}").into(),
+            |ui, event| {
+                ui.label(*event);
+            },
+        );
+        rects.borrow_mut().extend(
+            response
+                .days
+                .iter()
+                .flat_map(|day| day.item_responses().iter())
+                .map(|(id, response)| (*id, response.rect)),
+        );
+        *columns.borrow_mut() = response.columns.iter().map(|column| column.rect).collect();
+        *moved.borrow_mut() = response.moved;
+        response.days.swap_remove(0)
+    };
+    let mut drag_to = |simulator: &mut DragSimulator, event: &str, target: Pos2| {
+        let start = rects.borrow()[&Id::new(event)].center();
+        rects.borrow_mut().clear();
+        simulator.press(start, &mut show);
+        simulator.move_to(start + (target - start).normalized() * 4.0, &mut show);
+        for _ in 0..4 {
+            simulator.move_to(target, &mut show);
+        }
+        simulator.release(target, &mut show);
+    };
+    let mut simulator = DragSimulator::new();
+    simulator.frame(vec![], &mut show);
+
+    // Onto the upper half of a card of another day
+    let target = rects.borrow()[&Id::new("c")].center() - Vec2::new(0.0, 4.0);
+    drag_to(&mut simulator, "a", target);
+    assert_eq!(*days.borrow(), vec![vec!["b"], vec!["a", "c"], vec![]]);
+    let expected = CalendarMove {
+        from_day: 0,
+        to_day: 1,
+        index: 0,
+    };
+    assert_eq!(*moved.borrow(), Some(expected));
+
+    // Onto the placeholder of an empty day
+    let target = columns.borrow()[2].center_bottom() - Vec2::new(0.0, 8.0);
+    drag_to(&mut simulator, "b", target);
+    assert_eq!(*days.borrow(), vec![vec![], vec!["a", "c"], vec!["b"]]);
+    let expected = CalendarMove {
+        from_day: 0,
+        to_day: 2,
+        index: 0,
+    };
+    assert_eq!(*moved.borrow(), Some(expected));
+
+    // Within the same day
+    let target = rects.borrow()[&Id::new("a")].center();
+    drag_to(&mut simulator, "c", target);
+    assert_eq!(*days.borrow(), vec![vec![], vec!["c", "a"], vec!["b"]]);
+    let expected = CalendarMove {
+        from_day: 1,
+        to_day: 1,
+        index: 0,
+    };
+    assert_eq!(*moved.borrow(), Some(expected));
+}
+
+#[test]
+fn dnd_sidebar_reorders_and_persists_sections() {
+    let sections = RefCell::new(vec!["Explorer", "Search", "Git"]);
+    let mut show = |ui: &mut egui::Ui| {
+        dnd_sidebar(ui, "sidebar").show(
+            &mut sections.borrow_mut(),
+            |section| (*section).into(),
+            |ui, section| {
+                ui.label(*section);
+            },
+        )
+    };
+    let mut simulator = DragSimulator::new();
+    simulator.drag(Id::new("Git"), Id::new("Explorer"), &mut show);
+    assert_eq!(*sections.borrow(), vec!["Git", "Explorer", "Search"]);
+
+    // Like after a restart, the stored order is restored
+    *sections.borrow_mut() = vec!["Explorer", "Search", "Git"];
+    Dnd::forget(simulator.ctx(), Id::new("sidebar"));
+    simulator.frame(vec![], &mut show);
+    assert_eq!(*sections.borrow(), vec!["Git", "Explorer", "Search"]);
+}
+
+#[test]
+fn debug_overlay() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    let pos = harness.hold(0, 2);
+    let shapes = harness.output.shapes.len();
+    harness.frame(vec![Event::PointerMoved(pos), key(Key::Escape)]);
+    harness.frame(vec![button(pos, false)]);
+
+    harness.debug = true;
+    let pos = harness.hold(0, 2);
+    assert!(harness.output.shapes.len() > shapes);
+    let response = harness.frame(vec![button(pos, false)]);
+    assert!(response.is_drag_finished());
+}
+
+#[test]
+fn inspector_lists_state() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    let pos = harness.hold(0, 2);
+
+    // The inspector can be shown in another context, like a debug viewport
+    let inspector_ctx = Context::default();
+    let output = inspector_ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| egui_dnd::inspector(ui, &harness.ctx));
+    });
+    assert!(!output.shapes.is_empty());
+
+    let response = harness.frame(vec![button(pos, false)]);
+    assert!(response.is_drag_finished());
+}
+
+#[test]
+fn inspector_counts_only_the_shown_item_sizes() {
+    let mut harness = Harness::new(Layout::top_down(Align::Min), &[Vec2::splat(30.0); 5]);
+    harness.config.lock_cross_axis_size = true;
+    harness.frame(vec![]);
+    // The sizes of removed items are forgotten
+    harness.items.truncate(2);
+    harness.frame(vec![]);
+
+    let inspector_ctx = Context::default();
+    let texts = |events: Vec<Event>| {
+        let output = inspector_ctx.run(
+            RawInput {
+                events,
+                ..Default::default()
+            },
+            |ctx| {
+                CentralPanel::default().show(ctx, |ui| egui_dnd::inspector(ui, &harness.ctx));
+            },
+        );
+        output
+            .shapes
+            .into_iter()
+            .filter_map(|clipped| match clipped.shape {
+                egui::Shape::Text(text) => Some((text.galley.text().to_owned(), text.pos)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    let (_, header) = texts(vec![])
+        .into_iter()
+        .find(|(text, _)| text.starts_with("List "))
+        .expect("list not shown");
+    let click = header + Vec2::splat(2.0);
+    texts(vec![Event::PointerMoved(click), button(click, true)]);
+    texts(vec![button(click, false)]);
+    // Wait for the header to open
+    for _ in 0..10 {
+        texts(vec![]);
+    }
+    let texts = texts(vec![]);
+    let label = texts
+        .iter()
+        .position(|(text, _)| text == "Measured item sizes")
+        .expect("state not shown");
+    assert_eq!(texts[label + 1].0, "2");
+}
@@ -227,6 +227,21 @@ impl<T: Debug + Send + Sync + 'static, Cursor: Clone + Debug + Send + 'static>
         self.virtual_list.reset();
     }
 
+    /// The index in [InfiniteScroll::items] of the item at the given index of the filtered items,
+    /// which is the index passed to the item ui. Without a filter, this is the same index.
+    pub fn unfiltered_index(&self, index: usize) -> Option<usize> {
+        if let Some(filter) = &self.filter {
+            self.items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| filter(*item))
+                .nth(index)
+                .map(|(idx, _)| idx)
+        } else {
+            Some(index).filter(|index| *index < self.items.len())
+        }
+    }
+
     fn read_inboxes(&mut self, ui: &mut Ui) {
         self.bottom_inbox.read(ui).for_each(|state| {
             self.bottom_loading_state = match state {